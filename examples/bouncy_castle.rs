@@ -150,6 +150,7 @@ fn main() {
         clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
         is_running: false,
         gravitational_constant: 0.0,
+        ..Default::default()
     };
     run(rigidbodies, springs, vec![], vec![], parameters);
 }
@@ -0,0 +1,57 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    // Two fixed towers the bridge hangs between
+    for x in [-4.25, 4.25] {
+        let mut tower = Rigidbody::rectangle(0.5, 3.0, Vec2 { x, y: -1.5 }, f32::MAX / 10000.0, 0.3, ColorRGBA::gray());
+        tower.gravity_multiplier = 0.0;
+        polygons.push(tower);
+    }
+
+    // Planks overlapping at their ends so neighbours can be pinned together
+    let plank_count = 8;
+    let plank_width = 1.1;
+    for i in 0..plank_count {
+        let x = -3.5 + i as f32;
+        polygons.push(Rigidbody::rectangle(plank_width, 0.15, Vec2 { x, y: 0.0 }, 1.0, 0.3, ColorRGBA::orange()));
+    }
+
+    let mut pivot_joints = vec![];
+    let first = 2;
+    let last = first + plank_count - 1;
+    let mut left = PivotJoint::new(Vec2::new(0.25, 1.5), Vec2::new(-0.5, 0.0), &mut polygons, 0, first);
+    left.set_break_force(60.0);
+    pivot_joints.push(left);
+    for i in first..last {
+        let mut pin = PivotJoint::new(Vec2::new(0.5, 0.0), Vec2::new(-0.5, 0.0), &mut polygons, i, i + 1);
+        pin.set_break_force(60.0);
+        pivot_joints.push(pin);
+    }
+    let mut right = PivotJoint::new(Vec2::new(-0.25, 1.5), Vec2::new(0.5, 0.0), &mut polygons, 1, last);
+    right.set_break_force(60.0);
+    pivot_joints.push(right);
+
+    // Heavy boulder that snaps the bridge when it lands
+    polygons.push(Rigidbody::circle(0.4, Vec2 { x: 0.3, y: 4.0 }, 8.0, 0.1, ColorRGBA::random_hsl()));
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -10.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run(polygons, vec![], vec![], pivot_joints, parameters);
+}
@@ -0,0 +1,70 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    // Floor and walls of the pool
+    for (width, height, pos) in [
+        (16.0, 0.5, Vec2::new(0.0, -4.25)),
+        (0.5, 6.0, Vec2::new(-8.25, -1.0)),
+        (0.5, 6.0, Vec2::new(8.25, -1.0)),
+    ] {
+        let mut wall = Rigidbody::rectangle(width, height, pos, f32::MAX / 10000.0, 0.2, ColorRGBA::gray());
+        wall.gravity_multiplier = 0.0;
+        wall.friction = 0.5;
+        polygons.push(wall);
+    }
+
+    // Crates of balsa, wood, ice and steel
+    for (i, density) in [0.2, 0.6, 0.92, 7.8].into_iter().enumerate() {
+        let mut crate_body = Rigidbody::rectangle(0.8, 0.8, Vec2::new(-6.0 + i as f32 * 1.5, 2.0), 1.0, 0.1, ColorRGBA::random_hsl());
+        crate_body.set_density(density);
+        crate_body.friction = 0.4;
+        polygons.push(crate_body);
+    }
+
+    // Open hull of light wood, only its solid part displaces water
+    let mut boat = Rigidbody::concave(
+        vec![
+            Vec2::new(-1.5, 0.5),
+            Vec2::new(-1.2, -0.4),
+            Vec2::new(1.2, -0.4),
+            Vec2::new(1.5, 0.5),
+            Vec2::new(1.3, 0.5),
+            Vec2::new(1.05, -0.2),
+            Vec2::new(-1.05, -0.2),
+            Vec2::new(-1.3, 0.5),
+        ],
+        Vec2::new(4.0, 2.0),
+        1.0,
+        0.1,
+        ColorRGBA::new(0.6, 0.4, 0.2, 1.0),
+    );
+    boat.set_density(0.5);
+    boat.friction = 0.4;
+    polygons.push(boat);
+
+    let scene = Scene {
+        fluid_regions: vec![FluidRegion::rectangle(Vec2::new(-8.0, -4.0), Vec2::new(8.0, 0.0), 1.0)],
+        ..Default::default()
+    };
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -10.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run_scene(polygons, vec![], scene, parameters);
+}
@@ -0,0 +1,124 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    polygons.push(Rigidbody::rectangle(
+        30.0,
+        0.5,
+        Vec2 { x: 0.0, y: -2.0 },
+        f32::MAX / 10000.0,
+        0.2,
+        ColorRGBA::gray(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+    polygons[0].friction = 0.9;
+
+    // Car: chassis on two sprung wheels, the rear wheel is driven
+    polygons.push(Rigidbody::rectangle(
+        2.0,
+        0.4,
+        Vec2 { x: -6.0, y: 0.0 },
+        2.0,
+        0.2,
+        ColorRGBA::random_hsl(),
+    ));
+    for x in [-6.7, -5.3] {
+        let mut wheel = Rigidbody::circle(0.35, Vec2 { x, y: -0.6 }, 0.5, 0.2, ColorRGBA::random_hsl());
+        wheel.friction = 0.9;
+        polygons.push(wheel);
+    }
+
+    let mut wheel_joints = vec![];
+    let mut rear = WheelJoint::new(Vec2::new(-0.7, -0.6), Vec2::ZERO, Vec2::Y, &mut polygons, 1, 2);
+    rear.set_spring(40.0, 2.0).set_limits(-0.3, 0.1).set_motor(-6.0, 20.0);
+    wheel_joints.push(rear);
+    let mut front = WheelJoint::new(Vec2::new(0.7, -0.6), Vec2::ZERO, Vec2::Y, &mut polygons, 1, 3);
+    front.set_spring(40.0, 2.0).set_limits(-0.3, 0.1);
+    wheel_joints.push(front);
+
+    // Pulley: the heavier box lifts the lighter one
+    polygons.push(Rigidbody::rectangle(
+        0.5,
+        0.5,
+        Vec2 { x: 1.0, y: 0.0 },
+        2.0,
+        0.2,
+        ColorRGBA::random_hsl(),
+    ));
+    polygons.push(Rigidbody::rectangle(
+        0.4,
+        0.4,
+        Vec2 { x: 3.0, y: 0.0 },
+        1.0,
+        0.2,
+        ColorRGBA::random_hsl(),
+    ));
+    let pulley_joints = vec![PulleyJoint::new(
+        Vec2::new(1.0, 3.0),
+        Vec2::new(3.0, 3.0),
+        Vec2::ZERO,
+        Vec2::ZERO,
+        1.0,
+        &polygons,
+        4,
+        5,
+    )];
+
+    // Gear train: the motor driven small gear turns the large one at half the speed
+    polygons.push(Rigidbody::rectangle(
+        0.1,
+        0.1,
+        Vec2 { x: 7.0, y: 2.0 },
+        f32::MAX / 10000.0,
+        0.2,
+        ColorRGBA::gray(),
+    ));
+    polygons[6].gravity_multiplier = 0.0;
+    polygons[6].collision = false;
+    polygons.push(Rigidbody::polygon(8, 0.5, Vec2 { x: 6.5, y: 2.0 }, 1.0, 0.2, ColorRGBA::random_hsl()));
+    polygons.push(Rigidbody::polygon(16, 1.0, Vec2 { x: 8.0, y: 2.0 }, 2.0, 0.2, ColorRGBA::random_hsl()));
+    polygons[7].collision = false;
+    polygons[8].collision = false;
+
+    let mut pivot_joints = vec![];
+    let mut small = PivotJoint::new(Vec2::new(-0.5, 0.0), Vec2::ZERO, &mut polygons, 6, 7);
+    small.set_motor(2.0, 50.0);
+    pivot_joints.push(small);
+    pivot_joints.push(PivotJoint::new(Vec2::new(1.0, 0.0), Vec2::ZERO, &mut polygons, 6, 8));
+    let gear_joints = vec![GearJoint::new(
+        GearTarget::Pivot(0),
+        GearTarget::Pivot(1),
+        2.0,
+        &pivot_joints,
+        &[],
+        &polygons,
+    )];
+
+    let scene = Scene {
+        pivot_joints,
+        wheel_joints,
+        pulley_joints,
+        gear_joints,
+        ..Default::default()
+    };
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -12.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run_scene(polygons, vec![], scene, parameters);
+}
@@ -0,0 +1,48 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    // Heavy positive charge held in place with a light negative charge circling it
+    let mut nucleus = Rigidbody::circle(0.4, Vec2::new(0.0, 3.0), f32::MAX / 10000.0, 1.0, ColorRGBA::new(1.0, 0.3, 0.2, 1.0));
+    nucleus.charge = 2.0;
+    nucleus.gravity_multiplier = 0.0;
+    polygons.push(nucleus);
+    let mut electron = Rigidbody::circle(0.15, Vec2::new(3.0, 3.0), 1.0, 1.0, ColorRGBA::new(0.2, 0.5, 1.0, 1.0));
+    electron.charge = -1.0;
+    // Circular orbit, k * q1 * q2 / r^2 = m * v^2 / r
+    electron.velocity = Vec2::new(0.0, (10.0f32 * 2.0 / 3.0).sqrt());
+    polygons.push(electron);
+
+    // Two bar magnets at an angle, they turn to line up and snap together
+    let mut left = Rigidbody::rectangle(1.0, 0.3, Vec2::new(-2.0, -3.0), 0.3, 0.2, ColorRGBA::new(0.8, 0.8, 0.8, 1.0));
+    left.magnetic_moment = 1.0;
+    polygons.push(left);
+    let mut right = Rigidbody::rectangle(1.0, 0.3, Vec2::new(2.0, -3.0), 0.3, 0.2, ColorRGBA::new(0.8, 0.8, 0.8, 1.0));
+    right.magnetic_moment = 1.0;
+    right.rotate(std::f32::consts::FRAC_PI_4);
+    polygons.push(right);
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -10.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: false,
+        world_size: 100.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        coulomb_constant: 10.0,
+        magnetic_constant: 5.0,
+        softening: 0.05,
+        field_lines: true,
+        ..Default::default()
+    };
+    run(polygons, vec![], vec![], vec![], parameters);
+}
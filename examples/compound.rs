@@ -0,0 +1,68 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    polygons.push(Rigidbody::rectangle(
+        20.0,
+        1.0,
+        Vec2 { x: 0.0, y: -5.0 },
+        f32::MAX / 10.0,
+        0.3,
+        ColorRGBA::orange(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+    polygons[0].friction = 0.6;
+
+    // Light wooden handle with a dense steel head, tips over towards the head
+    let hammer = vec![
+        Fixture::rectangle(0.2, 2.0, Vec2::new(0.0, 0.0), 0.7, 0.3, 0.5),
+        Fixture::rectangle(1.0, 0.4, Vec2::new(0.0, 1.2), 7.8, 0.2, 0.4),
+    ];
+    polygons.push(Rigidbody::compound(
+        hammer,
+        Vec2 { x: -4.0, y: 0.0 },
+        ColorRGBA::random_hsl(),
+    ));
+
+    let dumbbell = vec![
+        Fixture::capsule(1.2, 0.08, Vec2::new(0.0, 0.0), 1.0, 0.3, 0.5),
+        Fixture::circle(0.35, Vec2::new(-0.7, 0.0), 5.0, 0.3, 0.5),
+        Fixture::circle(0.35, Vec2::new(0.7, 0.0), 5.0, 0.3, 0.5),
+    ];
+    polygons.push(Rigidbody::compound(
+        dumbbell,
+        Vec2 { x: 0.0, y: 0.0 },
+        ColorRGBA::random_hsl(),
+    ));
+
+    let table = vec![
+        Fixture::rectangle(2.0, 0.2, Vec2::new(0.0, 0.0), 1.0, 0.2, 0.6),
+        Fixture::rectangle(0.2, 1.0, Vec2::new(-0.9, -0.6), 1.0, 0.2, 0.6),
+        Fixture::rectangle(0.2, 1.0, Vec2::new(0.9, -0.6), 1.0, 0.2, 0.6),
+    ];
+    polygons.push(Rigidbody::compound(
+        table,
+        Vec2 { x: 4.0, y: 0.0 },
+        ColorRGBA::random_hsl(),
+    ));
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -10.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run(polygons, vec![], vec![], vec![], parameters);
+}
@@ -0,0 +1,85 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    polygons.push(Rigidbody::rectangle(
+        20.0,
+        1.0,
+        Vec2 { x: 0.0, y: -5.0 },
+        f32::MAX / 10.0,
+        0.3,
+        ColorRGBA::orange(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+
+    let l_shape = vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 0.5),
+        Vec2::new(0.5, 0.5),
+        Vec2::new(0.5, 2.0),
+        Vec2::new(0.0, 2.0),
+    ];
+    let u_shape = vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(1.5, 2.0),
+        Vec2::new(1.5, 0.5),
+        Vec2::new(0.5, 0.5),
+        Vec2::new(0.5, 2.0),
+        Vec2::new(0.0, 2.0),
+    ];
+    let mut star = vec![];
+    for i in 0..10 {
+        let angle = i as f32 * std::f32::consts::PI / 5.0;
+        let radius = if i % 2 == 0 { 1.2 } else { 0.5 };
+        star.push(Vec2::from_angle(angle) * radius);
+    }
+
+    for (i, shape) in [l_shape, u_shape, star].into_iter().enumerate() {
+        polygons.push(Rigidbody::concave(
+            shape,
+            Vec2 {
+                x: -4.0 + i as f32 * 4.0,
+                y: 2.0,
+            },
+            1.0,
+            0.3,
+            ColorRGBA::random_hsl(),
+        ));
+    }
+
+    for i in 0..6 {
+        polygons.push(Rigidbody::circle(
+            0.2,
+            Vec2 {
+                x: -0.5 + i as f32 * 0.2,
+                y: 6.0 + i as f32,
+            },
+            0.2,
+            0.5,
+            ColorRGBA::random_hsl(),
+        ));
+    }
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -10.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run(polygons, vec![], vec![], vec![], parameters);
+}
@@ -0,0 +1,80 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    // Fixed ceiling the pendulums hang from
+    polygons.push(Rigidbody::rectangle(
+        8.0,
+        0.2,
+        Vec2 { x: 0.0, y: 2.0 },
+        f32::MAX / 10000.0,
+        0.5,
+        ColorRGBA::gray(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+
+    // Rigid rod pendulum released sideways
+    polygons.push(Rigidbody::circle(
+        0.25,
+        Vec2 { x: -1.0, y: 2.0 },
+        1.0,
+        0.5,
+        ColorRGBA::random_hsl(),
+    ));
+
+    // Ball on a rope, starts slack and catches when the rope goes taut
+    polygons.push(Rigidbody::circle(
+        0.25,
+        Vec2 { x: 1.0, y: 1.0 },
+        1.0,
+        0.5,
+        ColorRGBA::random_hsl(),
+    ));
+
+    // Telescopic link that may slide between 0.5 and 1.5
+    polygons.push(Rigidbody::rectangle(
+        0.4,
+        0.4,
+        Vec2 { x: 3.0, y: 1.0 },
+        1.0,
+        0.5,
+        ColorRGBA::random_hsl(),
+    ));
+
+    let mut distance_joints = vec![];
+    distance_joints.push(DistanceJoint::new(Vec2::new(-3.0, -0.1), Vec2::ZERO, &polygons, 0, 1));
+    let mut telescope = DistanceJoint::new(Vec2::new(3.0, -0.1), Vec2::ZERO, &polygons, 0, 3);
+    telescope.set_limits(0.5, 1.5);
+    distance_joints.push(telescope);
+
+    let mut rope_joints = vec![];
+    let mut rope = RopeJoint::new(Vec2::new(1.0, -0.1), Vec2::ZERO, &polygons, 0, 2);
+    rope.set_max_length(2.5);
+    rope_joints.push(rope);
+
+    let scene = Scene {
+        distance_joints,
+        rope_joints,
+        ..Default::default()
+    };
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -8.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run_scene(polygons, vec![], scene, parameters);
+}
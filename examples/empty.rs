@@ -16,6 +16,7 @@ fn main(){
         clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
         is_running: false,
         gravitational_constant: 0.0,
+        ..Default::default()
     };
     run(vec![], vec![], vec![], vec![], parameters);
 }
\ No newline at end of file
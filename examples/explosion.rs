@@ -17,13 +17,11 @@ fn main() {
             ));
         }
     }
-    for i in 0..polygons.len() {
-        polygons[i].velocity = Vec2 {
-            x: (rand::random::<f32>() * 2.0 - 1.0) * 10.0,
-            y: (rand::random::<f32>() * 2.0 - 1.0) * 10.0,
-        };
-        polygons[i].angular_velocity = (rand::random::<f32>() * 2.0 - 1.0) * 500.0;
-    }
+    // Blast in the middle of the grid, every body sees it so the whole grid flies apart
+    Explosion::new(Vec2::new(15.75, 15.75), 40.0, 500.0, Falloff::Linear)
+        .set_occlusion(false)
+        .set_rays(4096)
+        .apply(&mut polygons);
     let parameters = Parameters {
         delta_time: 0.0,
         updates_per_frame: 1,
@@ -39,6 +37,7 @@ fn main() {
         clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
         is_running: false,
         gravitational_constant: 0.0,
+        ..Default::default()
     };
     run(polygons, vec![], vec![], vec![], parameters);
 }
@@ -0,0 +1,59 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    polygons.push(Rigidbody::rectangle(
+        30.0,
+        0.5,
+        Vec2 { x: 0.0, y: -4.0 },
+        f32::MAX / 10000.0,
+        0.2,
+        ColorRGBA::gray(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+    polygons[0].friction = 0.5;
+
+    for i in 0..24 {
+        let pos = Vec2::new(-9.0 + (i % 12) as f32 * 1.5, 2.0 + (i / 12) as f32 * 1.5);
+        polygons.push(Rigidbody::polygon(5, 0.3, pos, 1.0, 0.3, ColorRGBA::random_hsl()));
+    }
+
+    // Wind blowing up a chimney on the left, a whirlpool in the middle and a repulsor on the right
+    let mut wind = ForceField::wind(Vec2::Y, 20.0);
+    wind.set_region(FieldRegion::Aabb { min: Vec2::new(-9.0, -3.75), max: Vec2::new(-6.0, 4.0) });
+    let mut vortex = ForceField::vortex(Vec2::new(0.0, 0.0), 15.0);
+    vortex.set_falloff(Falloff::Linear, 3.0);
+    let mut repulsor = ForceField::radial(Vec2::new(6.0, -3.0), -10.0);
+    repulsor
+        .set_falloff(Falloff::InverseSquare, 10.0)
+        .set_region(FieldRegion::Polygon(vec![
+            Vec2::new(3.0, -3.75),
+            Vec2::new(9.0, -3.75),
+            Vec2::new(6.0, 1.0),
+        ]));
+
+    let scene = Scene {
+        force_fields: vec![wind, vortex, repulsor],
+        ..Default::default()
+    };
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -10.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run_scene(polygons, vec![], scene, parameters);
+}
@@ -0,0 +1,89 @@
+use std::f32::consts::PI;
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    // Fixed base for a two segment robot arm
+    polygons.push(Rigidbody::rectangle(
+        0.4,
+        0.4,
+        Vec2 { x: -3.0, y: 0.0 },
+        f32::MAX / 10000.0,
+        0.5,
+        ColorRGBA::gray(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+    polygons[0].collision = false;
+
+    polygons.push(Rigidbody::rectangle(
+        2.0,
+        0.2,
+        Vec2 { x: -2.0, y: 0.0 },
+        1.0,
+        0.5,
+        ColorRGBA::random_hsl(),
+    ));
+    polygons.push(Rigidbody::rectangle(
+        1.5,
+        0.15,
+        Vec2 { x: -0.25, y: 0.0 },
+        0.5,
+        0.5,
+        ColorRGBA::random_hsl(),
+    ));
+
+    // Door hinged on a fixed frame, swings between 0 and 100 degrees
+    polygons.push(Rigidbody::rectangle(
+        0.1,
+        0.1,
+        Vec2 { x: 2.0, y: 0.0 },
+        f32::MAX / 10000.0,
+        0.5,
+        ColorRGBA::gray(),
+    ));
+    polygons[3].gravity_multiplier = 0.0;
+    polygons[3].collision = false;
+    polygons.push(Rigidbody::rectangle(
+        0.1,
+        2.0,
+        Vec2 { x: 2.0, y: -1.0 },
+        1.0,
+        0.5,
+        ColorRGBA::random_hsl(),
+    ));
+    polygons[4].angular_velocity = 3.0;
+
+    let mut pivot_joints = vec![];
+
+    let mut shoulder = PivotJoint::new(Vec2::ZERO, Vec2::new(-1.0, 0.0), &mut polygons, 0, 1);
+    shoulder.set_motor(0.5, 60.0).set_limits(-PI / 4.0, PI / 2.0);
+    pivot_joints.push(shoulder);
+
+    let mut elbow = PivotJoint::new(Vec2::new(1.0, 0.0), Vec2::new(-0.75, 0.0), &mut polygons, 1, 2);
+    elbow.set_motor(-1.0, 10.0).set_limits(-PI / 2.0, 0.0);
+    pivot_joints.push(elbow);
+
+    let mut hinge = PivotJoint::new(Vec2::ZERO, Vec2::new(0.0, 1.0), &mut polygons, 3, 4);
+    hinge.set_limits(0.0, 100.0_f32.to_radians());
+    pivot_joints.push(hinge);
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -8.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run(polygons, vec![], vec![], pivot_joints, parameters);
+}
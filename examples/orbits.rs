@@ -0,0 +1,43 @@
+use ferrum_engine::*;
+
+fn main() {
+    let gravitational_constant = 1.0;
+    let mut polygons = vec![];
+
+    let mut sun = Rigidbody::circle(1.0, Vec2::ZERO, 1000.0, 1.0, ColorRGBA::new(1.0, 0.8, 0.2, 1.0));
+    sun.eternal = true;
+    polygons.push(sun.clone());
+
+    // Planets from a circle out to a long ellipse, each starting at its closest point to the sun
+    for (distance, eccentricity) in [(5.0, 0.0), (9.0, 0.3), (14.0, 0.6)] {
+        let mut planet = Rigidbody::circle(0.3, Vec2::new(distance, 0.0), 1.0, 1.0, ColorRGBA::random_hsl());
+        planet.set_orbit(&sun, eccentricity, gravitational_constant);
+        polygons.push(planet);
+    }
+
+    // Moon on a circle around the outer planet, riding along with it
+    let planet = polygons[3].clone();
+    let mut moon = Rigidbody::circle(0.1, planet.center + Vec2::new(1.5, 0.0), 0.01, 1.0, ColorRGBA::new(0.8, 0.8, 0.8, 1.0));
+    moon.set_orbit(&planet, 0.0, gravitational_constant);
+    polygons.push(moon);
+
+    let parameters = Parameters {
+        delta_time: 0.001,
+        updates_per_frame: 10,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -40.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: false,
+        world_size: 200.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant,
+        prediction_time: 10.0,
+        ..Default::default()
+    };
+    run(polygons, vec![], vec![], vec![], parameters);
+}
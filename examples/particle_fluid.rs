@@ -0,0 +1,52 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    // Tank, the water starts piled up in the left corner and collapses into a wave
+    for (width, height, pos) in [
+        (8.0, 0.4, Vec2::new(0.0, -0.2)),
+        (0.4, 5.0, Vec2::new(-4.2, 2.5)),
+        (0.4, 5.0, Vec2::new(4.2, 2.5)),
+    ] {
+        let mut wall = Rigidbody::rectangle(width, height, pos, f32::MAX / 10000.0, 0.2, ColorRGBA::gray());
+        wall.gravity_multiplier = 0.0;
+        wall.friction = 0.5;
+        polygons.push(wall);
+    }
+
+    // Crates of wood and steel waiting for the wave
+    for (density, x) in [(0.5, 1.5), (4.0, 2.8)] {
+        let mut crate_body = Rigidbody::rectangle(0.5, 0.5, Vec2::new(x, 0.25), 1.0, 0.1, ColorRGBA::random_hsl());
+        crate_body.set_density(density);
+        crate_body.friction = 0.4;
+        polygons.push(crate_body);
+    }
+
+    let mut water = ParticleFluid::new(0.06);
+    water.add_block(Vec2::new(-4.0, 0.0), Vec2::new(-2.0, 2.0), Vec2::ZERO);
+
+    let scene = Scene {
+        particle_fluids: vec![water],
+        ..Default::default()
+    };
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 2.0, 0.0, -7.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run_scene(polygons, vec![], scene, parameters);
+}
@@ -0,0 +1,81 @@
+use std::f32::consts::PI;
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+
+    // Fixed frame for a piston that pushes a box along the floor
+    polygons.push(Rigidbody::rectangle(
+        0.4,
+        0.4,
+        Vec2 { x: -3.0, y: 0.0 },
+        f32::MAX / 10000.0,
+        0.5,
+        ColorRGBA::gray(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+    polygons[0].collision = false;
+
+    polygons.push(Rigidbody::rectangle(
+        1.0,
+        0.3,
+        Vec2 { x: -2.5, y: 0.0 },
+        1.0,
+        0.5,
+        ColorRGBA::random_hsl(),
+    ));
+
+    // Elevator platform riding a vertical rail
+    polygons.push(Rigidbody::rectangle(
+        0.1,
+        0.1,
+        Vec2 { x: 2.0, y: -2.0 },
+        f32::MAX / 10000.0,
+        0.5,
+        ColorRGBA::gray(),
+    ));
+    polygons[2].gravity_multiplier = 0.0;
+    polygons[2].collision = false;
+    polygons.push(Rigidbody::rectangle(
+        1.2,
+        0.15,
+        Vec2 { x: 2.0, y: -2.0 },
+        2.0,
+        0.5,
+        ColorRGBA::random_hsl(),
+    ));
+
+    let mut prismatic_joints = vec![];
+
+    let mut piston = PrismaticJoint::new(Vec2::ZERO, Vec2::new(-0.5, 0.0), Vec2::X, &mut polygons, 0, 1);
+    piston.set_motor(1.0, 50.0).set_limits(0.0, 2.0);
+    prismatic_joints.push(piston);
+
+    let mut elevator = PrismaticJoint::new(Vec2::ZERO, Vec2::ZERO, Vec2::from_angle(PI / 2.0), &mut polygons, 2, 3);
+    elevator.set_motor(0.5, 100.0).set_limits(0.0, 3.0);
+    prismatic_joints.push(elevator);
+
+    let scene = Scene {
+        prismatic_joints,
+        ..Default::default()
+    };
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -8.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run_scene(polygons, vec![], scene, parameters);
+}
@@ -0,0 +1,81 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+    let mut pivot_joints = vec![];
+    let mut distance_joints = vec![];
+
+    // Ceiling the ropes hang from
+    polygons.push(Rigidbody::rectangle(
+        12.0,
+        0.2,
+        Vec2 { x: 0.0, y: 3.0 },
+        f32::MAX / 10000.0,
+        0.2,
+        ColorRGBA::gray(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+
+    // Chain of pivoted segments holding a weight
+    polygons.push(Rigidbody::circle(0.3, Vec2::new(-4.0, 0.0), 2.0, 0.2, ColorRGBA::random_hsl()));
+    Rope::new(
+        Vec2::new(-4.0, 2.9),
+        Vec2::new(-4.0, 0.3),
+        Some(0),
+        Some(1),
+        &ChainParams::default(),
+        &mut polygons,
+        &mut pivot_joints,
+        &mut distance_joints,
+    );
+
+    // Free end rope of particles, starting out horizontal so it swings down
+    let particles = ChainParams { link: ChainLink::Distance, segments: 16, ..Default::default() };
+    Rope::new(
+        Vec2::new(-2.0, 2.9),
+        Vec2::new(1.0, 2.9),
+        Some(0),
+        None,
+        &particles,
+        &mut polygons,
+        &mut pivot_joints,
+        &mut distance_joints,
+    );
+
+    // Net pinned at its top corners and middle
+    Cloth::new(
+        Vec2::new(2.0, 2.5),
+        9,
+        7,
+        0.3,
+        &[(0, 0), (4, 0), (8, 0)],
+        &ChainParams { color: ColorRGBA::random_hsl(), ..Default::default() },
+        &mut polygons,
+        &mut distance_joints,
+    );
+
+    let scene = Scene {
+        pivot_joints,
+        distance_joints,
+        ..Default::default()
+    };
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -8.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run_scene(polygons, vec![], scene, parameters);
+}
@@ -12,8 +12,7 @@ fn main() {
     ));
     rigidbodies[0].collision = false;
     rigidbodies[0].eternal = true;
-    rigidbodies.push(Rigidbody::polygon(
-        64,
+    rigidbodies.push(Rigidbody::circle(
         3.533,
         Vec2::new(25.0, 0.0),
         5000000.0,
@@ -48,6 +47,7 @@ fn main() {
         clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
         is_running: false,
         gravitational_constant: 0.0,
+        ..Default::default()
     };
     run(rigidbodies, springs, vec![], vec![], parameters);
 }
@@ -16,8 +16,7 @@ fn main() {
     polygons[0].gravity_multiplier = 0.0;
 
     for i in 0..10 {
-        polygons.push(Rigidbody::polygon(
-            32,
+        polygons.push(Rigidbody::circle(
             0.3533,
             Vec2 {
                 x: 2.5,
@@ -44,6 +43,7 @@ fn main() {
         clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
         is_running: false,
         gravitational_constant: 0.0,
+        ..Default::default()
     };
     run(polygons, vec![], vec![], vec![], parameters);
 }
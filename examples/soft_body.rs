@@ -0,0 +1,51 @@
+use ferrum_engine::*;
+
+fn main() {
+    let mut polygons = vec![];
+    let mut springs = vec![];
+
+    polygons.push(Rigidbody::rectangle(
+        30.0,
+        0.5,
+        Vec2 { x: 0.0, y: -3.0 },
+        f32::MAX / 10000.0,
+        0.2,
+        ColorRGBA::gray(),
+    ));
+    polygons[0].gravity_multiplier = 0.0;
+    polygons[0].friction = 0.5;
+
+    // Inflated blob, a hollow ring and a jelly block
+    let blob = SoftBodyParams { pressure: 20.0, ..Default::default() };
+    let ring = SoftBodyParams { shear_stiffness: 0.0, ..Default::default() };
+    let jelly = SoftBodyParams::default();
+    let soft_bodies = vec![
+        SoftBody::ring(Vec2::new(-3.0, 1.0), 0.8, 20, &blob, &mut polygons, &mut springs),
+        SoftBody::ring(Vec2::new(0.0, 1.0), 0.8, 20, &ring, &mut polygons, &mut springs),
+        SoftBody::grid(Vec2::new(3.0, 1.0), 6, 6, 0.25, &jelly, &mut polygons, &mut springs),
+    ];
+
+    let scene = Scene {
+        soft_bodies,
+        ..Default::default()
+    };
+
+    let parameters = Parameters {
+        delta_time: 0.0,
+        updates_per_frame: 1,
+        angular_velocity: true,
+        initial_camera: Camera {
+            camera_pos: Vec4::new(0.0, 0.0, 0.0, -8.0),
+            scaling_factor: 10.0,
+        },
+        time_multiplier: 1.0,
+        gravity: true,
+        world_size: 300.0,
+        gravity_force: Vec2::new(0.0, -9.81),
+        clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+        is_running: false,
+        gravitational_constant: 0.0,
+        ..Default::default()
+    };
+    run_scene(polygons, springs, scene, parameters);
+}
@@ -17,6 +17,7 @@ fn main() {
         clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
         is_running: false,
         gravitational_constant: 0.0,
+        ..Default::default()
     };
     let mut polygons = vec![];
 
@@ -32,8 +33,7 @@ fn main() {
         ColorRGBA::random_hsl(),
     ));
 
-    polygons.push(Rigidbody::polygon(
-        32,
+    polygons.push(Rigidbody::circle(
         0.3533,
         Vec2 {
             x: 0.4,
@@ -1,5 +1,5 @@
 use crate::spring::Spring;
-use crate::world::Parameters;
+use crate::world::{Parameters, Scene};
 use crate::{Rigidbody, World};
 use egui_wgpu::wgpu;
 use std::sync::Arc;
@@ -15,8 +15,7 @@ pub struct App {
     world: Option<World>,
     polygons: Vec<Rigidbody>,
     springs: Vec<Spring>,
-    weld_joints: Vec<WeldJoint>,
-    pivot_joints: Vec<PivotJoint>,
+    scene: Scene,
     parameters: Parameters,
 
 }
@@ -25,16 +24,14 @@ impl App {
     pub fn new(
         polygons: Vec<Rigidbody>,
         springs: Vec<Spring>,
-        weld_joints: Vec<WeldJoint>,
-        pivot_joints: Vec<PivotJoint>,
+        scene: Scene,
         parameters: Parameters,
     ) -> Self {
         Self {
             world: None,
             polygons,
             springs,
-            weld_joints,
-            pivot_joints,
+            scene,
             parameters,
         }
     }
@@ -51,8 +48,7 @@ impl ApplicationHandler<World> for App {
                     window,
                     self.polygons.clone(),
                     self.springs.clone(),
-                    self.weld_joints.clone(),
-                    self.pivot_joints.clone(),
+                    self.scene.clone(),
                     self.parameters.clone(),
                 )),
             );
@@ -96,7 +92,7 @@ impl ApplicationHandler<World> for App {
                 }
             }
             WindowEvent::MouseInput { state, button, .. } => {
-                world.ui.handle_mouse_input(state, button, &mut world.physics, &mut world.color_system)
+                world.ui.handle_mouse_input(state, button, &mut world.physics, &mut world.color_system, &world.parameters)
             }
             WindowEvent::KeyboardInput {
                 event:
@@ -120,13 +116,28 @@ pub fn run(
     weld_joints: Vec<WeldJoint>,
     pivot_joints: Vec<PivotJoint>,
     parameters: Parameters,
+) {
+    let scene = Scene {
+        weld_joints,
+        pivot_joints,
+        ..Default::default()
+    };
+    run_scene(rigidbodys, springs, scene, parameters);
+}
+
+/// Same as [`run`] for scenes starting with more than welds and pivots, such as other joints, soft bodies,
+/// force fields or fluids.
+pub fn run_scene(
+    rigidbodys: Vec<Rigidbody>,
+    springs: Vec<Spring>,
+    scene: Scene,
+    parameters: Parameters,
 ) {
     let event_loop = EventLoop::with_user_event().build().expect("Unable to create event loop");
     let mut app = App::new(
         rigidbodys,
         springs,
-        weld_joints,
-        pivot_joints,
+        scene,
         parameters,
     );
     event_loop.run_app(&mut app).expect("Error");
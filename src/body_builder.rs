@@ -1,7 +1,8 @@
 use glam::Vec2;
 use crate::enums::{BodyType, ColorType};
-use crate::spring::Spring;
-use crate::{ColorRGBA, Rigidbody};
+use crate::material::MATERIAL_PRESETS_PATH;
+use crate::spring::{Spring, SpringLaw};
+use crate::{AnchorSnap, ChainParams, ColorRGBA, Explosion, FieldRegion, FluidRegion, ForceField, Material, ParticleFluid, Rigidbody, SoftBody, SoftBodyParams};
 
 #[derive(Clone)]
 pub struct RigidbodyParams {
@@ -11,7 +12,11 @@ pub struct RigidbodyParams {
     pub(crate) height: f32,
     pub(crate) pos: Vec2,
     pub(crate) mass: f32,
+    /// Derive the mass from `density` and the area instead of using `mass`.
+    pub(crate) use_density: bool,
+    pub(crate) density: f32,
     pub(crate) restitution: f32,
+    pub(crate) friction: f32,
     pub(crate) color: Option<ColorRGBA>,
     pub(crate) collides: bool,
     pub(crate) rotation: f32,
@@ -19,7 +24,11 @@ pub struct RigidbodyParams {
     pub(crate) velocity: Vec2,
     pub(crate) color_type: ColorType,
     pub(crate) gravity_multiplier: f32,
+    pub(crate) charge: f32,
+    pub(crate) magnetic_moment: f32,
     pub(crate) eternal: bool,
+    /// Spawned bodies orbit the body pulling on them hardest with this eccentricity instead of keeping `velocity`.
+    pub(crate) orbit_eccentricity: Option<f32>,
 }
 
 #[derive(Clone, Default)]
@@ -31,6 +40,66 @@ pub struct SpringParams {
     pub(crate) body_b: usize,
     pub(crate) anchor_a: Vec2,
     pub(crate) anchor_b: Vec2,
+    /// Break threshold given to spawned springs.
+    pub(crate) break_force: Option<f32>,
+    pub(crate) law: SpringLaw,
+}
+
+/// Settings of the mouse joint used in the drag mode.
+#[derive(Clone)]
+pub struct DragParams {
+    pub(crate) max_force: f32,
+    pub(crate) frequency: f32,
+    pub(crate) damping_ratio: f32,
+}
+
+#[derive(Clone, Default)]
+pub struct JointParams {
+    /// World space slide direction of spawned prismatic joints, in radians.
+    pub(crate) axis_angle: f32,
+    /// Lets spawned distance joints change length between `min_length` and `max_length`.
+    pub(crate) enable_limit: bool,
+    pub(crate) min_length: f32,
+    pub(crate) max_length: f32,
+    /// Break threshold given to spawned weld and pivot joints.
+    pub(crate) break_force: Option<f32>,
+}
+
+#[derive(Clone)]
+pub struct SoftBodySpawnParams {
+    /// Spawn a grid of particles instead of a ring.
+    pub(crate) grid: bool,
+    /// Particles around the ring or along each side of the grid.
+    pub(crate) resolution: usize,
+    /// Radius of the ring or width of the grid.
+    pub(crate) size: f32,
+    pub(crate) params: SoftBodyParams,
+}
+
+#[derive(Clone)]
+pub struct ForceFieldSpawnParams {
+    /// Copied to the click position when placing a field.
+    pub(crate) field: ForceField,
+    /// Limits placed fields to a box of `region_size` around the click.
+    pub(crate) bounded: bool,
+    pub(crate) region_size: Vec2,
+}
+
+#[derive(Clone)]
+pub struct FluidSpawnParams {
+    /// Width and height of placed water boxes.
+    pub(crate) size: Vec2,
+    pub(crate) density: f32,
+    pub(crate) linear_drag: f32,
+    pub(crate) angular_drag: f32,
+}
+
+#[derive(Clone)]
+pub struct ParticleSpawnParams {
+    /// Width and height of the blocks of particles poured in on click.
+    pub(crate) size: Vec2,
+    /// Rest spacing of a new fluid, blocks join the first fluid with the same spacing.
+    pub(crate) spacing: f32,
 }
 
 #[derive(Clone)]
@@ -38,6 +107,21 @@ pub struct BodyBuilder {
     pub(crate) body_type: BodyType,
     pub(crate) rigidbody_params: RigidbodyParams,
     pub(crate) spring_params: SpringParams,
+    pub(crate) joint_params: JointParams,
+    pub(crate) soft_body_params: SoftBodySpawnParams,
+    /// Links of ropes drawn between two bodies.
+    pub(crate) chain_params: ChainParams,
+    pub(crate) force_field_params: ForceFieldSpawnParams,
+    pub(crate) fluid_params: FluidSpawnParams,
+    pub(crate) particle_params: ParticleSpawnParams,
+    /// Moved to the click position when detonating.
+    pub(crate) explosion: Explosion,
+    /// Feature of the body that anchors of spawned springs and joints snap to.
+    pub(crate) anchor_snap: AnchorSnap,
+    /// Built-in materials followed by the user's saved presets.
+    pub(crate) materials: Vec<Material>,
+    /// Name given to the next saved preset.
+    pub(crate) material_name: String,
 }
 
 impl BodyBuilder {
@@ -73,6 +157,25 @@ impl BodyBuilder {
                     )
                 }
             }
+            BodyType::Circle => {
+                rigidbody = Rigidbody::circle(
+                    body_params.radius,
+                    body_params.pos,
+                    body_params.mass,
+                    body_params.restitution,
+                    body_params.color.unwrap_or(random_color),
+                )
+            }
+            BodyType::Capsule => {
+                rigidbody = Rigidbody::capsule(
+                    body_params.width,
+                    body_params.radius,
+                    body_params.pos,
+                    body_params.mass,
+                    body_params.restitution,
+                    body_params.color.unwrap_or(random_color),
+                )
+            }
             _ => {
                 if body_params.color.is_some() {
                     rigidbody = Rigidbody::polygon(
@@ -95,15 +198,46 @@ impl BodyBuilder {
                 }
             }
         };
+        rigidbody.friction = body_params.friction;
+        if body_params.use_density {
+            rigidbody.set_density(body_params.density);
+        }
         rigidbody.eternal = body_params.eternal;
         rigidbody.collision = body_params.collides;
         rigidbody.rotate(body_params.rotation);
         rigidbody.angular_velocity = body_params.angular_velocity;
         rigidbody.velocity = body_params.velocity;
         rigidbody.gravity_multiplier = body_params.gravity_multiplier;
+        rigidbody.charge = body_params.charge;
+        rigidbody.magnetic_moment = body_params.magnetic_moment;
         rigidbody
     }
 
+    /// Copies a material into the spawn parameters, switching them to density based mass.
+    pub fn apply_material(&mut self, material: &Material) {
+        let body_params = &mut self.rigidbody_params;
+        body_params.use_density = true;
+        body_params.density = material.density;
+        body_params.restitution = material.restitution;
+        body_params.friction = material.friction;
+    }
+
+    /// Adds the current spawn parameters to the material library and writes the user presets to disk.
+    pub fn save_material(&mut self) -> std::io::Result<()> {
+        let body_params = &self.rigidbody_params;
+        let material = Material::new(
+            &self.material_name,
+            body_params.density,
+            body_params.restitution,
+            body_params.friction,
+        );
+        match self.materials.iter_mut().find(|existing| existing.name == material.name) {
+            Some(existing) => *existing = material,
+            None => self.materials.push(material),
+        }
+        Material::save_presets(MATERIAL_PRESETS_PATH, &self.materials)
+    }
+
     pub fn create_spring(&self, rigidbodies: &mut Vec<Rigidbody>) -> Spring {
         assert_ne!(self.body_type, BodyType::Spring, "Body Type is not spring");
         let spring_params = &self.spring_params;
@@ -119,9 +253,68 @@ impl BodyBuilder {
         )
     }
 
+    /// Appends the particles and springs of a soft body centered on `pos`.
+    pub fn create_soft_body(
+        &self,
+        pos: Vec2,
+        palette: &Option<Vec<ColorRGBA>>,
+        rigidbodies: &mut Vec<Rigidbody>,
+        springs: &mut Vec<Spring>,
+    ) -> SoftBody {
+        let soft_body_params = &self.soft_body_params;
+        let mut params = soft_body_params.params.clone();
+        params.color = match palette {
+            Some(palette) => ColorRGBA::random_from_palette(palette),
+            None => ColorRGBA::random_hsl(),
+        };
+        let resolution = soft_body_params.resolution;
+        if soft_body_params.grid {
+            let spacing = soft_body_params.size / (resolution.max(2) - 1) as f32;
+            SoftBody::grid(pos, resolution, resolution, spacing, &params, rigidbodies, springs)
+        } else {
+            SoftBody::ring(pos, soft_body_params.size, resolution, &params, rigidbodies, springs)
+        }
+    }
+
+    /// Force field from the spawn parameters centered on `pos`.
+    pub fn create_force_field(&self, pos: Vec2) -> ForceField {
+        let force_field_params = &self.force_field_params;
+        let mut field = force_field_params.field.clone();
+        field.region = if force_field_params.bounded {
+            let half_size = force_field_params.region_size.abs() * 0.5;
+            FieldRegion::Aabb { min: pos - half_size, max: pos + half_size }
+        } else {
+            FieldRegion::Everywhere
+        };
+        field.move_to(pos);
+        field
+    }
+
+    /// Box of fluid from the spawn parameters centered on `pos`.
+    pub fn create_fluid_region(&self, pos: Vec2) -> FluidRegion {
+        let fluid_params = &self.fluid_params;
+        let half_size = fluid_params.size.abs() * 0.5;
+        let mut fluid_region = FluidRegion::rectangle(pos - half_size, pos + half_size, fluid_params.density);
+        fluid_region.set_drag(fluid_params.linear_drag, fluid_params.angular_drag);
+        fluid_region
+    }
+
+    /// Pours a block of particles centered on `pos` into the matching fluid, or into a new one.
+    pub fn spawn_particles(&self, pos: Vec2, particle_fluids: &mut Vec<ParticleFluid>) {
+        let particle_params = &self.particle_params;
+        let half_size = particle_params.size.abs() * 0.5;
+        let index = match particle_fluids.iter().position(|fluid| fluid.spacing == particle_params.spacing) {
+            Some(index) => index,
+            None => {
+                particle_fluids.push(ParticleFluid::new(particle_params.spacing));
+                particle_fluids.len() - 1
+            }
+        };
+        particle_fluids[index].add_block(pos - half_size, pos + half_size, Vec2::ZERO);
+    }
+
     pub fn create_joint() -> Rigidbody {
-        let mut rigidbody = Rigidbody::polygon(
-            32,
+        let mut rigidbody = Rigidbody::circle(
             0.03,
             Vec2::ZERO,
             1.0,
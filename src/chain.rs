@@ -0,0 +1,200 @@
+use glam::Vec2;
+use crate::{ColorRGBA, DistanceJoint, PivotJoint, Rigidbody};
+
+/// Constraint holding neighbouring links of a rope together.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ChainLink {
+    /// Rigid segments pinned end to end by pivot joints.
+    #[default]
+    Pivot,
+    /// Particles kept apart by distance joints.
+    Distance,
+}
+
+/// Bodies of generated ropes and cloths.
+#[derive(Clone, Debug)]
+pub struct ChainParams {
+    /// Number of links a rope is split into.
+    pub segments: usize,
+    /// How rope links are connected, cloths always use distance joints.
+    pub link: ChainLink,
+    /// Width of rope segments and diameter of particles.
+    pub thickness: f32,
+    /// Mass of every segment or particle.
+    pub mass: f32,
+    pub color: ColorRGBA,
+}
+
+impl Default for ChainParams {
+    fn default() -> Self {
+        Self {
+            segments: 12,
+            link: ChainLink::Pivot,
+            thickness: 0.08,
+            mass: 0.1,
+            color: ColorRGBA::new(0.8, 0.6, 0.4, 1.0),
+        }
+    }
+}
+
+/// Indices of the bodies and joints a rope was built from, valid until bodies or joints are removed.
+#[derive(Clone, Debug, Default)]
+pub struct Rope {
+    /// Links from the start to the end of the rope.
+    pub bodies: Vec<usize>,
+    pub pivot_joints: Vec<usize>,
+    pub distance_joints: Vec<usize>,
+}
+
+/// Indices of the particles and joints a cloth was built from, valid until bodies or joints are removed.
+#[derive(Clone, Debug, Default)]
+pub struct Cloth {
+    /// Particles row by row starting at the top left corner.
+    pub particles: Vec<usize>,
+    pub columns: usize,
+    pub rows: usize,
+    pub distance_joints: Vec<usize>,
+}
+
+fn particle(pos: Vec2, params: &ChainParams) -> Rigidbody {
+    let mut particle = Rigidbody::circle(params.thickness * 0.5, pos, params.mass, 0.1, params.color);
+    particle.friction = 0.5;
+    particle
+}
+
+/// Holds a particle in place without taking part in the simulation.
+fn pin(particle: &mut Rigidbody) {
+    particle.mass = f32::MAX / 10000.0;
+    particle.moment_of_inertia = f32::MAX / 10000.0;
+    particle.gravity_multiplier = 0.0;
+    particle.is_static = true;
+}
+
+impl Rope {
+    /// Straight rope from `start` to `end`, ends given a body are attached to it at that point.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        start: Vec2,
+        end: Vec2,
+        body_a: Option<usize>,
+        body_b: Option<usize>,
+        params: &ChainParams,
+        rigidbodys: &mut Vec<Rigidbody>,
+        pivot_joints: &mut Vec<PivotJoint>,
+        distance_joints: &mut Vec<DistanceJoint>,
+    ) -> Self {
+        let segments = params.segments.max(1);
+        let step = (end - start) / segments as f32;
+        let mut rope = Rope::default();
+
+        match params.link {
+            ChainLink::Pivot => {
+                for i in 0..segments {
+                    let mut segment = Rigidbody::rectangle(
+                        params.thickness,
+                        step.length(),
+                        start + step * (i as f32 + 0.5),
+                        params.mass,
+                        0.1,
+                        params.color,
+                    );
+                    segment.rotate(step.to_angle() - std::f32::consts::FRAC_PI_2);
+                    segment.friction = 0.5;
+                    rigidbodys.push(segment);
+                    rope.bodies.push(rigidbodys.len() - 1);
+                }
+                // Every joint point lies on a segment end, where the previous and the next body meet
+                let mut chain: Vec<usize> = vec![];
+                chain.extend(body_a);
+                chain.extend(&rope.bodies);
+                chain.extend(body_b);
+                let first_point = if body_a.is_some() { 0 } else { 1 };
+                for (i, pair) in chain.windows(2).enumerate() {
+                    let point = start + step * (first_point + i) as f32;
+                    let anchor_a = rigidbodys[pair[0]].inverse_transform_point(point);
+                    let anchor_b = rigidbodys[pair[1]].inverse_transform_point(point);
+                    pivot_joints.push(PivotJoint::new(anchor_a, anchor_b, rigidbodys, pair[0], pair[1]));
+                    rope.pivot_joints.push(pivot_joints.len() - 1);
+                }
+            }
+            ChainLink::Distance => {
+                // Attached ends use the body itself as the end node
+                let mut chain: Vec<(usize, Vec2)> = vec![];
+                for i in 0..=segments {
+                    let point = start + step * i as f32;
+                    let attached = match i {
+                        0 => body_a,
+                        i if i == segments => body_b,
+                        _ => None,
+                    };
+                    match attached {
+                        Some(body) => chain.push((body, rigidbodys[body].inverse_transform_point(point))),
+                        None => {
+                            rigidbodys.push(particle(point, params));
+                            rope.bodies.push(rigidbodys.len() - 1);
+                            chain.push((rigidbodys.len() - 1, Vec2::ZERO));
+                        }
+                    }
+                }
+                for pair in chain.windows(2) {
+                    distance_joints.push(DistanceJoint::new(pair[0].1, pair[1].1, rigidbodys, pair[0].0, pair[1].0));
+                    rope.distance_joints.push(distance_joints.len() - 1);
+                }
+            }
+        }
+        rope
+    }
+}
+
+impl Cloth {
+    /// Grid of particles `spacing` apart hanging down from `top_left`, linked to their horizontal and vertical
+    /// neighbours by distance joints. The particles at the `(column, row)` positions in `pinned` stay in place.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        top_left: Vec2,
+        columns: usize,
+        rows: usize,
+        spacing: f32,
+        pinned: &[(usize, usize)],
+        params: &ChainParams,
+        rigidbodys: &mut Vec<Rigidbody>,
+        distance_joints: &mut Vec<DistanceJoint>,
+    ) -> Self {
+        let columns = columns.max(1);
+        let rows = rows.max(1);
+        let mut cloth = Cloth { columns, rows, ..Default::default() };
+        for row in 0..rows {
+            for column in 0..columns {
+                let mut particle = particle(top_left + Vec2::new(column as f32, -(row as f32)) * spacing, params);
+                if pinned.contains(&(column, row)) {
+                    pin(&mut particle);
+                }
+                rigidbodys.push(particle);
+                cloth.particles.push(rigidbodys.len() - 1);
+            }
+        }
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let body = cloth.particle(column, row);
+                let mut neighbours = vec![];
+                if column + 1 < columns {
+                    neighbours.push(cloth.particle(column + 1, row));
+                }
+                if row + 1 < rows {
+                    neighbours.push(cloth.particle(column, row + 1));
+                }
+                for neighbour in neighbours {
+                    distance_joints.push(DistanceJoint::new(Vec2::ZERO, Vec2::ZERO, rigidbodys, body, neighbour));
+                    cloth.distance_joints.push(distance_joints.len() - 1);
+                }
+            }
+        }
+        cloth
+    }
+
+    /// Index of the particle at `column` and `row`.
+    pub fn particle(&self, column: usize, row: usize) -> usize {
+        self.particles[row * self.columns + column]
+    }
+}
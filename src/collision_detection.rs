@@ -1,29 +1,9 @@
 use glam::Vec2;
-use crate::rigidbody::Rigidbody;
+use crate::rigidbody::{outline, Rigidbody, Shape};
+use crate::triangulation::{polygon_centroid, signed_area};
 
 
-pub fn sat_collision(shape1: &Rigidbody, shape2: &Rigidbody) -> [Vec2; 2] {
-    // Simple circle check
-    if shape1.center.distance(shape2.center) > shape1.radius + shape2.radius {
-        return [
-            Vec2 {
-                x: -133.7,
-                y: -133.7,
-            },
-            Vec2 { x: -133.7, y: 0.0 },
-        ];
-    }
-    // Treat shapes with more than 31 vertices as circles
-    if shape1.vertices.len() >= 32 && shape2.vertices.len() >= 32 {
-        let mut delta = shape2.center - shape1.center;
-        let dist = delta.length();
-        let overlap = shape1.radius + shape2.radius - dist; // Total overlap amount
-        if delta == Vec2::ZERO {
-            delta = Vec2::new(rand::random::<f32>(), rand::random::<f32>());
-        }
-        return [delta.normalize(), Vec2 { x: overlap, y: 1.0 }];
-    }
-
+fn sat_polygons(shape1: &[Vec2], shape2: &[Vec2]) -> [Vec2; 2] {
     let mut overlap: f32 = 2.0_f32.powf(32.0);
     let mut smallest: Vec2 = Vec2 { x: 0.0, y: 0.0 };
     let axes1: Vec<Vec2> = get_axes(shape1);
@@ -99,11 +79,11 @@ pub fn sat_collision(shape1: &Rigidbody, shape2: &Rigidbody) -> [Vec2; 2] {
         },
     ]
 }
-fn get_axes(shape: &Rigidbody) -> Vec<Vec2> {
+fn get_axes(shape: &[Vec2]) -> Vec<Vec2> {
     let mut axes: Vec<Vec2> = vec![];
-    for i in 0..shape.vertices.len() {
-        let p1: &Vec2 = &shape.vertices[i];
-        let p2: &Vec2 = &shape.vertices[if i + 1 == shape.vertices.len() {
+    for i in 0..shape.len() {
+        let p1: &Vec2 = &shape[i];
+        let p2: &Vec2 = &shape[if i + 1 == shape.len() {
             0
         } else {
             i + 1
@@ -117,12 +97,12 @@ fn get_axes(shape: &Rigidbody) -> Vec<Vec2> {
     axes
 }
 
-fn project(shape: &Rigidbody, axis: &Vec2) -> Vec2 {
-    let mut min: f32 = axis.dot(shape.vertices[0]);
+fn project(shape: &[Vec2], axis: &Vec2) -> Vec2 {
+    let mut min: f32 = axis.dot(shape[0]);
     let mut max: f32 = min;
 
-    for i in 1..shape.vertices.len() {
-        let p: f32 = axis.dot(shape.vertices[i]);
+    for i in 1..shape.len() {
+        let p: f32 = axis.dot(shape[i]);
         if p < min {
             min = p;
         } else if p > max {
@@ -167,21 +147,21 @@ fn clip(v1: Vec2, v2: Vec2, normal: Vec2, offset: f32) -> Vec<Vec2> {
     }
     clipped
 }
-fn best_edge(polygon: &Rigidbody, normal: Vec2) -> (Vec2, Vec2, Vec2) {
-    let c = polygon.vertices.len();
+fn best_edge(polygon: &[Vec2], normal: Vec2) -> (Vec2, Vec2, Vec2) {
+    let c = polygon.len();
     let mut max = f32::MIN;
     let mut index = 0;
     for i in 0..c {
-        let projection = normal.dot(polygon.vertices[i]);
+        let projection = normal.dot(polygon[i]);
         if projection > max {
             max = projection;
             index = i
         }
     }
 
-    let v = polygon.vertices[index];
-    let v1 = polygon.vertices[(index + 1) % c];
-    let v0 = polygon.vertices[(index + c - 1) % c];
+    let v = polygon[index];
+    let v1 = polygon[(index + 1) % c];
+    let v0 = polygon[(index + c - 1) % c];
 
     let mut l = v - v1;
     let mut r = v - v0;
@@ -196,19 +176,21 @@ fn best_edge(polygon: &Rigidbody, normal: Vec2) -> (Vec2, Vec2, Vec2) {
     }
 }
 
-pub fn find_contact_points(
-    polygon1: &Rigidbody,
-    polygon2: &Rigidbody,
+fn polygon_contact_points(
+    polygon1: &[Vec2],
+    center1: Vec2,
+    polygon2: &[Vec2],
+    center2: Vec2,
     mtv: &[Vec2; 2],
 ) -> Vec<Vec2> {
     let normal;
-    if mtv[0].normalize().dot(polygon1.center) < mtv[0].normalize().dot(polygon2.center) {
+    if mtv[0].normalize().dot(center1) < mtv[0].normalize().dot(center2) {
         normal = mtv[0].normalize();
     } else {
         normal = -mtv[0].normalize();
     }
-    let edge1 = best_edge(&polygon1, normal);
-    let edge2 = best_edge(&polygon2, -normal);
+    let edge1 = best_edge(polygon1, normal);
+    let edge2 = best_edge(polygon2, -normal);
     let edge1v = edge1.1 - edge1.0;
     let edge2v = edge2.1 - edge2.0;
 
@@ -251,3 +233,525 @@ pub fn find_contact_points(
 
     clipped
 }
+
+/// Narrowphase result, `normal` points from the first body towards the second.
+#[derive(Clone, Debug, Default)]
+pub struct Manifold {
+    pub normal: Vec2,
+    pub penetration: f32,
+    pub contacts: Vec<Vec2>,
+    /// Mixed restitution of the two touching pieces.
+    pub restitution: f32,
+    /// Mixed Coulomb friction coefficient of the two touching pieces.
+    pub friction: f32,
+}
+
+impl Manifold {
+    fn flipped(mut self) -> Self {
+        self.normal = -self.normal;
+        self
+    }
+}
+
+/// One convex piece of a body as seen by the narrowphase.
+#[derive(Clone, Copy)]
+pub struct Collider<'a> {
+    pub shape: Shape,
+    pub vertices: &'a [Vec2],
+    pub center: Vec2,
+    pub restitution: f32,
+    pub friction: f32,
+}
+
+/// Tests every convex piece of `body1` against every piece of `body2`.
+pub fn collide(body1: &Rigidbody, body2: &Rigidbody) -> Vec<Manifold> {
+    if body1.center.distance(body2.center) > body1.radius + body2.radius {
+        return vec![];
+    }
+    let colliders2 = body2.colliders();
+    let mut manifolds = vec![];
+    for collider1 in body1.colliders() {
+        for collider2 in &colliders2 {
+            if let Some(mut manifold) = collide_colliders(&collider1, collider2) {
+                manifold.restitution = (collider1.restitution + collider2.restitution) * 0.5;
+                manifold.friction = (collider1.friction + collider2.friction) * 0.5;
+                manifolds.push(manifold);
+            }
+        }
+    }
+    manifolds
+}
+
+/// Where a ray enters a body, found by [`raycast`].
+#[derive(Clone, Copy, Debug)]
+pub struct RayHit {
+    /// Distance from the origin of the ray.
+    pub distance: f32,
+    pub point: Vec2,
+    /// Outward normal of the surface that was hit.
+    pub normal: Vec2,
+}
+
+/// First point within `max_distance` where the ray from `origin` along the unit vector `direction` enters `body`.
+/// Pieces of the body containing the origin are not hit.
+pub fn raycast(body: &Rigidbody, origin: Vec2, direction: Vec2, max_distance: f32) -> Option<RayHit> {
+    // Bounding circle test first, most rays pass far from most bodies
+    let along = (body.center - origin).dot(direction).clamp(0.0, max_distance);
+    if (origin + direction * along).distance(body.center) > body.radius {
+        return None;
+    }
+    let mut closest: Option<RayHit> = None;
+    for collider in body.colliders() {
+        let hit = match collider.shape {
+            Shape::Circle { radius } => raycast_circle(origin, direction, collider.center, radius),
+            Shape::Polygon => raycast_convex(origin, direction, collider.vertices),
+            Shape::Capsule { .. } => {
+                raycast_convex(origin, direction, &outline(collider.shape, collider.vertices, collider.center, body.angle))
+            }
+        };
+        if let Some(hit) = hit
+            && hit.distance <= max_distance
+            && closest.is_none_or(|closest| hit.distance < closest.distance)
+        {
+            closest = Some(hit);
+        }
+    }
+    closest
+}
+
+fn raycast_circle(origin: Vec2, direction: Vec2, center: Vec2, radius: f32) -> Option<RayHit> {
+    let offset = origin - center;
+    let c = offset.length_squared() - radius * radius;
+    if c <= 0.0 {
+        return None;
+    }
+    let b = offset.dot(direction);
+    let discriminant = b * b - c;
+    if b > 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let distance = -b - discriminant.sqrt();
+    let point = origin + direction * distance;
+    Some(RayHit { distance, point, normal: (point - center) / radius })
+}
+
+/// Clips the ray against the half planes of every edge, the last edge it enters through is the one hit.
+fn raycast_convex(origin: Vec2, direction: Vec2, vertices: &[Vec2]) -> Option<RayHit> {
+    if vertices.len() < 3 {
+        return None;
+    }
+    let winding = if signed_area(vertices) < 0.0 { -1.0 } else { 1.0 };
+    let mut enter = 0.0;
+    let mut exit = f32::MAX;
+    let mut enter_normal = None;
+    for i in 0..vertices.len() {
+        let start = vertices[i];
+        let normal = outward_normal(start, vertices[(i + 1) % vertices.len()], winding);
+        let distance = normal.dot(start - origin);
+        let speed = normal.dot(direction);
+        if speed == 0.0 {
+            if distance < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let t = distance / speed;
+        if speed < 0.0 {
+            if t > enter || enter_normal.is_none() && t >= enter {
+                enter = t;
+                enter_normal = Some(normal);
+            }
+        } else {
+            exit = exit.min(t);
+        }
+        if enter > exit {
+            return None;
+        }
+    }
+    let normal = enter_normal?;
+    Some(RayHit { distance: enter, point: origin + direction * enter, normal })
+}
+
+pub fn intersects(body1: &Rigidbody, body2: &Rigidbody) -> bool {
+    !collide(body1, body2).is_empty()
+}
+
+/// Dispatches to the narrowphase routine matching the shapes of both pieces.
+pub fn collide_colliders(collider1: &Collider, collider2: &Collider) -> Option<Manifold> {
+    match (collider1.shape, collider2.shape) {
+        (Shape::Polygon, Shape::Polygon) => polygon_polygon(collider1, collider2),
+        (Shape::Circle { radius: radius1 }, Shape::Circle { radius: radius2 }) => {
+            round_round(collider1.center, radius1, collider2.center, radius2)
+        }
+        (Shape::Polygon, Shape::Circle { radius }) => {
+            circle_polygon(collider2.center, radius, collider1.vertices)
+        }
+        (Shape::Circle { radius }, Shape::Polygon) => {
+            circle_polygon(collider1.center, radius, collider2.vertices).map(Manifold::flipped)
+        }
+        (Shape::Capsule { radius }, Shape::Circle { radius: circle_radius }) => {
+            let closest = closest_point_on_segment(collider2.center, collider1.vertices[0], collider1.vertices[1]);
+            round_round(closest, radius, collider2.center, circle_radius)
+        }
+        (Shape::Circle { radius: circle_radius }, Shape::Capsule { radius }) => {
+            let closest = closest_point_on_segment(collider1.center, collider2.vertices[0], collider2.vertices[1]);
+            round_round(collider1.center, circle_radius, closest, radius)
+        }
+        (Shape::Capsule { radius: radius1 }, Shape::Capsule { radius: radius2 }) => {
+            capsule_capsule(collider1.vertices, radius1, collider2.vertices, radius2)
+        }
+        (Shape::Capsule { radius }, Shape::Polygon) => {
+            capsule_polygon(collider1.vertices, radius, collider2.vertices)
+        }
+        (Shape::Polygon, Shape::Capsule { radius }) => {
+            capsule_polygon(collider2.vertices, radius, collider1.vertices).map(Manifold::flipped)
+        }
+    }
+}
+
+fn polygon_polygon(collider1: &Collider, collider2: &Collider) -> Option<Manifold> {
+    let result = sat_polygons(collider1.vertices, collider2.vertices);
+    if result[1].y == 0.0 {
+        return None;
+    }
+    let mut normal = result[0].normalize();
+    if normal.dot(collider2.center - collider1.center) < 0.0 {
+        normal = -normal;
+    }
+    let contacts = polygon_contact_points(collider1.vertices, collider1.center, collider2.vertices, collider2.center, &result);
+    Some(Manifold { normal, penetration: result[1].x, contacts, ..Default::default() })
+}
+
+fn round_round(center1: Vec2, radius1: f32, center2: Vec2, radius2: f32) -> Option<Manifold> {
+    let delta = center2 - center1;
+    let distance = delta.length();
+    let penetration = radius1 + radius2 - distance;
+    if penetration <= 0.0 {
+        return None;
+    }
+    let normal = if distance > f32::EPSILON { delta / distance } else { Vec2::Y };
+    let contact = center1 + normal * (radius1 - penetration * 0.5);
+    Some(Manifold { normal, penetration, contacts: vec![contact], ..Default::default() })
+}
+
+/// Normal points from the polygon towards the circle.
+fn circle_polygon(center: Vec2, radius: f32, vertices: &[Vec2]) -> Option<Manifold> {
+    let n = vertices.len();
+    let winding = if signed_area(vertices) < 0.0 { -1.0 } else { 1.0 };
+    let mut separation = f32::MIN;
+    let mut edge = 0;
+    for i in 0..n {
+        let normal = outward_normal(vertices[i], vertices[(i + 1) % n], winding);
+        let s = normal.dot(center - vertices[i]);
+        if s > radius {
+            return None;
+        }
+        if s > separation {
+            separation = s;
+            edge = i;
+        }
+    }
+
+    let v1 = vertices[edge];
+    let v2 = vertices[(edge + 1) % n];
+    let face_normal = outward_normal(v1, v2, winding);
+    // Center inside the polygon
+    if separation < f32::EPSILON {
+        return Some(Manifold {
+            normal: face_normal,
+            penetration: radius - separation,
+            contacts: vec![center - face_normal * radius],
+            ..Default::default()
+        });
+    }
+
+    let u1 = (center - v1).dot(v2 - v1);
+    let u2 = (center - v2).dot(v1 - v2);
+    let vertex = if u1 <= 0.0 {
+        Some(v1)
+    } else if u2 <= 0.0 {
+        Some(v2)
+    } else {
+        None
+    };
+    match vertex {
+        Some(vertex) => {
+            let distance = center.distance(vertex);
+            if distance > radius || distance <= f32::EPSILON {
+                return None;
+            }
+            Some(Manifold {
+                normal: (center - vertex) / distance,
+                penetration: radius - distance,
+                contacts: vec![vertex],
+                ..Default::default()
+            })
+        }
+        None => Some(Manifold {
+            normal: face_normal,
+            penetration: radius - separation,
+            contacts: vec![center - face_normal * separation],
+            ..Default::default()
+        }),
+    }
+}
+
+fn capsule_capsule(spine1: &[Vec2], radius1: f32, spine2: &[Vec2], radius2: f32) -> Option<Manifold> {
+    let (a1, b1) = (spine1[0], spine1[1]);
+    let (a2, b2) = (spine2[0], spine2[1]);
+    if segments_intersect(a1, b1, a2, b2) {
+        return sat_rounded(spine1, radius1, spine2, radius2);
+    }
+    let pairs = [
+        (a1, closest_point_on_segment(a1, a2, b2)),
+        (b1, closest_point_on_segment(b1, a2, b2)),
+        (closest_point_on_segment(a2, a1, b1), a2),
+        (closest_point_on_segment(b2, a1, b1), b2),
+    ];
+    let reach = radius1 + radius2;
+    let (closest1, closest2) = closest_pair(&pairs);
+    let distance = closest1.distance(closest2);
+    if distance >= reach {
+        return None;
+    }
+    if distance <= f32::EPSILON {
+        return sat_rounded(spine1, radius1, spine2, radius2);
+    }
+    let normal = (closest2 - closest1) / distance;
+
+    let mut contacts: Vec<Vec2> = vec![];
+    for (p1, p2) in pairs {
+        let pair_distance = p1.distance(p2);
+        if pair_distance < reach && is_aligned(p1, p2, normal) {
+            push_unique(&mut contacts, p1 + normal * (radius1 - (reach - pair_distance) * 0.5));
+        }
+    }
+    Some(Manifold { normal, penetration: reach - distance, contacts, ..Default::default() })
+}
+
+/// Normal points from the capsule towards the polygon.
+fn capsule_polygon(spine: &[Vec2], radius: f32, vertices: &[Vec2]) -> Option<Manifold> {
+    let (a, b) = (spine[0], spine[1]);
+    let n = vertices.len();
+    let core_overlaps = point_in_convex(a, vertices)
+        || point_in_convex(b, vertices)
+        || (0..n).any(|i| segments_intersect(a, b, vertices[i], vertices[(i + 1) % n]));
+    if core_overlaps {
+        return sat_rounded(spine, radius, vertices, 0.0);
+    }
+
+    let mut pairs: Vec<(Vec2, Vec2)> = vec![
+        (a, closest_point_on_polygon(a, vertices)),
+        (b, closest_point_on_polygon(b, vertices)),
+    ];
+    for &vertex in vertices {
+        pairs.push((closest_point_on_segment(vertex, a, b), vertex));
+    }
+    let (closest1, closest2) = closest_pair(&pairs);
+    let distance = closest1.distance(closest2);
+    if distance >= radius || distance <= f32::EPSILON {
+        return None;
+    }
+    let normal = (closest2 - closest1) / distance;
+
+    let mut contacts: Vec<Vec2> = vec![];
+    for (p1, p2) in pairs {
+        if p1.distance(p2) < radius && is_aligned(p1, p2, normal) {
+            push_unique(&mut contacts, p2);
+        }
+    }
+    Some(Manifold { normal, penetration: radius - distance, contacts, ..Default::default() })
+}
+
+/// SAT between two convex cores inflated by a radius, used once the cores overlap.
+fn sat_rounded(vertices1: &[Vec2], radius1: f32, vertices2: &[Vec2], radius2: f32) -> Option<Manifold> {
+    let mut axes = edge_normals(vertices1);
+    axes.extend(edge_normals(vertices2));
+
+    let mut normal = Vec2::ZERO;
+    let mut penetration = f32::MAX;
+    for axis in axes {
+        let (min1, max1) = project_points(vertices1, axis);
+        let (min2, max2) = project_points(vertices2, axis);
+        let overlap = (max1 + radius1).min(max2 + radius2) - (min1 - radius1).max(min2 - radius2);
+        if overlap <= 0.0 {
+            return None;
+        }
+        if overlap < penetration {
+            penetration = overlap;
+            normal = axis;
+        }
+    }
+    if normal.dot(polygon_centroid(vertices2) - polygon_centroid(vertices1)) < 0.0 {
+        normal = -normal;
+    }
+
+    // Deepest points of the first shape that lie past the start of the second one
+    let (min2, _) = project_points(vertices2, normal);
+    let mut contacts: Vec<Vec2> = vec![];
+    for &vertex in vertices1 {
+        let point = vertex + normal * radius1;
+        if point.dot(normal) >= min2 - radius2 {
+            push_unique(&mut contacts, point);
+        }
+    }
+    if contacts.is_empty() {
+        let (_, max1) = project_points(vertices1, normal);
+        if let Some(&vertex) = vertices1.iter().find(|v| v.dot(normal) >= max1 - f32::EPSILON) {
+            contacts.push(vertex + normal * radius1);
+        }
+    }
+    Some(Manifold { normal, penetration, contacts, ..Default::default() })
+}
+
+pub fn closest_point_on_segment(point: Vec2, start: Vec2, end: Vec2) -> Vec2 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared <= f32::EPSILON {
+        return start;
+    }
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    start + segment * t
+}
+
+pub fn closest_point_on_polygon(point: Vec2, vertices: &[Vec2]) -> Vec2 {
+    let n = vertices.len();
+    let mut closest = vertices[0];
+    let mut closest_distance = f32::MAX;
+    for i in 0..n {
+        let candidate = closest_point_on_segment(point, vertices[i], vertices[(i + 1) % n]);
+        let distance = candidate.distance_squared(point);
+        if distance < closest_distance {
+            closest_distance = distance;
+            closest = candidate;
+        }
+    }
+    closest
+}
+
+pub fn point_in_convex(point: Vec2, vertices: &[Vec2]) -> bool {
+    let n = vertices.len();
+    if n < 3 {
+        return false;
+    }
+    let mut positive = false;
+    let mut negative = false;
+    for i in 0..n {
+        let edge = vertices[(i + 1) % n] - vertices[i];
+        let cross = edge.perp_dot(point - vertices[i]);
+        positive |= cross > 0.0;
+        negative |= cross < 0.0;
+    }
+    !(positive && negative)
+}
+
+fn segments_intersect(a1: Vec2, b1: Vec2, a2: Vec2, b2: Vec2) -> bool {
+    let d1 = (b2 - a2).perp_dot(a1 - a2);
+    let d2 = (b2 - a2).perp_dot(b1 - a2);
+    let d3 = (b1 - a1).perp_dot(a2 - a1);
+    let d4 = (b1 - a1).perp_dot(b2 - a1);
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+fn closest_pair(pairs: &[(Vec2, Vec2)]) -> (Vec2, Vec2) {
+    let mut closest = pairs[0];
+    for &pair in &pairs[1..] {
+        if pair.0.distance_squared(pair.1) < closest.0.distance_squared(closest.1) {
+            closest = pair;
+        }
+    }
+    closest
+}
+
+/// Whether the pair is separated (nearly) along the contact normal, so it touches the same feature.
+fn is_aligned(p1: Vec2, p2: Vec2, normal: Vec2) -> bool {
+    (p2 - p1).dot(normal) >= p1.distance(p2) * 0.99
+}
+
+fn push_unique(points: &mut Vec<Vec2>, point: Vec2) {
+    if points.iter().all(|p| p.distance_squared(point) > 1e-8) {
+        points.push(point);
+    }
+}
+
+fn outward_normal(start: Vec2, end: Vec2, winding: f32) -> Vec2 {
+    let edge = end - start;
+    Vec2::new(edge.y, -edge.x).normalize_or_zero() * winding
+}
+
+fn edge_normals(vertices: &[Vec2]) -> Vec<Vec2> {
+    let n = vertices.len();
+    (0..n)
+        .map(|i| (vertices[(i + 1) % n] - vertices[i]).perp().normalize_or_zero())
+        .filter(|axis| *axis != Vec2::ZERO)
+        .collect()
+}
+
+fn project_points(vertices: &[Vec2], axis: Vec2) -> (f32, f32) {
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    for vertex in vertices {
+        let p = axis.dot(*vertex);
+        min = min.min(p);
+        max = max.max(p);
+    }
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColorRGBA;
+
+    fn square() -> Vec<Vec2> {
+        vec![Vec2::new(-1.0, -1.0), Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0), Vec2::new(-1.0, 1.0)]
+    }
+
+    #[test]
+    fn circle_polygon_face_contact() {
+        let manifold = circle_polygon(Vec2::new(1.5, 0.0), 1.0, &square()).unwrap();
+        assert!(manifold.normal.abs_diff_eq(Vec2::X, 1e-6));
+        assert!((manifold.penetration - 0.5).abs() < 1e-6);
+        assert!(manifold.contacts[0].abs_diff_eq(Vec2::new(1.0, 0.0), 1e-6));
+    }
+
+    #[test]
+    fn circle_polygon_vertex_contact() {
+        let manifold = circle_polygon(Vec2::new(1.5, 1.5), 1.0, &square()).unwrap();
+        assert!(manifold.normal.abs_diff_eq(Vec2::ONE.normalize(), 1e-6));
+        assert!((manifold.penetration - (1.0 - 0.5f32.sqrt())).abs() < 1e-6);
+        assert!(manifold.contacts[0].abs_diff_eq(Vec2::ONE, 1e-6));
+        assert!(circle_polygon(Vec2::new(1.8, 1.8), 1.0, &square()).is_none());
+    }
+
+    #[test]
+    fn capsule_capsule_parallel() {
+        let spine1 = [Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0)];
+        let spine2 = [Vec2::new(-1.0, 0.8), Vec2::new(1.0, 0.8)];
+        let manifold = capsule_capsule(&spine1, 0.5, &spine2, 0.5).unwrap();
+        assert!(manifold.normal.abs_diff_eq(Vec2::Y, 1e-6));
+        assert!((manifold.penetration - 0.2).abs() < 1e-6);
+        assert_eq!(manifold.contacts.len(), 2);
+        for contact in manifold.contacts {
+            assert!((contact.y - 0.4).abs() < 1e-6);
+        }
+        let spine2 = [Vec2::new(-1.0, 1.1), Vec2::new(1.0, 1.1)];
+        assert!(capsule_capsule(&spine1, 0.5, &spine2, 0.5).is_none());
+    }
+
+    #[test]
+    fn raycast_hit_distance() {
+        let square = Rigidbody::rectangle(2.0, 2.0, Vec2::ZERO, 1.0, 0.0, ColorRGBA::white());
+        let hit = raycast(&square, Vec2::new(-5.0, 0.5), Vec2::X, 10.0).unwrap();
+        assert!((hit.distance - 4.0).abs() < 1e-5);
+        assert!(hit.normal.abs_diff_eq(-Vec2::X, 1e-6));
+
+        let circle = Rigidbody::circle(1.0, Vec2::ZERO, 1.0, 0.0, ColorRGBA::white());
+        let hit = raycast(&circle, Vec2::new(0.0, -5.0), Vec2::Y, 10.0).unwrap();
+        assert!((hit.distance - 4.0).abs() < 1e-5);
+        assert!(hit.point.abs_diff_eq(-Vec2::Y, 1e-5));
+
+        assert!(raycast(&circle, Vec2::new(0.0, -5.0), Vec2::Y, 3.0).is_none());
+        assert!(raycast(&circle, Vec2::new(2.0, -5.0), Vec2::Y, 10.0).is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use glam::Vec2;
-use crate::collision_detection::{find_contact_points, sat_collision};
+use crate::collision_detection::collide;
 use crate::physics::PhysicsSystem;
 use crate::Rigidbody;
 
@@ -100,6 +100,9 @@ impl PhysicsSystem {
     }
 
     pub fn collision_resolution(&mut self) {
+        for polygon in &mut self.polygons {
+            polygon.contact_impulse = Vec2::ZERO;
+        }
         let sections = self.separate_into_section();
         for section in sections {
             for i in 0..section.len() {
@@ -126,7 +129,7 @@ impl PhysicsSystem {
             }
         }
     }
-    fn resolve_contact_velocity(body1: &mut Rigidbody, body2: &mut Rigidbody, contact: Vec2, normal: Vec2) {
+    fn resolve_contact_velocity(body1: &mut Rigidbody, body2: &mut Rigidbody, contact: Vec2, normal: Vec2, restitution: f32, friction: f32) {
         // Effective masses
         let m1 = if body1.is_static { 0.0 } else { 1.0 / body1.mass };
         let m2 = if body2.is_static { 0.0 } else { 1.0 / body2.mass };
@@ -147,7 +150,7 @@ impl PhysicsSystem {
 
         // Restitution OFF for resting contacts (prevents bounciness in stacks)
         let restitution_vel_threshold = 1e-2; // tweak
-        let mut e = (body1.restitution + body2.restitution) * 0.5;
+        let mut e = restitution;
         if vel_n.abs() < restitution_vel_threshold { e = 0.0; }
 
         if vel_n > 0.0 {
@@ -166,25 +169,32 @@ impl PhysicsSystem {
         body2.velocity += impulse_n * m2;
         body1.angular_velocity -= rn1 * jn * i1;
         body2.angular_velocity += rn2 * jn * i2;
+        body1.contact_impulse -= impulse_n;
+        body2.contact_impulse += impulse_n;
 
         // Friction (Coulomb) — uses same effective mass in tangent dir
-        //let tangent = (rv - normal * vel_n).normalize_or_zero();
-        //let vel_t = rv.dot(tangent);
-        //let k_tangent = m1 + m2
-        //    + (r1.perp_dot(tangent)).powi(2) * i1
-        //    + (r2.perp_dot(tangent)).powi(2) * i2;
+        if friction <= 0.0 { return; }
+        let v1 = body1.velocity + r1.perp() * body1.angular_velocity;
+        let v2 = body2.velocity + r2.perp() * body2.angular_velocity;
+        let rv = v2 - v1;
+        let tangent = (rv - normal * rv.dot(normal)).normalize_or_zero();
+        let vel_t = rv.dot(tangent);
+        let k_tangent = m1 + m2
+            + (r1.perp_dot(tangent)).powi(2) * i1
+            + (r2.perp_dot(tangent)).powi(2) * i2;
 
-        //let jt = -vel_t / k_tangent;
-        //let mu = (body1.friction + body2.friction) * 0.5;
+        let jt = -vel_t / k_tangent;
 
         // Clamp friction to Coulomb cone
-        //let jt_clamped = jt.clamp(-jn * mu, jn * mu);
-        //let impulse_t = tangent * jt_clamped;
-
-        //body1.velocity -= impulse_t * m1;
-        //body2.velocity += impulse_t * m2;
-        //body1.angular_velocity -= r1.perp_dot(impulse_t) * i1;
-        //body2.angular_velocity += r2.perp_dot(impulse_t) * i2;
+        let jt_clamped = jt.clamp(-jn * friction, jn * friction);
+        let impulse_t = tangent * jt_clamped;
+
+        body1.velocity -= impulse_t * m1;
+        body2.velocity += impulse_t * m2;
+        body1.angular_velocity -= r1.perp_dot(impulse_t) * i1;
+        body2.angular_velocity += r2.perp_dot(impulse_t) * i2;
+        body1.contact_impulse -= impulse_t;
+        body2.contact_impulse += impulse_t;
     }
 
     fn positional_correction_pair(
@@ -211,31 +221,24 @@ impl PhysicsSystem {
 
 
     pub fn check_and_resolve(body1: &mut Rigidbody, body2: &mut Rigidbody) {
-        let result = sat_collision(&body1, &body2);
-        // assume: result[1].y != 0 indicates collision, result[0] = axis, result[1].x = depth
-        if result[1].y == 0.0 { return; }
-
-        // Ensure axis points from body1 -> body2 (have SAT return this if possible)
-        let mut normal = result[0].normalize();
-        if normal.dot(body2.center - body1.center) < 0.0 {
-            normal = -normal;
-        }
-        let penetration = result[1].x;
-
-        // Build manifold
-        let contacts = find_contact_points(&body1, &body2, &result);
-        if contacts.is_empty() { return; }
-
-        // --- Velocity solver (sequential impulses). Iterate for stacks.
-        const VEL_ITERS: usize = 1; // tweak (4–10)
-        for _ in 0..VEL_ITERS {
-            for &c in &contacts {
-                Self::resolve_contact_velocity(body1, body2, c, normal);
+        // Concave bodies can touch in several places, one manifold per pair of convex pieces
+        for manifold in collide(body1, body2) {
+            let normal = manifold.normal;
+            let penetration = manifold.penetration;
+            let contacts = &manifold.contacts;
+            if contacts.is_empty() { continue; }
+
+            // --- Velocity solver (sequential impulses). Iterate for stacks.
+            const VEL_ITERS: usize = 1; // tweak (4–10)
+            for _ in 0..VEL_ITERS {
+                for &c in contacts {
+                    Self::resolve_contact_velocity(body1, body2, c, normal, manifold.restitution, manifold.friction);
+                }
             }
-        }
 
-        // --- Single positional correction for the pair
-        Self::positional_correction_pair(body1, body2, normal, penetration, contacts.len());
+            // --- Single positional correction for the pair
+            Self::positional_correction_pair(body1, body2, normal, penetration, contacts.len());
+        }
     }
 
 
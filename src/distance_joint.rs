@@ -0,0 +1,152 @@
+use glam::Vec2;
+use crate::{ColorRGBA, Rigidbody};
+
+/// Rigid rod between two anchors, optionally allowed to change length between `min_length` and `max_length`.
+#[derive(Clone)]
+pub struct DistanceJoint {
+    local_anchor_a: Vec2,
+    local_anchor_b: Vec2,
+    pub(crate) body_a: usize,
+    pub(crate) body_b: usize,
+
+    /// Distance kept between the anchors while the limit is disabled.
+    pub length: f32,
+
+    /// Replaces the fixed `length` with a range the anchors may move freely in.
+    pub enable_limit: bool,
+    pub min_length: f32,
+    pub max_length: f32,
+
+    /// Baumgarte stabilization factor for positional drift (small: 0.01..0.2)
+    pub beta: f32,
+}
+
+impl DistanceJoint {
+    /// The joint length starts as the current distance between the anchors.
+    pub fn new(local_anchor_a: Vec2, local_anchor_b: Vec2, rigidbodys: &[Rigidbody], body_a: usize, body_b: usize) -> Self {
+        let length = rigidbodys[body_b].transform_point(local_anchor_b)
+            .distance(rigidbodys[body_a].transform_point(local_anchor_a));
+        Self {
+            local_anchor_a,
+            local_anchor_b,
+            body_a,
+            body_b,
+            length,
+            enable_limit: false,
+            min_length: length,
+            max_length: length,
+            beta: 0.1,
+        }
+    }
+
+    pub fn set_length(&mut self, length: f32) -> &mut Self {
+        self.length = length.max(0.0);
+        self
+    }
+
+    pub fn set_limits(&mut self, min_length: f32, max_length: f32) -> &mut Self {
+        self.enable_limit = true;
+        self.min_length = min_length.min(max_length).max(0.0);
+        self.max_length = max_length.max(min_length).max(0.0);
+        self
+    }
+
+    /// Current distance between the anchors.
+    pub fn current_length(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        let (anchor_a, anchor_b) = self.get_anchor_world_positions(rigidbodys);
+        anchor_a.distance(anchor_b)
+    }
+
+    pub fn solve_velocity_constraints(&self, rigidbodys: &mut [Rigidbody], dt: f32) {
+        let a;
+        let b;
+        if self.body_a > self.body_b {
+            let (left, right) = rigidbodys.split_at_mut(self.body_a);
+            b = &mut left[self.body_b];
+            a = &mut right[0];
+        } else {
+            let (left, right) = rigidbodys.split_at_mut(self.body_b);
+            a = &mut left[self.body_a];
+            b = &mut right[0];
+        }
+
+        let ra = a.rotation_matrix() * self.local_anchor_a;
+        let rb = b.rotation_matrix() * self.local_anchor_b;
+        let d = (b.center + rb) - (a.center + ra);
+        let length = d.length();
+        // Direction is undefined when the anchors coincide
+        if length <= 1e-6 {
+            return;
+        }
+        let n = d / length;
+
+        let arm_a = ra.perp_dot(n);
+        let arm_b = rb.perp_dot(n);
+        let k = 1.0 / a.mass + 1.0 / b.mass
+            + arm_a * arm_a / a.moment_of_inertia
+            + arm_b * arm_b / b.moment_of_inertia;
+        if k <= 0.0 {
+            return;
+        }
+        let stretch_speed = |a: &Rigidbody, b: &Rigidbody| {
+            n.dot(b.velocity - a.velocity) + arm_b * b.angular_velocity - arm_a * a.angular_velocity
+        };
+
+        if !self.enable_limit {
+            let bias = (self.beta / dt) * (length - self.length);
+            let impulse = -(stretch_speed(a, b) + bias) / k;
+            apply_impulse(a, b, n, arm_a, arm_b, impulse);
+            return;
+        }
+
+        // Speculative while inside the range, Baumgarte once the limit has been passed
+        let bias = |separation: f32| {
+            if separation > 0.0 { separation / dt } else { self.beta * separation / dt }
+        };
+
+        let lower_separation = length - self.min_length;
+        let impulse = (-(stretch_speed(a, b) + bias(lower_separation)) / k).max(0.0);
+        apply_impulse(a, b, n, arm_a, arm_b, impulse);
+
+        let upper_separation = self.max_length - length;
+        let impulse = (-(-stretch_speed(a, b) + bias(upper_separation)) / k).max(0.0);
+        apply_impulse(a, b, n, arm_a, arm_b, -impulse);
+    }
+
+    pub fn get_anchor_world_positions(&self, rigidbodys: &[Rigidbody]) -> (Vec2, Vec2) {
+        (
+            rigidbodys[self.body_a].transform_point(self.local_anchor_a),
+            rigidbodys[self.body_b].transform_point(self.local_anchor_b),
+        )
+    }
+
+    /// Thin rectangle drawn between the anchors.
+    pub fn create_link(&self, rigidbodys: &[Rigidbody]) -> Rigidbody {
+        let (anchor_a, anchor_b) = self.get_anchor_world_positions(rigidbodys);
+        create_link(anchor_a, anchor_b, ColorRGBA::new(1.0, 1.0, 1.0, 0.8))
+    }
+}
+
+/// Non colliding rectangle spanning from `start` to `end`, shared by the joints drawn as a line.
+pub(crate) fn create_link(start: Vec2, end: Vec2, color: ColorRGBA) -> Rigidbody {
+    let mut line = Rigidbody::rectangle(
+        start.distance(end).max(0.01),
+        0.03,
+        (start + end) * 0.5,
+        1.0,
+        0.0,
+        color,
+    );
+    line.rotate((end - start).to_angle());
+    line.collision = false;
+    line
+}
+
+/// Impulse `impulse` along `direction` on body b and against it on body a, with lever arms `arm_a` and `arm_b`.
+/// Shared by the joints acting along a single axis.
+pub(crate) fn apply_impulse(a: &mut Rigidbody, b: &mut Rigidbody, direction: Vec2, arm_a: f32, arm_b: f32, impulse: f32) {
+    a.velocity -= direction * impulse / a.mass;
+    a.angular_velocity -= arm_a * impulse / a.moment_of_inertia;
+    b.velocity += direction * impulse / b.mass;
+    b.angular_velocity += arm_b * impulse / b.moment_of_inertia;
+}
@@ -1,11 +1,28 @@
-use crate::{Parameters, Rigidbody, Spring};
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use glam::Vec2;
+use crate::{PairwiseConstants, Parameters, Rigidbody, Spring};
 use crate::physics::PhysicsSystem;
 
+/// File the energy history is exported to, relative to the working directory.
+pub const ENERGY_HISTORY_PATH: &str = "energy_history.csv";
+
 #[derive(Default)]
 pub struct Energy {
     pub kinetic_energy: f64,
+    /// Potential of the N-body gravity, the charges and the magnetic dipoles between every pair of bodies.
     pub potential_energy: f64,
+    /// Potential of the uniform gravity, zero for bodies at the `datum` height.
+    pub gravitational_energy: f64,
     pub spring_energy: f64,
+    /// Energy held by the springs of soft joints, the wheel suspensions and the mouse joint.
+    pub constraint_energy: f64,
+    pub linear_momentum: Vec2,
+    /// Angular momentum around the world origin, spin and orbital parts combined.
+    pub angular_momentum: f64,
+    /// Height the uniform gravity potential is measured from.
+    pub datum: f32,
 }
 
 
@@ -20,40 +37,149 @@ impl Energy {
         }
         kinetic_energy as f64
     }
-    pub fn calculate_fake_gravitational_energy(rigidbody: &Rigidbody, gravity: f32, origin: f32) -> f64 {
-        let height = origin - rigidbody.center.y;
-        rigidbody.mass as f64 * gravity as f64 * rigidbody.gravity_multiplier as f64 * height as f64
 
+    /// Work done against the uniform gravity `gravity` lifting the body from the datum at height `origin`.
+    pub fn calculate_uniform_gravity_energy(rigidbody: &Rigidbody, gravity: Vec2, origin: f32) -> f64 {
+        if rigidbody.is_static || rigidbody.gravity_multiplier == 0.0 {
+            return 0.0;
+        }
+        let height = rigidbody.center - Vec2::new(0.0, origin);
+        -(rigidbody.mass as f64) * rigidbody.gravity_multiplier as f64 * gravity.dot(height) as f64
     }
 
     pub fn calculate_spring_energy(spring: &Spring, rigidbodys: &Vec<Rigidbody>) -> f64 {
-        let a = &rigidbodys[spring.body_a];
-        let b = &rigidbodys[spring.body_b];
-
-        let world_anchor_a = a.center + spring.anchor_a;
-        let world_anchor_b = b.center + spring.anchor_b;
-
-        let distance = world_anchor_a.distance(world_anchor_b);
-        let stretch = distance - spring.rest_length;
-
-        (0.5 * spring.stiffness * stretch * stretch) as f64
+        if spring.broken {
+            return 0.0;
+        }
+        spring.potential_energy(rigidbodys) as f64
     }
 
-    pub fn update_energy(&mut self, rigidbodys: &Vec<Rigidbody>, springs: &Vec<Spring>, parameters: &Parameters) {
+    pub fn update_energy(&mut self, rigidbodys: &Vec<Rigidbody>, springs: &Vec<Spring>, constraint_energy: f64, parameters: &Parameters) {
         self.kinetic_energy = 0.0;
         self.spring_energy = 0.0;
         self.potential_energy = 0.0;
+        self.gravitational_energy = 0.0;
+        self.linear_momentum = Vec2::ZERO;
+        self.angular_momentum = 0.0;
+        let gravity = if parameters.gravity { parameters.gravity_force } else { Vec2::ZERO };
         for polygon in rigidbodys {
             self.kinetic_energy += Self::calculate_kinetic_energy(polygon);
+            self.gravitational_energy += Self::calculate_uniform_gravity_energy(polygon, gravity, self.datum);
+            // Static bodies have a near infinite mass standing in for the ground
+            if !polygon.is_static {
+                self.linear_momentum += polygon.mass * polygon.velocity;
+                self.angular_momentum += (polygon.moment_of_inertia * polygon.angular_velocity
+                    + polygon.mass * polygon.center.perp_dot(polygon.velocity)) as f64;
+            }
         }
-        self.potential_energy = PhysicsSystem::calculate_gravitational_energy(rigidbodys, parameters.gravitational_constant) as f64;
+        self.potential_energy = PhysicsSystem::calculate_pairwise_energy(rigidbodys, &PairwiseConstants::new(parameters)) as f64;
         for spring in springs {
             self.spring_energy += Self::calculate_spring_energy(spring, rigidbodys);
         }
+        self.constraint_energy = constraint_energy;
     }
 
     pub fn get_energy(&self) -> f64 {
-        self.kinetic_energy + self.spring_energy + self.potential_energy
+        self.kinetic_energy + self.spring_energy + self.potential_energy + self.gravitational_energy + self.constraint_energy
+    }
+}
+
+/// Energy and momentum totals at one point in simulated time.
+#[derive(Clone, Copy, Debug)]
+pub struct EnergySample {
+    pub time: f32,
+    pub kinetic_energy: f64,
+    pub gravitational_energy: f64,
+    pub potential_energy: f64,
+    pub spring_energy: f64,
+    pub constraint_energy: f64,
+    pub total_energy: f64,
+    pub linear_momentum: Vec2,
+    pub angular_momentum: f64,
+}
+
+/// Rolling record of [`Energy`] over time, used to spot energy drift and instabilities.
+pub struct EnergyHistory {
+    pub samples: VecDeque<EnergySample>,
+    /// Oldest samples are dropped once this many are stored.
+    pub capacity: usize,
+    /// Simulated seconds between samples, zero records every physics update.
+    pub interval: f32,
+    pub recording: bool,
+}
+
+impl Default for EnergyHistory {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            capacity: 10000,
+            interval: 0.01,
+            recording: true,
+        }
     }
 }
 
+impl EnergyHistory {
+    /// Stores the current totals if recording and at least `interval` has passed since the last sample.
+    pub fn record(&mut self, energy: &Energy, time: f32) {
+        if !self.recording {
+            return;
+        }
+        if let Some(last) = self.samples.back()
+            && (0.0..self.interval).contains(&(time - last.time))
+        {
+            return;
+        }
+        self.samples.push_back(EnergySample {
+            time,
+            kinetic_energy: energy.kinetic_energy,
+            gravitational_energy: energy.gravitational_energy,
+            potential_energy: energy.potential_energy,
+            spring_energy: energy.spring_energy,
+            constraint_energy: energy.constraint_energy,
+            total_energy: energy.get_energy(),
+            linear_momentum: energy.linear_momentum,
+            angular_momentum: energy.angular_momentum,
+        });
+        while self.samples.len() > self.capacity.max(1) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Change of the total energy since the first sample, relative to the first sample's magnitude.
+    pub fn relative_drift(&self) -> Option<f64> {
+        let first = self.samples.front()?;
+        let last = self.samples.back()?;
+        if first.total_energy == 0.0 {
+            return None;
+        }
+        Some((last.total_energy - first.total_energy) / first.total_energy.abs())
+    }
+
+    /// Every sample as comma separated values with a header line.
+    pub fn to_csv(&self) -> String {
+        let mut contents = String::from(
+            "time,kinetic,gravitational,n_body,spring,joint,total,momentum_x,momentum_y,angular_momentum\n",
+        );
+        for sample in &self.samples {
+            contents += &format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                sample.time,
+                sample.kinetic_energy,
+                sample.gravitational_energy,
+                sample.potential_energy,
+                sample.spring_energy,
+                sample.constraint_energy,
+                sample.total_energy,
+                sample.linear_momentum.x,
+                sample.linear_momentum.y,
+                sample.angular_momentum,
+            );
+        }
+        contents
+    }
+
+    pub fn export_csv(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+}
@@ -69,7 +69,12 @@ pub enum Menu {
     Editor = 6,
     DragParams = 7,
     Advanced = 8,
+    EnergyChart = 9,
     Color = 10,
+    Telemetry = 11,
+    ForceFields = 12,
+    FluidRegions = 13,
+    ParticleFluids = 14,
 }
 
 #[repr(usize)]
@@ -81,6 +86,41 @@ pub enum BodyType {
     Spring = 2,
     WeldJoint = 3,
     PivotJoint = 4,
+    Circle = 5,
+    Capsule = 6,
+    PrismaticJoint = 7,
+    DistanceJoint = 8,
+    RopeJoint = 9,
+    SoftBody = 10,
+    Rope = 11,
+    ForceField = 12,
+    FluidRegion = 13,
+    ParticleFluid = 14,
+    Explosion = 15,
+}
+
+impl BodyType {
+    /// Body types that spawn a single rigidbody on click.
+    pub fn is_rigidbody(&self) -> bool {
+        matches!(self, BodyType::RegularPolygon | BodyType::Rectangle | BodyType::Circle | BodyType::Capsule)
+    }
+
+    /// Body types placed by clicking where two rigidbodies overlap.
+    pub fn is_overlap_joint(&self) -> bool {
+        matches!(self, BodyType::WeldJoint | BodyType::PivotJoint | BodyType::PrismaticJoint)
+    }
+
+    /// Body types placed by dragging from one rigidbody to another.
+    pub fn is_drag_link(&self) -> bool {
+        matches!(self, BodyType::Spring | BodyType::DistanceJoint | BodyType::RopeJoint | BodyType::Rope)
+    }
+}
+
+/// Joint picked in the edit mode, holding its index in the matching joint list.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SelectedJoint {
+    Weld(usize),
+    Pivot(usize),
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -0,0 +1,88 @@
+use std::f32::consts::PI;
+use glam::Vec2;
+use crate::collision_detection::{raycast, RayHit};
+use crate::force_field::Falloff;
+use crate::Rigidbody;
+
+/// Burst of impulse pushing bodies away from `center`, carried by rays cast evenly in every direction.
+/// Bodies are pushed where the rays hit them, so near and large bodies catch more of the blast and
+/// off center hits spin them. Bodies containing the center are not pushed.
+#[derive(Clone, Debug)]
+pub struct Explosion {
+    pub center: Vec2,
+    /// Rays stop at this distance.
+    pub radius: f32,
+    /// Impulse of the whole blast, shared out evenly over the rays.
+    pub impulse: f32,
+    /// How the impulse of a ray fades with the distance it travelled, measured against `radius`.
+    pub falloff: Falloff,
+    /// Rays stop at the first body they hit, so bodies shield the ones behind them.
+    pub occlusion: bool,
+    /// More rays hit small and far bodies more reliably.
+    pub rays: u32,
+}
+
+impl Explosion {
+    pub fn new(center: Vec2, radius: f32, impulse: f32, falloff: Falloff) -> Self {
+        Self {
+            center,
+            radius: radius.max(0.0),
+            impulse,
+            falloff,
+            occlusion: true,
+            rays: 360,
+        }
+    }
+
+    pub fn set_occlusion(&mut self, occlusion: bool) -> &mut Self {
+        self.occlusion = occlusion;
+        self
+    }
+
+    pub fn set_rays(&mut self, rays: u32) -> &mut Self {
+        self.rays = rays.max(1);
+        self
+    }
+
+    /// Every point a ray pushes a body at, as the body index, the hit and the ray direction.
+    /// Static bodies block rays when occlusion is on but are never pushed.
+    pub fn hits(&self, rigidbodys: &[Rigidbody]) -> Vec<(usize, RayHit, Vec2)> {
+        let mut hits = vec![];
+        let rays = self.rays.max(1);
+        for ray in 0..rays {
+            let direction = Vec2::from_angle(ray as f32 * 2.0 * PI / rays as f32);
+            let mut ray_hits: Vec<(usize, RayHit)> = rigidbodys
+                .iter()
+                .enumerate()
+                .filter(|(_, body)| body.collision)
+                .filter_map(|(i, body)| Some((i, raycast(body, self.center, direction, self.radius)?)))
+                .collect();
+            if self.occlusion {
+                ray_hits.sort_by(|a, b| a.1.distance.total_cmp(&b.1.distance));
+                ray_hits.truncate(1);
+            }
+            for (i, hit) in ray_hits {
+                if !rigidbodys[i].is_static {
+                    hits.push((i, hit, direction));
+                }
+            }
+        }
+        hits
+    }
+
+    /// Pushes the bodies hit by the blast and returns their indices, each listed once.
+    pub fn apply(&self, rigidbodys: &mut [Rigidbody]) -> Vec<usize> {
+        let ray_impulse = self.impulse / self.rays.max(1) as f32;
+        let mut pushed = vec![];
+        for (i, hit, direction) in self.hits(rigidbodys) {
+            let body = &mut rigidbodys[i];
+            let impulse = direction * ray_impulse * self.falloff.scale(hit.distance, self.radius);
+            body.velocity += impulse / body.mass;
+            body.angular_velocity += (hit.point - body.center).perp_dot(impulse) / body.moment_of_inertia;
+            if !pushed.contains(&i) {
+                pushed.push(i);
+            }
+        }
+        pushed
+    }
+}
@@ -0,0 +1,175 @@
+use std::f32::consts::PI;
+use glam::Vec2;
+use crate::collision_detection::Collider;
+use crate::rigidbody::Shape;
+use crate::triangulation::signed_area;
+
+/// Convex piece of a compound rigidbody with its own material.
+/// Like `Rigidbody::vertices`, `vertices` and `center` are world space caches of the local shape.
+#[derive(Clone, Debug)]
+pub struct Fixture {
+    pub shape: Shape,
+    pub vertices: Vec<Vec2>,
+    pub center: Vec2,
+    pub local_vertices: Vec<Vec2>,
+    pub local_center: Vec2,
+    pub density: f32,
+    pub restitution: f32,
+    pub friction: f32,
+}
+
+impl Fixture {
+    /// Convex polygon, `points` are relative to `offset` which is relative to the body origin.
+    pub fn polygon(points: Vec<Vec2>, offset: Vec2, density: f32, restitution: f32, friction: f32) -> Self {
+        let mut vertices: Vec<Vec2> = points.iter().map(|&point| point + offset).collect();
+        if signed_area(&vertices) < 0.0 {
+            vertices.reverse();
+        }
+        let (_, center, _) = shape_properties(Shape::Polygon, &vertices, offset);
+        Fixture {
+            shape: Shape::Polygon,
+            local_vertices: vertices.clone(),
+            local_center: center,
+            vertices,
+            center,
+            density,
+            restitution,
+            friction,
+        }
+    }
+
+    pub fn rectangle(width: f32, height: f32, offset: Vec2, density: f32, restitution: f32, friction: f32) -> Self {
+        let points = vec![
+            Vec2::new(-width / 2.0, -height / 2.0),
+            Vec2::new(width / 2.0, -height / 2.0),
+            Vec2::new(width / 2.0, height / 2.0),
+            Vec2::new(-width / 2.0, height / 2.0),
+        ];
+        Self::polygon(points, offset, density, restitution, friction)
+    }
+
+    pub fn circle(radius: f32, offset: Vec2, density: f32, restitution: f32, friction: f32) -> Self {
+        Fixture {
+            shape: Shape::Circle { radius },
+            vertices: vec![],
+            center: offset,
+            local_vertices: vec![],
+            local_center: offset,
+            density,
+            restitution,
+            friction,
+        }
+    }
+
+    /// Horizontal capsule of spine `length` centered on `offset`.
+    pub fn capsule(length: f32, radius: f32, offset: Vec2, density: f32, restitution: f32, friction: f32) -> Self {
+        let vertices = vec![
+            offset - Vec2::new(length / 2.0, 0.0),
+            offset + Vec2::new(length / 2.0, 0.0),
+        ];
+        Fixture {
+            shape: Shape::Capsule { radius },
+            local_vertices: vertices.clone(),
+            local_center: offset,
+            vertices,
+            center: offset,
+            density,
+            restitution,
+            friction,
+        }
+    }
+
+    pub fn area(&self) -> f32 {
+        shape_properties(self.shape, &self.vertices, self.center).0
+    }
+
+    pub fn mass(&self) -> f32 {
+        self.density * self.area()
+    }
+
+    /// Moment of inertia about the fixture's own centroid.
+    pub fn moment_of_inertia(&self) -> f32 {
+        let (_, _, second_moment) = shape_properties(self.shape, &self.vertices, self.center);
+        second_moment * self.density
+    }
+
+    pub fn collider(&self) -> Collider<'_> {
+        Collider {
+            shape: self.shape,
+            vertices: &self.vertices,
+            center: self.center,
+            restitution: self.restitution,
+            friction: self.friction,
+        }
+    }
+}
+
+/// Area, centroid and second moment of area about the centroid.
+/// `center` is only used by circles, which have no vertices.
+pub(crate) fn shape_properties(shape: Shape, vertices: &[Vec2], center: Vec2) -> (f32, Vec2, f32) {
+    match shape {
+        Shape::Circle { radius } => {
+            let area = PI * radius * radius;
+            (area, center, 0.5 * area * radius * radius)
+        }
+        Shape::Capsule { radius } => {
+            let length = vertices[0].distance(vertices[1]);
+            let box_area = length * 2.0 * radius;
+            let cap_area = PI * radius * radius;
+            let box_inertia = box_area * (length * length + 4.0 * radius * radius) / 12.0;
+            // Both half discs about their own centroid, then shifted out to the ends of the spine
+            let cap_offset = length / 2.0 + 4.0 * radius / (3.0 * PI);
+            let cap_centroid_inertia = cap_area * radius * radius * (0.5 - 16.0 / (9.0 * PI * PI));
+            let cap_inertia = cap_centroid_inertia + cap_area * cap_offset * cap_offset;
+            let centroid = (vertices[0] + vertices[1]) * 0.5;
+            (box_area + cap_area, centroid, box_inertia + cap_inertia)
+        }
+        Shape::Polygon => {
+            let n = vertices.len();
+            let mut area = 0.0;
+            let mut centroid = Vec2::ZERO;
+            let mut inertia = 0.0;
+            for i in 0..n {
+                let p0 = vertices[i];
+                let p1 = vertices[(i + 1) % n];
+                let cross = p0.perp_dot(p1);
+                area += cross;
+                centroid += (p0 + p1) * cross;
+                inertia += cross * (p0.dot(p0) + p0.dot(p1) + p1.dot(p1));
+            }
+            area *= 0.5;
+            centroid /= 6.0 * area;
+            (area, centroid, inertia / 12.0 - area * centroid.length_squared())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_square_properties() {
+        let square = [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 1.0)];
+        let (area, centroid, second_moment) = shape_properties(Shape::Polygon, &square, Vec2::ZERO);
+        assert!((area - 1.0).abs() < 1e-6);
+        assert!(centroid.abs_diff_eq(Vec2::splat(0.5), 1e-6));
+        assert!((second_moment - 1.0 / 6.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn circle_properties() {
+        let (area, centroid, second_moment) = shape_properties(Shape::Circle { radius: 2.0 }, &[], Vec2::new(1.0, 2.0));
+        assert!((area - 4.0 * PI).abs() < 1e-5);
+        assert_eq!(centroid, Vec2::new(1.0, 2.0));
+        assert!((second_moment - 8.0 * PI).abs() < 1e-4);
+    }
+
+    #[test]
+    fn capsule_without_spine_is_a_circle() {
+        let spine = [Vec2::ZERO, Vec2::ZERO];
+        let (area, _, second_moment) = shape_properties(Shape::Capsule { radius: 1.0 }, &spine, Vec2::ZERO);
+        assert!((area - PI).abs() < 1e-5);
+        assert!((second_moment - 0.5 * PI).abs() < 1e-5);
+    }
+}
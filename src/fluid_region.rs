@@ -0,0 +1,142 @@
+use std::fs;
+use std::io;
+use glam::Vec2;
+use crate::rigidbody::outline;
+use crate::triangulation::{clip_polygon, polygon_centroid, signed_area, triangulate};
+use crate::{ColorRGBA, Rigidbody};
+
+/// File fluid regions are saved to from the GUI, relative to the working directory.
+pub const FLUID_REGIONS_PATH: &str = "fluid_regions.txt";
+
+/// Body of still liquid, bodies inside are buoyed up by the weight of the liquid they displace and slowed by drag.
+#[derive(Clone, Debug)]
+pub struct FluidRegion {
+    /// World space outline in counter clockwise order, concave outlines are allowed.
+    pub vertices: Vec<Vec2>,
+    /// Relative to water like body densities, bodies less dense than the fluid float.
+    pub density: f32,
+    /// Drag on the velocity of the submerged part, per unit of submerged area.
+    pub linear_drag: f32,
+    /// Drag on the spin of the submerged part, per unit of submerged area.
+    pub angular_drag: f32,
+    pub color: ColorRGBA,
+}
+
+impl FluidRegion {
+    pub fn new(vertices: Vec<Vec2>, density: f32) -> Self {
+        let mut vertices = vertices;
+        if signed_area(&vertices) < 0.0 {
+            vertices.reverse();
+        }
+        Self {
+            vertices,
+            density,
+            linear_drag: 2.0,
+            angular_drag: 1.0,
+            color: ColorRGBA::new(0.1, 0.4, 0.9, 0.35),
+        }
+    }
+
+    /// Axis aligned box of water from `min` to `max`.
+    pub fn rectangle(min: Vec2, max: Vec2, density: f32) -> Self {
+        Self::new(vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)], density)
+    }
+
+    pub fn set_drag(&mut self, linear_drag: f32, angular_drag: f32) -> &mut Self {
+        self.linear_drag = linear_drag.max(0.0);
+        self.angular_drag = angular_drag.max(0.0);
+        self
+    }
+
+    /// Area and centroid of the part of `rigidbody` below the surface, summed over its convex pieces.
+    pub fn submerged_area(&self, rigidbody: &Rigidbody) -> (f32, Vec2) {
+        let mut area = 0.0;
+        let mut weighted_centroid = Vec2::ZERO;
+        for collider in rigidbody.colliders() {
+            let piece = outline(collider.shape, collider.vertices, collider.center, rigidbody.angle);
+            if piece.len() < 3 {
+                continue;
+            }
+            let submerged = clip_polygon(&self.vertices, &piece);
+            if submerged.len() < 3 {
+                continue;
+            }
+            let piece_area = signed_area(&submerged).abs();
+            area += piece_area;
+            weighted_centroid += polygon_centroid(&submerged) * piece_area;
+        }
+        if area <= f32::EPSILON {
+            return (0.0, rigidbody.center);
+        }
+        (area, weighted_centroid / area)
+    }
+
+    /// Applies buoyancy and drag for one step to every body in the fluid, `gravity` is the world gravity.
+    pub fn apply_buoyancy(&self, rigidbodys: &mut [Rigidbody], gravity: Vec2, dt: f32) {
+        if self.vertices.len() < 3 {
+            return;
+        }
+        for body in rigidbodys.iter_mut() {
+            if body.is_static || body.gravity_multiplier == 0.0 {
+                continue;
+            }
+            let (area, centroid) = self.submerged_area(body);
+            if area == 0.0 {
+                continue;
+            }
+            let arm = centroid - body.center;
+
+            // Weight of the displaced fluid, pushing against gravity through the submerged centroid
+            let buoyancy = -gravity * body.gravity_multiplier * self.density * area;
+            body.velocity += buoyancy / body.mass * dt;
+            body.angular_velocity += arm.perp_dot(buoyancy) / body.moment_of_inertia * dt;
+
+            // Drag is capped so it can stop the submerged part but never reverse it
+            let point_velocity = body.velocity + arm.perp() * body.angular_velocity;
+            let linear = (self.linear_drag * self.density * area * dt / body.mass).min(1.0);
+            let impulse = -point_velocity * linear * body.mass;
+            body.velocity += impulse / body.mass;
+            body.angular_velocity += arm.perp_dot(impulse) / body.moment_of_inertia;
+            let angular = (self.angular_drag * self.density * area * dt / body.mass).min(1.0);
+            body.angular_velocity *= 1.0 - angular;
+        }
+    }
+
+    /// Triangles of the outline for drawing.
+    pub fn triangulate(&self) -> Vec<u32> {
+        triangulate(&self.vertices)
+    }
+
+    /// Reads regions written by [`FluidRegion::save_regions`], one
+    /// `density,linear_drag,angular_drag,x1,y1,x2,y2,...` per line. Lines that fail to parse are skipped.
+    pub fn load_regions(path: &str) -> Vec<FluidRegion> {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return vec![];
+        };
+        contents
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<f32> = line.split(',').map(|field| field.trim().parse()).collect::<Result<_, _>>().ok()?;
+                if fields.len() < 9 || fields.len().is_multiple_of(2) {
+                    return None;
+                }
+                let vertices = fields[3..].chunks_exact(2).map(|point| Vec2::new(point[0], point[1])).collect();
+                let mut region = FluidRegion::new(vertices, fields[0]);
+                region.set_drag(fields[1], fields[2]);
+                Some(region)
+            })
+            .collect()
+    }
+
+    pub fn save_regions(path: &str, regions: &[FluidRegion]) -> io::Result<()> {
+        let mut contents = String::new();
+        for region in regions {
+            contents += &format!("{},{},{}", region.density, region.linear_drag, region.angular_drag);
+            for vertex in &region.vertices {
+                contents += &format!(",{},{}", vertex.x, vertex.y);
+            }
+            contents += "\n";
+        }
+        fs::write(path, contents)
+    }
+}
@@ -0,0 +1,226 @@
+use glam::Vec2;
+use crate::body_builder::BodyBuilder;
+use crate::distance_joint::create_link;
+use crate::triangulation::{signed_area, triangulate};
+use crate::{ColorRGBA, Rigidbody};
+
+/// Shape of the push a [`ForceField`] gives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FieldKind {
+    /// Same push along `direction` everywhere in the region.
+    Wind { direction: Vec2 },
+    /// Pull towards `center`, a negative strength pushes away from it.
+    Radial { center: Vec2 },
+    /// Swirl counter clockwise around `center`, a negative strength turns clockwise.
+    Vortex { center: Vec2 },
+}
+
+/// How a strength fades with the distance from a center, measured against a radius. Every falloff is at full
+/// strength at the center, used by radial and vortex fields and by explosions.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Falloff {
+    /// Full strength at any distance.
+    #[default]
+    None,
+    /// Fades to zero at `radius`.
+    Linear,
+    /// Full strength up to a tenth of `radius`, then fading with the inverse square of the distance down to a
+    /// hundredth at `radius`.
+    InverseSquare,
+}
+
+impl Falloff {
+    /// Fraction of the full strength left at `distance` from the center.
+    pub fn scale(&self, distance: f32, radius: f32) -> f32 {
+        match self {
+            Falloff::None => 1.0,
+            Falloff::Linear => (1.0 - distance / radius).max(0.0),
+            Falloff::InverseSquare => {
+                let full_strength = radius * 0.1;
+                (full_strength / distance.max(full_strength)).powi(2)
+            }
+        }
+    }
+}
+
+/// Part of the world a [`ForceField`] acts in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FieldRegion {
+    #[default]
+    Everywhere,
+    Aabb { min: Vec2, max: Vec2 },
+    /// Simple polygon in world space, concave outlines are allowed.
+    Polygon(Vec<Vec2>),
+}
+
+impl FieldRegion {
+    pub fn contains(&self, point: Vec2) -> bool {
+        match self {
+            FieldRegion::Everywhere => true,
+            FieldRegion::Aabb { min, max } => point.cmpge(*min).all() && point.cmple(*max).all(),
+            FieldRegion::Polygon(vertices) => {
+                // Even-odd rule, counts the edges crossed by a ray going right from the point
+                let mut inside = false;
+                for i in 0..vertices.len() {
+                    let a = vertices[i];
+                    let b = vertices[(i + 1) % vertices.len()];
+                    if (a.y > point.y) != (b.y > point.y) {
+                        let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                        if point.x < x {
+                            inside = !inside;
+                        }
+                    }
+                }
+                inside
+            }
+        }
+    }
+}
+
+/// Source of acceleration acting on every non static body inside its region, independently of the body's mass.
+/// Like gravity it is scaled by the body's `gravity_multiplier`, so bodies pinned in place stay put.
+#[derive(Clone, Debug)]
+pub struct ForceField {
+    pub kind: FieldKind,
+    /// Acceleration in m/s^2 at full strength.
+    pub strength: f32,
+    pub falloff: Falloff,
+    /// Distance the falloff is measured against.
+    pub radius: f32,
+    pub region: FieldRegion,
+    pub enabled: bool,
+    pub color: ColorRGBA,
+}
+
+impl ForceField {
+    fn new(kind: FieldKind, strength: f32) -> Self {
+        Self {
+            kind,
+            strength,
+            falloff: Falloff::None,
+            radius: 1.0,
+            region: FieldRegion::Everywhere,
+            enabled: true,
+            color: ColorRGBA::new(0.3, 0.7, 1.0, 0.15),
+        }
+    }
+
+    /// Uniform wind blowing along `direction`.
+    pub fn wind(direction: Vec2, strength: f32) -> Self {
+        Self::new(FieldKind::Wind { direction: direction.normalize_or(Vec2::X) }, strength)
+    }
+
+    /// Attractor at `center`, or a repulsor when `strength` is negative.
+    pub fn radial(center: Vec2, strength: f32) -> Self {
+        Self::new(FieldKind::Radial { center }, strength)
+    }
+
+    pub fn vortex(center: Vec2, strength: f32) -> Self {
+        Self::new(FieldKind::Vortex { center }, strength)
+    }
+
+    pub fn set_falloff(&mut self, falloff: Falloff, radius: f32) -> &mut Self {
+        self.falloff = falloff;
+        self.radius = radius.max(0.0001);
+        self
+    }
+
+    pub fn set_region(&mut self, region: FieldRegion) -> &mut Self {
+        self.region = region;
+        self
+    }
+
+    /// Center of radial and vortex fields, wind fields have none.
+    pub fn center(&self) -> Option<Vec2> {
+        match self.kind {
+            FieldKind::Wind { .. } => None,
+            FieldKind::Radial { center } | FieldKind::Vortex { center } => Some(center),
+        }
+    }
+
+    /// Moves the center of radial and vortex fields, or the region of any field, so it sits on `position`.
+    pub fn move_to(&mut self, position: Vec2) {
+        let offset = match self.kind {
+            FieldKind::Wind { .. } => match &self.region {
+                FieldRegion::Everywhere => return,
+                FieldRegion::Aabb { min, max } => position - (*min + *max) * 0.5,
+                FieldRegion::Polygon(vertices) => {
+                    position - vertices.iter().copied().sum::<Vec2>() / vertices.len().max(1) as f32
+                }
+            },
+            FieldKind::Radial { center } | FieldKind::Vortex { center } => position - center,
+        };
+        match &mut self.kind {
+            FieldKind::Wind { .. } => {}
+            FieldKind::Radial { center } | FieldKind::Vortex { center } => *center += offset,
+        }
+        match &mut self.region {
+            FieldRegion::Everywhere => {}
+            FieldRegion::Aabb { min, max } => {
+                *min += offset;
+                *max += offset;
+            }
+            FieldRegion::Polygon(vertices) => {
+                for vertex in vertices {
+                    *vertex += offset;
+                }
+            }
+        }
+    }
+
+    /// Triangles covering the region, `None` for fields acting everywhere.
+    pub fn region_mesh(&self) -> Option<(Vec<Vec2>, Vec<u32>)> {
+        match &self.region {
+            FieldRegion::Everywhere => None,
+            FieldRegion::Aabb { min, max } => Some((
+                vec![*min, Vec2::new(max.x, min.y), *max, Vec2::new(min.x, max.y)],
+                vec![0, 1, 2, 0, 2, 3],
+            )),
+            FieldRegion::Polygon(vertices) if vertices.len() < 3 => None,
+            FieldRegion::Polygon(vertices) => {
+                let mut vertices = vertices.clone();
+                if signed_area(&vertices) < 0.0 {
+                    vertices.reverse();
+                }
+                let indices = triangulate(&vertices);
+                Some((vertices, indices))
+            }
+        }
+    }
+
+    /// Dot on the center of radial and vortex fields, or a line along the wind from the middle of a bounded region.
+    pub fn create_marker(&self) -> Option<Rigidbody> {
+        match self.kind {
+            FieldKind::Wind { direction } => {
+                let (vertices, _) = self.region_mesh()?;
+                let middle = vertices.iter().copied().sum::<Vec2>() / vertices.len().max(1) as f32;
+                Some(create_link(middle, middle + direction * 0.5, ColorRGBA::new(1.0, 1.0, 1.0, 0.5)))
+            }
+            FieldKind::Radial { center } | FieldKind::Vortex { center } => {
+                let mut marker = BodyBuilder::create_joint();
+                marker.move_to(center);
+                Some(marker)
+            }
+        }
+    }
+
+    /// Acceleration the field gives a body whose center is at `position`.
+    pub fn acceleration(&self, position: Vec2) -> Vec2 {
+        if !self.enabled || !self.region.contains(position) {
+            return Vec2::ZERO;
+        }
+        match self.kind {
+            FieldKind::Wind { direction } => direction * self.strength,
+            FieldKind::Radial { center } => {
+                let offset = center - position;
+                let distance = offset.length();
+                offset.normalize_or_zero() * self.strength * self.falloff.scale(distance, self.radius)
+            }
+            FieldKind::Vortex { center } => {
+                let offset = position - center;
+                let distance = offset.length();
+                offset.perp().normalize_or_zero() * self.strength * self.falloff.scale(distance, self.radius)
+            }
+        }
+    }
+}
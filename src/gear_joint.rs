@@ -0,0 +1,138 @@
+use glam::Vec2;
+use crate::distance_joint::create_link;
+use crate::{ColorRGBA, PivotJoint, PrismaticJoint, Rigidbody};
+
+/// Joint whose coordinate a [`GearJoint`] drives: a pivot's angle or a prismatic joint's translation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GearTarget {
+    /// Index into the pivot joints.
+    Pivot(usize),
+    /// Index into the prismatic joints.
+    Prismatic(usize),
+}
+
+/// Couples two joints so `coordinate_a + ratio * coordinate_b` stays constant, e.g. meshing gears or a rack and pinion.
+#[derive(Clone)]
+pub struct GearJoint {
+    pub joint_a: GearTarget,
+    pub joint_b: GearTarget,
+    pub ratio: f32,
+    /// Value of `coordinate_a + ratio * coordinate_b` when the joint was created.
+    pub constant: f32,
+
+    /// Baumgarte stabilization factor for positional drift (small: 0.01..0.2)
+    pub beta: f32,
+}
+
+/// One body's row of the constraint jacobian, velocity coefficients followed by the angular coefficient.
+struct JacobianEntry {
+    body: usize,
+    linear: Vec2,
+    angular: f32,
+}
+
+impl GearJoint {
+    pub fn new(
+        joint_a: GearTarget,
+        joint_b: GearTarget,
+        ratio: f32,
+        pivot_joints: &[PivotJoint],
+        prismatic_joints: &[PrismaticJoint],
+        rigidbodys: &[Rigidbody],
+    ) -> Self {
+        let mut gear_joint = Self {
+            joint_a,
+            joint_b,
+            ratio,
+            constant: 0.0,
+            beta: 0.1,
+        };
+        gear_joint.constant = gear_joint.coordinate(pivot_joints, prismatic_joints, rigidbodys);
+        gear_joint
+    }
+
+    fn joint_coordinate(target: GearTarget, pivot_joints: &[PivotJoint], prismatic_joints: &[PrismaticJoint], rigidbodys: &[Rigidbody]) -> f32 {
+        match target {
+            GearTarget::Pivot(index) => pivot_joints[index].joint_angle(rigidbodys),
+            GearTarget::Prismatic(index) => prismatic_joints[index].joint_translation(rigidbodys),
+        }
+    }
+
+    /// Current `coordinate_a + ratio * coordinate_b`.
+    pub fn coordinate(&self, pivot_joints: &[PivotJoint], prismatic_joints: &[PrismaticJoint], rigidbodys: &[Rigidbody]) -> f32 {
+        Self::joint_coordinate(self.joint_a, pivot_joints, prismatic_joints, rigidbodys)
+            + self.ratio * Self::joint_coordinate(self.joint_b, pivot_joints, prismatic_joints, rigidbodys)
+    }
+
+    /// Adds `scale` times the jacobian of `target`'s coordinate, merging rows of bodies already present.
+    fn add_jacobian(
+        entries: &mut Vec<JacobianEntry>,
+        target: GearTarget,
+        scale: f32,
+        pivot_joints: &[PivotJoint],
+        prismatic_joints: &[PrismaticJoint],
+        rigidbodys: &[Rigidbody],
+    ) {
+        let rows = match target {
+            GearTarget::Pivot(index) => {
+                let joint = &pivot_joints[index];
+                [(joint.body_a, Vec2::ZERO, -1.0), (joint.body_b, Vec2::ZERO, 1.0)]
+            }
+            GearTarget::Prismatic(index) => {
+                let joint = &prismatic_joints[index];
+                let (axis, arm_a, arm_b) = joint.axial_jacobian(rigidbodys);
+                [(joint.body_a, -axis, -arm_a), (joint.body_b, axis, arm_b)]
+            }
+        };
+        for (body, linear, angular) in rows {
+            match entries.iter_mut().find(|entry| entry.body == body) {
+                Some(entry) => {
+                    entry.linear += linear * scale;
+                    entry.angular += angular * scale;
+                }
+                None => entries.push(JacobianEntry { body, linear: linear * scale, angular: angular * scale }),
+            }
+        }
+    }
+
+    pub fn solve_velocity_constraints(&self, pivot_joints: &[PivotJoint], prismatic_joints: &[PrismaticJoint], rigidbodys: &mut [Rigidbody], dt: f32) {
+        let mut entries = vec![];
+        Self::add_jacobian(&mut entries, self.joint_a, 1.0, pivot_joints, prismatic_joints, rigidbodys);
+        Self::add_jacobian(&mut entries, self.joint_b, self.ratio, pivot_joints, prismatic_joints, rigidbodys);
+
+        let mut k = 0.0;
+        let mut c_dot = 0.0;
+        for entry in &entries {
+            let body = &rigidbodys[entry.body];
+            k += entry.linear.length_squared() / body.mass + entry.angular * entry.angular / body.moment_of_inertia;
+            c_dot += entry.linear.dot(body.velocity) + entry.angular * body.angular_velocity;
+        }
+        if k <= 0.0 {
+            return;
+        }
+
+        let c = self.coordinate(pivot_joints, prismatic_joints, rigidbodys) - self.constant;
+        let impulse = -(c_dot + (self.beta / dt) * c) / k;
+        for entry in &entries {
+            let body = &mut rigidbodys[entry.body];
+            body.velocity += entry.linear * impulse / body.mass;
+            body.angular_velocity += entry.angular * impulse / body.moment_of_inertia;
+        }
+    }
+
+    fn anchor(target: GearTarget, pivot_joints: &[PivotJoint], prismatic_joints: &[PrismaticJoint], rigidbodys: &[Rigidbody]) -> Vec2 {
+        match target {
+            GearTarget::Pivot(index) => pivot_joints[index].get_anchor_world_position(rigidbodys),
+            GearTarget::Prismatic(index) => prismatic_joints[index].get_anchor_world_position(rigidbodys),
+        }
+    }
+
+    /// Line between the anchors of the two coupled joints.
+    pub fn create_link(&self, pivot_joints: &[PivotJoint], prismatic_joints: &[PrismaticJoint], rigidbodys: &[Rigidbody]) -> Rigidbody {
+        create_link(
+            Self::anchor(self.joint_a, pivot_joints, prismatic_joints, rigidbodys),
+            Self::anchor(self.joint_b, pivot_joints, prismatic_joints, rigidbodys),
+            ColorRGBA::new(0.6, 0.8, 1.0, 0.6),
+        )
+    }
+}
@@ -1,10 +1,15 @@
-use crate::enums::{BodyType, ColorType, InputMode, Menu};
-use crate::{Camera, ColorRGBA, Parameters};
+use crate::enums::{BodyType, ColorType, InputMode, Menu, SelectedJoint};
+use crate::{dominant_attractor, AnchorSnap, Camera, ChainLink, ColorRGBA, Falloff, FieldKind, FieldRegion, FluidRegion, ForceField, GearTarget, OrbitalElements, Parameters, ParticleFluid, PivotJoint, SpringLaw, Telemetry, WeldJoint};
 use egui::{Align2};
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+use glam::Vec2;
 use egui_wgpu::{ScreenDescriptor, wgpu};
 use std::f32::consts::PI;
-use crate::body_builder::BodyBuilder;
+use crate::body_builder::{BodyBuilder, DragParams};
 use crate::color::ColorSystem;
+use crate::energy::{EnergyHistory, EnergySample, ENERGY_HISTORY_PATH};
+use crate::telemetry::TELEMETRY_PATH;
+use crate::fluid_region::FLUID_REGIONS_PATH;
 use crate::input::UiSystem;
 use crate::physics::PhysicsSystem;
 use crate::render::RenderSystem;
@@ -31,7 +36,12 @@ impl RenderSystem {
                 ui.checkbox(&mut ui_system.menus[Menu::Camera as usize], "Camera Position");
                 ui.checkbox(&mut ui_system.menus[Menu::Spawner as usize], "Spawned Body Properties", );
                 ui.checkbox(&mut ui_system.menus[Menu::Editor as usize], "Edit Selected Polygon", );
-                ui.checkbox(&mut ui_system.menus[Menu::Energy as usize], "Kinetic Energy Info", );
+                ui.checkbox(&mut ui_system.menus[Menu::Energy as usize], "Energy Info", );
+                ui.checkbox(&mut ui_system.menus[Menu::EnergyChart as usize], "Energy Chart", );
+                ui.checkbox(&mut ui_system.menus[Menu::Telemetry as usize], "Telemetry", );
+                ui.checkbox(&mut ui_system.menus[Menu::ForceFields as usize], "Force Fields", );
+                ui.checkbox(&mut ui_system.menus[Menu::FluidRegions as usize], "Fluid Regions", );
+                ui.checkbox(&mut ui_system.menus[Menu::ParticleFluids as usize], "Particle Fluids", );
                 ui.checkbox(&mut ui_system.menus[Menu::FPS as usize], "Show FPS");
                 ui.checkbox(&mut ui_system.menus[Menu::Color as usize], "Color Menu");
                 ui.checkbox(&mut ui_system.menus[Menu::Advanced as usize], "Advanced Settings");
@@ -55,11 +65,29 @@ impl RenderSystem {
         if ui_system.menus[Menu::Input as usize] {
             self.input_menu(ui_system)
         }
+        if ui_system.menus[Menu::DragParams as usize] && ui_system.input_mode == InputMode::Drag {
+            self.drag_params_menu(&mut ui_system.drag_params)
+        }
         if ui_system.menus[Menu::Editor as usize] {
-            self.editor_menu(physics_system, ui_system)
+            self.editor_menu(physics_system, ui_system, parameters)
         }
         if ui_system.menus[Menu::Advanced as usize] {
-            self.advanced_menu(parameters)
+            self.advanced_menu(parameters, physics_system)
+        }
+        if ui_system.menus[Menu::EnergyChart as usize] {
+            self.energy_chart_menu(&mut physics_system.energy_history)
+        }
+        if ui_system.menus[Menu::Telemetry as usize] {
+            self.telemetry_menu(&mut physics_system.telemetry)
+        }
+        if ui_system.menus[Menu::ForceFields as usize] {
+            self.force_field_menu(&mut physics_system.force_fields)
+        }
+        if ui_system.menus[Menu::FluidRegions as usize] {
+            self.fluid_region_menu(&mut physics_system.fluid_regions)
+        }
+        if ui_system.menus[Menu::ParticleFluids as usize] {
+            self.particle_fluid_menu(&mut physics_system.particle_fluids)
         }
         if ui_system.menus[Menu::Color as usize] {
             self.color_menu(color_system)
@@ -114,19 +142,309 @@ impl RenderSystem {
             .resizable(false)
             .vscroll(true)
             .default_open(true)
-            .max_height(100.0)
+            .max_height(250.0)
             .max_width(300.0)
             .title_bar(false)
             .show(self.egui_renderer.context(), |ui| {
+                let energy = &mut physics_system.energy;
                 ui.heading("Energy");
-                ui.label(format!("Spring Energy: {:.3} Joules", physics_system.energy.spring_energy));
-                ui.label(format!("Kinetic Energy: {:.3} Joules", physics_system.energy.kinetic_energy));
-                ui.label(format!("Potential Energy: {:.3} Joules", physics_system.energy.potential_energy));
-                ui.label(format!("Total Energy: {:.3} Joules", physics_system.energy.get_energy()));
+                ui.label(format!("Spring Energy: {:.3} Joules", energy.spring_energy));
+                ui.label(format!("Kinetic Energy: {:.3} Joules", energy.kinetic_energy));
+                ui.label(format!("Gravitational Energy: {:.3} Joules", energy.gravitational_energy));
+                ui.label(format!("Pairwise Potential Energy: {:.3} Joules", energy.potential_energy));
+                ui.label(format!("Joint Energy: {:.3} Joules", energy.constraint_energy));
+                ui.label(format!("Total Energy: {:.3} Joules", energy.get_energy()));
+                ui.columns(2, |ui| {
+                    ui[0].label("Datum Height");
+                    ui[1].add(egui::DragValue::new(&mut energy.datum).speed(0.1));
+                });
+
+                ui.heading("Momentum");
+                ui.label(format!("Linear Momentum: ({:.3}, {:.3}) kg m/s", energy.linear_momentum.x, energy.linear_momentum.y));
+                ui.label(format!("Angular Momentum: {:.3} kg m^2/s", energy.angular_momentum));
 
 
             });
     }
+    fn energy_chart_menu(&mut self, history: &mut EnergyHistory) {
+        egui::Window::new("Energy Chart")
+            .resizable(true)
+            .vscroll(true)
+            .default_open(true)
+            .default_width(400.0)
+            .title_bar(false)
+            .show(self.egui_renderer.context(), |ui| {
+                ui.heading("Energy Chart");
+                ui.columns(2, |ui| {
+                    ui[0].checkbox(&mut history.recording, "Recording");
+                    if ui[1].button("Clear").clicked() {
+                        history.samples.clear();
+                    }
+                });
+                ui.columns(2, |ui| {
+                    ui[0].label("Sample Interval");
+                    ui[1].add(egui::DragValue::new(&mut history.interval).speed(0.001).range(0.0..=f32::MAX));
+                });
+                ui.columns(2, |ui| {
+                    ui[0].label("Max Samples");
+                    ui[1].add(egui::DragValue::new(&mut history.capacity).speed(10).range(1..=usize::MAX));
+                });
+                match history.relative_drift() {
+                    Some(drift) => ui.label(format!("Total Energy Drift: {:.4}%", drift * 100.0)),
+                    None => ui.label("Total Energy Drift: -"),
+                };
+
+                let series = |value: fn(&EnergySample) -> f64| -> Vec<[f64; 2]> {
+                    history.samples.iter().map(|sample| [sample.time as f64, value(sample)]).collect()
+                };
+                // Kinetic, potential, spring and joint energy add up to the total
+                ui.label("Energy (Joules)");
+                line_chart(ui, "energy_chart", 150.0, vec![
+                    ("Kinetic", egui::Color32::LIGHT_BLUE, series(|sample| sample.kinetic_energy)),
+                    ("Potential", egui::Color32::LIGHT_GREEN, series(|sample| sample.gravitational_energy + sample.potential_energy)),
+                    ("Spring", egui::Color32::from_rgb(255, 170, 0), series(|sample| sample.spring_energy)),
+                    ("Joint", egui::Color32::from_rgb(255, 120, 200), series(|sample| sample.constraint_energy)),
+                    ("Total", egui::Color32::WHITE, series(|sample| sample.total_energy)),
+                ]);
+                ui.label("Momentum");
+                line_chart(ui, "momentum_chart", 100.0, vec![
+                    ("Linear X", egui::Color32::LIGHT_RED, series(|sample| sample.linear_momentum.x as f64)),
+                    ("Linear Y", egui::Color32::LIGHT_YELLOW, series(|sample| sample.linear_momentum.y as f64)),
+                    ("Angular", egui::Color32::from_rgb(200, 130, 255), series(|sample| sample.angular_momentum)),
+                ]);
+
+                ui.columns(2, |ui| {
+                    ui[0].label(format!("{} Samples", history.samples.len()));
+                    if ui[1].button("Export CSV").clicked()
+                        && let Err(error) = history.export_csv(ENERGY_HISTORY_PATH)
+                    {
+                        eprintln!("Failed to export the energy history: {}", error);
+                    }
+                });
+            });
+    }
+    fn telemetry_menu(&mut self, telemetry: &mut Vec<Telemetry>) {
+        let mut unpin = None;
+        egui::Window::new("Telemetry")
+            .resizable(false)
+            .vscroll(true)
+            .default_open(true)
+            .max_height(400.0)
+            .title_bar(false)
+            .show(self.egui_renderer.context(), |ui| {
+                ui.heading("Telemetry");
+                if telemetry.is_empty() {
+                    ui.label("Pin a body in the body editor to record it");
+                }
+                for (i, pinned) in telemetry.iter_mut().enumerate() {
+                    egui::CollapsingHeader::new(format!("Body {}", pinned.body)).default_open(true).show(ui, |ui| {
+                        if let Some(sample) = pinned.samples.back() {
+                            ui.label(format!("Position: ({:.3}, {:.3})", sample.position.x, sample.position.y));
+                            ui.label(format!("Velocity: ({:.3}, {:.3})", sample.velocity.x, sample.velocity.y));
+                            ui.label(format!("Angle: {:.2}°", sample.angle.to_degrees()));
+                            ui.label(format!("Angular Velocity: {:.3} rad/s", sample.angular_velocity));
+                            ui.label(format!("Contact Force: ({:.3}, {:.3}) N", sample.contact_force.x, sample.contact_force.y));
+                        }
+                        ui.columns(2, |ui| {
+                            ui[0].label("Trail Duration");
+                            ui[1].add(egui::DragValue::new(&mut pinned.trail_duration).speed(0.1).range(0.0..=f32::MAX));
+                        });
+                        ui.columns(2, |ui| {
+                            ui[0].label(format!("{} Samples", pinned.samples.len()));
+                            if ui[1].button("Unpin").clicked() {
+                                unpin = Some(i);
+                            }
+                        });
+                    });
+                }
+                ui.columns(2, |ui| {
+                    if ui[0].button("Clear").clicked() {
+                        for pinned in telemetry.iter_mut() {
+                            pinned.samples.clear();
+                        }
+                    }
+                    if ui[1].button("Export CSV").clicked()
+                        && let Err(error) = Telemetry::export_csv(TELEMETRY_PATH, telemetry)
+                    {
+                        eprintln!("Failed to export telemetry: {}", error);
+                    }
+                });
+            });
+        if let Some(i) = unpin {
+            telemetry.remove(i);
+        }
+    }
+    fn force_field_menu(&mut self, force_fields: &mut Vec<ForceField>) {
+        let mut delete = None;
+        egui::Window::new("Force Fields")
+            .resizable(false)
+            .vscroll(true)
+            .default_open(true)
+            .max_height(400.0)
+            .title_bar(false)
+            .show(self.egui_renderer.context(), |ui| {
+                ui.heading("Force Fields");
+                if force_fields.is_empty() {
+                    ui.label("Place a field with the Force Field spawner type");
+                }
+                for (i, field) in force_fields.iter_mut().enumerate() {
+                    egui::CollapsingHeader::new(format!("Force Field {}", i)).show(ui, |ui| {
+                        ui.columns(2, |ui| {
+                            ui[0].label("Enabled");
+                            ui[1].add(egui::Checkbox::new(&mut field.enabled, "Enabled"));
+                        });
+                        force_field_settings(ui, field, true);
+                        if ui.button("Delete").clicked() {
+                            delete = Some(i);
+                        }
+                    });
+                }
+            });
+        if let Some(i) = delete {
+            force_fields.remove(i);
+        }
+    }
+    fn fluid_region_menu(&mut self, fluid_regions: &mut Vec<FluidRegion>) {
+        let mut delete = None;
+        egui::Window::new("Fluid Regions")
+            .resizable(false)
+            .vscroll(true)
+            .default_open(true)
+            .max_height(400.0)
+            .title_bar(false)
+            .show(self.egui_renderer.context(), |ui| {
+                ui.heading("Fluid Regions");
+                if fluid_regions.is_empty() {
+                    ui.label("Place water with the Fluid Region spawner type");
+                }
+                for (i, fluid_region) in fluid_regions.iter_mut().enumerate() {
+                    egui::CollapsingHeader::new(format!("Fluid Region {}", i)).show(ui, |ui| {
+                        ui.columns(2, |ui| {
+                            ui[0].label("Density");
+                            ui[1].add(egui::DragValue::new(&mut fluid_region.density).speed(0.01).range(0.0..=f32::MAX));
+                        });
+                        ui.columns(2, |ui| {
+                            ui[0].label("Linear Drag");
+                            ui[1].add(egui::DragValue::new(&mut fluid_region.linear_drag).speed(0.01).range(0.0..=f32::MAX));
+                        });
+                        ui.columns(2, |ui| {
+                            ui[0].label("Angular Drag");
+                            ui[1].add(egui::DragValue::new(&mut fluid_region.angular_drag).speed(0.01).range(0.0..=f32::MAX));
+                        });
+                        let color = &mut fluid_region.color;
+                        let mut rgba = [color.r, color.g, color.b, color.a];
+                        ui.columns(2, |ui| {
+                            ui[0].label("Color");
+                            ui[1].color_edit_button_rgba_unmultiplied(&mut rgba);
+                        });
+                        *color = ColorRGBA::new(rgba[0], rgba[1], rgba[2], rgba[3]);
+                        if ui.button("Delete").clicked() {
+                            delete = Some(i);
+                        }
+                    });
+                }
+                ui.columns(2, |ui| {
+                    if ui[0].button("Save").clicked()
+                        && let Err(error) = FluidRegion::save_regions(FLUID_REGIONS_PATH, fluid_regions)
+                    {
+                        eprintln!("Failed to save fluid regions: {}", error);
+                    }
+                    if ui[1].button("Load").clicked() {
+                        fluid_regions.extend(FluidRegion::load_regions(FLUID_REGIONS_PATH));
+                    }
+                });
+            });
+        if let Some(i) = delete {
+            fluid_regions.remove(i);
+        }
+    }
+    fn particle_fluid_menu(&mut self, particle_fluids: &mut Vec<ParticleFluid>) {
+        let mut delete = None;
+        egui::Window::new("Particle Fluids")
+            .resizable(false)
+            .vscroll(true)
+            .default_open(true)
+            .max_height(400.0)
+            .title_bar(false)
+            .show(self.egui_renderer.context(), |ui| {
+                ui.heading("Particle Fluids");
+                if particle_fluids.is_empty() {
+                    ui.label("Pour in particles with the Particle Fluid spawner type");
+                }
+                for (i, particle_fluid) in particle_fluids.iter_mut().enumerate() {
+                    egui::CollapsingHeader::new(format!("Particle Fluid {}", i)).show(ui, |ui| {
+                        ui.label(format!("Particles: {}", particle_fluid.particles.len()));
+                        ui.label(format!("Spacing: {}", particle_fluid.spacing));
+                        ui.columns(2, |ui| {
+                            ui[0].label("Rest Density");
+                            ui[1].add(egui::DragValue::new(&mut particle_fluid.rest_density).speed(0.01).range(0.01..=f32::MAX));
+                        });
+                        ui.columns(2, |ui| {
+                            ui[0].label("Stiffness");
+                            ui[1].add(egui::DragValue::new(&mut particle_fluid.stiffness).speed(10.0).range(1.0..=f32::MAX));
+                        });
+                        ui.columns(2, |ui| {
+                            ui[0].label("Viscosity");
+                            ui[1].add(egui::DragValue::new(&mut particle_fluid.viscosity).speed(0.001).range(0.0..=f32::MAX));
+                        });
+                        ui.columns(2, |ui| {
+                            ui[0].label("Surface Tension");
+                            ui[1].add(egui::DragValue::new(&mut particle_fluid.surface_tension).speed(0.01).range(0.0..=f32::MAX));
+                        });
+                        ui.columns(2, |ui| {
+                            ui[0].label("Friction");
+                            ui[1].add(egui::DragValue::new(&mut particle_fluid.friction).speed(0.01).range(0.0..=1.0));
+                        });
+                        ui.columns(2, |ui| {
+                            ui[0].label("Gravity Multiplier");
+                            ui[1].add(egui::DragValue::new(&mut particle_fluid.gravity_multiplier).speed(0.01));
+                        });
+                        let color = &mut particle_fluid.color;
+                        let mut rgba = [color.r, color.g, color.b, color.a];
+                        ui.columns(2, |ui| {
+                            ui[0].label("Color");
+                            ui[1].color_edit_button_rgba_unmultiplied(&mut rgba);
+                        });
+                        *color = ColorRGBA::new(rgba[0], rgba[1], rgba[2], rgba[3]);
+                        ui.columns(2, |ui| {
+                            if ui[0].button("Clear").clicked() {
+                                particle_fluid.particles.clear();
+                            }
+                            if ui[1].button("Delete").clicked() {
+                                delete = Some(i);
+                            }
+                        });
+                    });
+                }
+            });
+        if let Some(i) = delete {
+            particle_fluids.remove(i);
+        }
+    }
+    fn drag_params_menu(&mut self, drag_params: &mut DragParams) {
+        egui::Window::new("Drag Settings")
+            .resizable(false)
+            .vscroll(false)
+            .default_open(true)
+            .anchor(Align2::RIGHT_CENTER, [0.0, 0.0])
+            .title_bar(false)
+            .show(self.egui_renderer.context(), |ui| {
+                ui.heading("Drag Settings");
+                ui.columns(2, |ui| {
+                    ui[0].label("Max Force");
+                    ui[1].add(egui::DragValue::new(&mut drag_params.max_force).speed(1.0).range(0.0..=f32::MAX));
+                });
+                ui.columns(2, |ui| {
+                    ui[0].label("Frequency (Hz)");
+                    ui[1].add(egui::DragValue::new(&mut drag_params.frequency).speed(0.05).range(0.01..=60.0));
+                });
+                ui.columns(2, |ui| {
+                    ui[0].label("Damping Ratio");
+                    ui[1].add(egui::DragValue::new(&mut drag_params.damping_ratio).speed(0.01).range(0.0..=2.0));
+                });
+            });
+    }
+
     fn fps_menu(&mut self, timing: &mut Timing) {
         egui::Window::new("FPS")
             .resizable(false)
@@ -179,10 +497,31 @@ impl RenderSystem {
                     ui[1]
                         .add(egui::DragValue::new(&mut parameters.gravity_force.y).speed(0.1));
                 });
+                ui.columns(2, |ui| {
+                    ui[0].label("Gravitational Constant");
+                    ui[1].add(egui::DragValue::new(&mut parameters.gravitational_constant).speed(0.01));
+                });
+                ui.columns(2, |ui| {
+                    ui[0].label("Coulomb Constant");
+                    ui[1].add(egui::DragValue::new(&mut parameters.coulomb_constant).speed(0.01));
+                });
+                ui.columns(2, |ui| {
+                    ui[0].label("Magnetic Constant");
+                    ui[1].add(egui::DragValue::new(&mut parameters.magnetic_constant).speed(0.01));
+                });
+                ui.columns(2, |ui| {
+                    ui[0].label("Softening");
+                    ui[1].add(egui::DragValue::new(&mut parameters.softening).speed(0.01).range(0.0..=f32::MAX));
+                });
+                ui.checkbox(&mut parameters.field_lines, "Field Lines");
+                ui.columns(2, |ui| {
+                    ui[0].label("Prediction Time");
+                    ui[1].add(egui::DragValue::new(&mut parameters.prediction_time).speed(0.1).range(0.0..=f32::MAX));
+                });
             });
     }
 
-    fn advanced_menu(&mut self, parameters: &mut Parameters) {
+    fn advanced_menu(&mut self, parameters: &mut Parameters, physics_system: &mut PhysicsSystem) {
         egui::Window::new("Spawner")
             .resizable(false)
             .vscroll(false)
@@ -207,6 +546,18 @@ impl RenderSystem {
                     ui[1]
                         .add(egui::DragValue::new(&mut parameters.updates_per_frame).speed(1));
                 });
+                ui.columns(2, |ui| {
+                    ui[0].label(format!("Broken Constraints: {}", physics_system.break_events.len()));
+                    if ui[1].button("Clear").clicked() {
+                        physics_system.break_events.clear();
+                    }
+                });
+                for event in physics_system.break_events.iter().rev().take(5) {
+                    ui.label(format!(
+                        "{:.2}s {:?} {}-{} at force {:.2}",
+                        event.time, event.constraint, event.body_a, event.body_b, event.force
+                    ));
+                }
             });
     }
 
@@ -233,6 +584,16 @@ impl RenderSystem {
                             BodyType::Rectangle,
                             "Rectangle",
                         );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::Circle,
+                            "Circle",
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::Capsule,
+                            "Capsule",
+                        );
                         ui.selectable_value(
                             &mut spawn_parameters.body_type,
                             BodyType::Spring,
@@ -248,6 +609,51 @@ impl RenderSystem {
                             BodyType::PivotJoint,
                             "Pivot Joint"
                         );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::PrismaticJoint,
+                            "Prismatic Joint"
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::DistanceJoint,
+                            "Distance Joint"
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::RopeJoint,
+                            "Rope Joint"
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::SoftBody,
+                            "Soft Body"
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::Rope,
+                            "Rope"
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::ForceField,
+                            "Force Field"
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::FluidRegion,
+                            "Fluid Region"
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::ParticleFluid,
+                            "Particle Fluid"
+                        );
+                        ui.selectable_value(
+                            &mut spawn_parameters.body_type,
+                            BodyType::Explosion,
+                            "Explosion"
+                        );
                     });
                 match spawn_parameters.body_type {
                     BodyType::RegularPolygon => {
@@ -341,10 +747,56 @@ impl RenderSystem {
                             };
                         });
                     }
+                    BodyType::Circle | BodyType::Capsule => {
+                        ui.label("Click anywhere on the screen not overlapping another rigidbody to spawn another");
+                        egui::ComboBox::from_label("Color")
+                            .selected_text(format!(
+                                "{:?}",
+                                spawn_parameters.rigidbody_params.color_type
+                            ))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut spawn_parameters.rigidbody_params.color_type,
+                                    ColorType::Random,
+                                    "Random Color",
+                                );
+                                ui.selectable_value(
+                                    &mut spawn_parameters.rigidbody_params.color_type,
+                                    ColorType::Set,
+                                    "Set Color",
+                                );
+                            });
+                        if spawn_parameters.body_type == BodyType::Capsule {
+                            ui.columns(2, |ui| {
+                                ui[0].label("Length");
+                                ui[1].add(
+                                    egui::DragValue::new(
+                                        &mut spawn_parameters.rigidbody_params.width,
+                                    )
+                                    .speed(0.01),
+                                );
+                                if spawn_parameters.rigidbody_params.width < 0.0 {
+                                    spawn_parameters.rigidbody_params.width = 0.0
+                                };
+                            });
+                        }
+                        ui.columns(2, |ui| {
+                            ui[0].label("Radius");
+                            ui[1].add(
+                                egui::DragValue::new(
+                                    &mut spawn_parameters.rigidbody_params.radius,
+                                )
+                                .speed(0.01),
+                            );
+                            if spawn_parameters.rigidbody_params.radius < 0.001 {
+                                spawn_parameters.rigidbody_params.radius = 0.001
+                            };
+                        });
+                    }
                     _ => {}
                 }
 
-                if spawn_parameters.body_type == BodyType::Rectangle || spawn_parameters.body_type == BodyType::RegularPolygon {
+                if spawn_parameters.body_type.is_rigidbody() {
                     ui.columns(2, |ui| {
                         ui[0].label("Restitution/Bounciness");
                         ui[1].add(
@@ -384,16 +836,68 @@ impl RenderSystem {
                             .speed(0.01),
                         )
                     });
+                    let mut selected_material = None;
+                    egui::ComboBox::from_label("Material")
+                        .selected_text("Apply Preset")
+                        .show_ui(ui, |ui| {
+                            for (i, material) in spawn_parameters.materials.iter().enumerate() {
+                                if ui.selectable_label(false, &material.name).clicked() {
+                                    selected_material = Some(i);
+                                }
+                            }
+                        });
+                    if let Some(i) = selected_material {
+                        let material = spawn_parameters.materials[i].clone();
+                        spawn_parameters.apply_material(&material);
+                    }
                     ui.columns(2, |ui| {
-                        ui[0].label("Mass");
+                        ui[0].label("Use Density");
+                        ui[1].add(egui::Checkbox::new(
+                            &mut spawn_parameters.rigidbody_params.use_density,
+                            "Mass from area",
+                        ));
+                    });
+                    if spawn_parameters.rigidbody_params.use_density {
+                        ui.columns(2, |ui| {
+                            ui[0].label("Density");
+                            ui[1].add(
+                                egui::DragValue::new(&mut spawn_parameters.rigidbody_params.density)
+                                    .speed(0.01),
+                            );
+                            if spawn_parameters.rigidbody_params.density <= 0.0 {
+                                spawn_parameters.rigidbody_params.density = 0.0000000001
+                            };
+                        });
+                    } else {
+                        ui.columns(2, |ui| {
+                            ui[0].label("Mass");
+                            ui[1].add(
+                                egui::DragValue::new(&mut spawn_parameters.rigidbody_params.mass)
+                                    .speed(0.01),
+                            );
+                            if spawn_parameters.rigidbody_params.mass <= 0.0 {
+                                spawn_parameters.rigidbody_params.mass = 0.0000000001
+                            };
+                        });
+                    }
+                    ui.columns(2, |ui| {
+                        ui[0].label("Friction");
                         ui[1].add(
-                            egui::DragValue::new(&mut spawn_parameters.rigidbody_params.mass)
+                            egui::DragValue::new(&mut spawn_parameters.rigidbody_params.friction)
                                 .speed(0.01),
                         );
-                        if spawn_parameters.rigidbody_params.mass <= 0.0 {
-                            spawn_parameters.rigidbody_params.mass = 0.0000000001
+                        if spawn_parameters.rigidbody_params.friction < 0.0 {
+                            spawn_parameters.rigidbody_params.friction = 0.0
                         };
                     });
+                    ui.columns(2, |ui| {
+                        ui[0].text_edit_singleline(&mut spawn_parameters.material_name);
+                        if ui[1].button("Save Preset").clicked()
+                            && let Err(error) = spawn_parameters.save_material()
+                        {
+                            eprintln!("Failed to save material presets: {}", error);
+                        }
+                    });
                     ui.columns(2, |ui| {
                         let mut rotation_degrees = spawn_parameters.rigidbody_params.rotation.to_degrees();
                         ui[0].label("Rotation");
@@ -420,6 +924,25 @@ impl RenderSystem {
                             .speed(0.01),
                         )
                     });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Charge");
+                        ui[1].add(egui::DragValue::new(&mut spawn_parameters.rigidbody_params.charge).speed(0.01));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Magnetic Moment");
+                        ui[1].add(egui::DragValue::new(&mut spawn_parameters.rigidbody_params.magnetic_moment).speed(0.01));
+                    });
+                    ui.columns(2, |ui| {
+                        let orbit_eccentricity = &mut spawn_parameters.rigidbody_params.orbit_eccentricity;
+                        let mut orbit = orbit_eccentricity.is_some();
+                        ui[0].add(egui::Checkbox::new(&mut orbit, "Orbit"));
+                        if orbit {
+                            let eccentricity = orbit_eccentricity.get_or_insert(0.0);
+                            ui[1].add(egui::DragValue::new(eccentricity).speed(0.01).range(0.0..=f32::MAX).prefix("e: "));
+                        } else {
+                            *orbit_eccentricity = None;
+                        }
+                    });
                     ui.columns(2, |ui| {
                         ui[0].label("Collides");
                         ui[1].add(egui::Checkbox::new(
@@ -489,7 +1012,130 @@ impl RenderSystem {
                             spawn_parameters.spring_params.rest_length = 0.0
                         };
                     });
-                } else if spawn_parameters.body_type == BodyType::WeldJoint || spawn_parameters.body_type == BodyType::PivotJoint {
+                    spring_law_settings(ui, &mut spawn_parameters.spring_params.law);
+                    break_threshold(ui, "Break Force", &mut spawn_parameters.spring_params.break_force);
+                } else if spawn_parameters.body_type == BodyType::DistanceJoint
+                    || spawn_parameters.body_type == BodyType::RopeJoint {
+                    ui.label("To spawn a joint:");
+                    ui.label("Click on a rigidbody and drag.");
+                    ui.label("Drop the line on a different rigidbody to connect them.");
+                    ui.label("The current distance between the two points becomes the joint length.");
+                    if spawn_parameters.body_type == BodyType::DistanceJoint {
+                        let joint_params = &mut spawn_parameters.joint_params;
+                        ui.columns(2, |ui| {
+                            ui[0].label("Length Limits");
+                            ui[1].add(egui::Checkbox::new(&mut joint_params.enable_limit, "Enabled"));
+                        });
+                        if joint_params.enable_limit {
+                            ui.columns(3, |ui| {
+                                let mut min_length = joint_params.min_length;
+                                let mut max_length = joint_params.max_length;
+                                ui[0].label("Min/Max");
+                                ui[1].add(egui::DragValue::new(&mut min_length).speed(0.01));
+                                ui[2].add(egui::DragValue::new(&mut max_length).speed(0.01));
+                                joint_params.min_length = min_length.min(max_length).max(0.0);
+                                joint_params.max_length = max_length.max(min_length).max(0.0);
+                            });
+                        }
+                    }
+                } else if spawn_parameters.body_type == BodyType::ForceField {
+                    ui.label("Click anywhere on the screen to place a force field");
+                    let force_field_params = &mut spawn_parameters.force_field_params;
+                    force_field_settings(ui, &mut force_field_params.field, false);
+                    ui.columns(2, |ui| {
+                        ui[0].label("Bounded");
+                        ui[1].add(egui::Checkbox::new(&mut force_field_params.bounded, "Bounded"));
+                    });
+                    if force_field_params.bounded {
+                        ui.columns(3, |ui| {
+                            ui[0].label("Region Size");
+                            ui[1].add(egui::DragValue::new(&mut force_field_params.region_size.x).speed(0.1).range(0.0..=f32::MAX));
+                            ui[2].add(egui::DragValue::new(&mut force_field_params.region_size.y).speed(0.1).range(0.0..=f32::MAX));
+                        });
+                    }
+                } else if spawn_parameters.body_type == BodyType::FluidRegion {
+                    ui.label("Click anywhere on the screen to place a box of fluid");
+                    let fluid_params = &mut spawn_parameters.fluid_params;
+                    ui.columns(3, |ui| {
+                        ui[0].label("Size");
+                        ui[1].add(egui::DragValue::new(&mut fluid_params.size.x).speed(0.1).range(0.1..=f32::MAX));
+                        ui[2].add(egui::DragValue::new(&mut fluid_params.size.y).speed(0.1).range(0.1..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Density");
+                        ui[1].add(egui::DragValue::new(&mut fluid_params.density).speed(0.01).range(0.0..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Linear Drag");
+                        ui[1].add(egui::DragValue::new(&mut fluid_params.linear_drag).speed(0.01).range(0.0..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Angular Drag");
+                        ui[1].add(egui::DragValue::new(&mut fluid_params.angular_drag).speed(0.01).range(0.0..=f32::MAX));
+                    });
+                } else if spawn_parameters.body_type == BodyType::ParticleFluid {
+                    ui.label("Click anywhere on the screen to pour in a block of particles");
+                    let particle_params = &mut spawn_parameters.particle_params;
+                    ui.columns(3, |ui| {
+                        ui[0].label("Size");
+                        ui[1].add(egui::DragValue::new(&mut particle_params.size.x).speed(0.1).range(0.01..=f32::MAX));
+                        ui[2].add(egui::DragValue::new(&mut particle_params.size.y).speed(0.1).range(0.01..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Spacing");
+                        ui[1].add(egui::DragValue::new(&mut particle_params.spacing).speed(0.001).range(0.01..=1.0));
+                    });
+                } else if spawn_parameters.body_type == BodyType::Explosion {
+                    ui.label("Click anywhere on the screen to detonate an explosion");
+                    let explosion = &mut spawn_parameters.explosion;
+                    ui.columns(2, |ui| {
+                        ui[0].label("Radius");
+                        ui[1].add(egui::DragValue::new(&mut explosion.radius).speed(0.1).range(0.0..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Impulse");
+                        ui[1].add(egui::DragValue::new(&mut explosion.impulse).speed(1.0));
+                    });
+                    egui::ComboBox::from_label("Falloff")
+                        .selected_text(format!("{:?}", explosion.falloff))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut explosion.falloff, Falloff::None, "None");
+                            ui.selectable_value(&mut explosion.falloff, Falloff::Linear, "Linear");
+                            ui.selectable_value(&mut explosion.falloff, Falloff::InverseSquare, "InverseSquare");
+                        });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Occlusion");
+                        ui[1].add(egui::Checkbox::new(&mut explosion.occlusion, "Bodies Shield"));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Rays");
+                        ui[1].add(egui::DragValue::new(&mut explosion.rays).speed(1.0).range(1..=10000));
+                    });
+                } else if spawn_parameters.body_type == BodyType::Rope {
+                    ui.label("To spawn a rope:");
+                    ui.label("Click on a rigidbody and drag.");
+                    ui.label("Drop the line on a different rigidbody to hang a rope between them.");
+                    let chain_params = &mut spawn_parameters.chain_params;
+                    ui.columns(2, |ui| {
+                        ui[0].label("Links");
+                        ui[1].horizontal(|ui| {
+                            ui.selectable_value(&mut chain_params.link, ChainLink::Pivot, "Pivot");
+                            ui.selectable_value(&mut chain_params.link, ChainLink::Distance, "Distance");
+                        });
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Segments");
+                        ui[1].add(egui::DragValue::new(&mut chain_params.segments).range(1..=100));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Thickness");
+                        ui[1].add(egui::DragValue::new(&mut chain_params.thickness).speed(0.005).range(0.01..=1.0));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Segment Mass");
+                        ui[1].add(egui::DragValue::new(&mut chain_params.mass).speed(0.01).range(0.001..=100.0));
+                    });
+                } else if spawn_parameters.body_type.is_overlap_joint() {
                     ui.label("To spawn a joint:");
                     ui.label("Stop the simulation using the config menu.");
                     ui.label("Go to select mode.");
@@ -497,6 +1143,75 @@ impl RenderSystem {
                     ui.label("Change it's position to be overlapping another rigidbody.");
                     ui.label("Change to spawn mode.");
                     ui.label("Click an overlapping section to spawn a rigidbody");
+                    if spawn_parameters.body_type == BodyType::PrismaticJoint {
+                        ui.columns(2, |ui| {
+                            let mut axis_degrees = spawn_parameters.joint_params.axis_angle.to_degrees();
+                            ui[0].label("Axis Angle");
+                            ui[1].add(egui::DragValue::new(&mut axis_degrees).speed(0.5));
+                            spawn_parameters.joint_params.axis_angle = axis_degrees.to_radians();
+                        });
+                    }
+                    if spawn_parameters.body_type == BodyType::WeldJoint || spawn_parameters.body_type == BodyType::PivotJoint {
+                        break_threshold(ui, "Break Force", &mut spawn_parameters.joint_params.break_force);
+                    }
+                } else if spawn_parameters.body_type == BodyType::SoftBody {
+                    ui.label("Click anywhere on the screen not overlapping another rigidbody to spawn a soft body");
+                    let soft_body_params = &mut spawn_parameters.soft_body_params;
+                    ui.columns(2, |ui| {
+                        ui[0].label("Shape");
+                        ui[1].horizontal(|ui| {
+                            ui.selectable_value(&mut soft_body_params.grid, false, "Ring");
+                            ui.selectable_value(&mut soft_body_params.grid, true, "Grid");
+                        });
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label(if soft_body_params.grid { "Width" } else { "Radius" });
+                        ui[1].add(egui::DragValue::new(&mut soft_body_params.size).speed(0.01).range(0.1..=10.0));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label(if soft_body_params.grid { "Particles per Side" } else { "Particles" });
+                        ui[1].add(egui::DragValue::new(&mut soft_body_params.resolution).range(3..=64));
+                    });
+                    let params = &mut soft_body_params.params;
+                    ui.columns(2, |ui| {
+                        ui[0].label("Particle Radius");
+                        ui[1].add(egui::DragValue::new(&mut params.particle_radius).speed(0.005).range(0.01..=1.0));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Particle Mass");
+                        ui[1].add(egui::DragValue::new(&mut params.particle_mass).speed(0.01).range(0.001..=100.0));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Stiffness");
+                        ui[1].add(egui::DragValue::new(&mut params.stiffness).speed(0.1).range(0.0..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Shear Stiffness");
+                        ui[1].add(egui::DragValue::new(&mut params.shear_stiffness).speed(0.1).range(0.0..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Bend Stiffness");
+                        ui[1].add(egui::DragValue::new(&mut params.bend_stiffness).speed(0.1).range(0.0..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Dampening");
+                        ui[1].add(egui::DragValue::new(&mut params.damping).speed(0.01).range(0.0..=f32::MAX));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Pressure");
+                        ui[1].add(egui::DragValue::new(&mut params.pressure).speed(0.1).range(0.0..=f32::MAX));
+                    });
+                }
+                if spawn_parameters.body_type.is_drag_link() || spawn_parameters.body_type.is_overlap_joint() {
+                    egui::ComboBox::from_label("Anchor Snap")
+                        .selected_text(format!("{:?}", spawn_parameters.anchor_snap))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut spawn_parameters.anchor_snap, AnchorSnap::Free, "Free");
+                            ui.selectable_value(&mut spawn_parameters.anchor_snap, AnchorSnap::Vertex, "Vertex");
+                            ui.selectable_value(&mut spawn_parameters.anchor_snap, AnchorSnap::EdgeMidpoint, "Edge Midpoint");
+                            ui.selectable_value(&mut spawn_parameters.anchor_snap, AnchorSnap::Centroid, "Centroid");
+                            ui.selectable_value(&mut spawn_parameters.anchor_snap, AnchorSnap::Outline, "Outline");
+                        });
                 }
             });
     }
@@ -517,7 +1232,7 @@ impl RenderSystem {
                     .show_ui(ui, |ui| {
                         ui.selectable_value(&mut ui_system.input_mode, InputMode::Spawn, "Spawn Bodies", );
                         ui.selectable_value(&mut ui_system.input_mode, InputMode::Edit, "Edit Bodies", );
-                        ui.selectable_value(&mut ui_system.input_mode, InputMode::Drag, "Drag Body w/ Spring");
+                        ui.selectable_value(&mut ui_system.input_mode, InputMode::Drag, "Drag Body w/ Mouse");
                         //ui.selectable_value(&mut ui_system.input_mode, InputMode::Move, "Move Body w/ Mouse");
                         ui.selectable_value(&mut ui_system.input_mode, InputMode::Nothing, "Nothing", );
                     });
@@ -539,9 +1254,25 @@ impl RenderSystem {
         }
     }
 
-    fn editor_menu(&mut self, physics_system: &mut PhysicsSystem, ui_system: &mut UiSystem) {
+    fn editor_menu(&mut self, physics_system: &mut PhysicsSystem, ui_system: &mut UiSystem, parameters: &Parameters) {
         if ui_system.selected_polygon.is_some() {
-            let selected_polygon = &mut physics_system.polygons[ui_system.selected_polygon.unwrap()];
+            let selected_index = ui_system.selected_polygon.unwrap();
+            let gravitational_constant = parameters.gravitational_constant;
+            let central = dominant_attractor(&physics_system.polygons, selected_index, gravitational_constant)
+                .map(|j| (j, physics_system.polygons[j].clone()));
+            let selected_polygon = &mut physics_system.polygons[selected_index];
+            let weld_joints = &mut physics_system.weld_joints;
+            let pivot_joints = &mut physics_system.pivot_joints;
+            let prismatic_joints = &mut physics_system.prismatic_joints;
+            let distance_joints = &mut physics_system.distance_joints;
+            let rope_joints = &mut physics_system.rope_joints;
+            let wheel_joints = &mut physics_system.wheel_joints;
+            let gear_joints = &mut physics_system.gear_joints;
+            let mut retuned_gear = None;
+            let mut deleted_gear = None;
+            let telemetry = &mut physics_system.telemetry;
+            let menus = &mut ui_system.menus;
+            let orbit_eccentricity = &mut ui_system.orbit_eccentricity;
             egui::Window::new("Body Editor")
                 .resizable(false)
                 .vscroll(false)
@@ -582,8 +1313,9 @@ impl RenderSystem {
                                 .speed(0.1),
                         );
                         let angle_radians = angle_degrees.to_radians();
-                        selected_polygon.rotate(angle_radians - old_angle);
-                        selected_polygon.angle += angle_radians - old_angle;
+                        if angle_radians != old_angle {
+                            selected_polygon.rotate(angle_radians - old_angle);
+                        }
                     });
                     ui.columns(2, |ui| {
                         ui[0].label("Angular Velocity");
@@ -603,12 +1335,30 @@ impl RenderSystem {
                             selected_polygon.mass = 0.000000000001
                         };
                     });
+                    ui.columns(2, |ui| {
+                        let old_density = selected_polygon.density();
+                        let mut density = old_density;
+                        ui[0].label("Density");
+                        ui[1].add(egui::DragValue::new(&mut density).speed(0.01));
+                        if density != old_density && density > 0.0 {
+                            selected_polygon.set_density(density);
+                        }
+                    });
                     ui.columns(2, |ui| {
                         ui[0].label("Restitution/Bounciness");
                         ui[1].add(
                             egui::DragValue::new(&mut selected_polygon.restitution).speed(0.01),
                         )
                     });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Friction");
+                        ui[1].add(
+                            egui::DragValue::new(&mut selected_polygon.friction).speed(0.01),
+                        )
+                    });
+                    if selected_polygon.friction < 0.0 {
+                        selected_polygon.friction = 0.0
+                    }
                     if selected_polygon.restitution < 0.0 {
                         selected_polygon.restitution = 0.0
                     }
@@ -622,6 +1372,14 @@ impl RenderSystem {
                                 .speed(0.01),
                         )
                     });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Charge");
+                        ui[1].add(egui::DragValue::new(&mut selected_polygon.charge).speed(0.01));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Magnetic Moment");
+                        ui[1].add(egui::DragValue::new(&mut selected_polygon.magnetic_moment).speed(0.01));
+                    });
                     ui.columns(2, |ui| {
                         ui[0].label("Collides");
                         ui[1].add(egui::Checkbox::new(
@@ -633,10 +1391,51 @@ impl RenderSystem {
                         ui[0].label("Eternal");
                         ui[1].add(egui::Checkbox::new(&mut selected_polygon.eternal, "Eternal"));
                     });
+                    ui.columns(2, |ui| {
+                        let pinned = telemetry.iter().position(|pinned| pinned.body == selected_index);
+                        let mut pin = pinned.is_some();
+                        ui[0].label("Telemetry");
+                        ui[1].add(egui::Checkbox::new(&mut pin, "Pinned"));
+                        match (pinned, pin) {
+                            (None, true) => {
+                                let color = selected_polygon.color;
+                                telemetry.push(Telemetry::new(selected_index, ColorRGBA::new(color.r, color.g, color.b, 1.0)));
+                                menus[Menu::Telemetry as usize] = true;
+                            }
+                            (Some(i), false) => {
+                                telemetry.remove(i);
+                            }
+                            _ => {}
+                        }
+                    });
                     ui.columns(1, |ui| {
                         ui[0].label(format!("Velocity Polar Form {}", vec2_to_string(selected_polygon.velocity)));
 
                     });
+                    if let Some((central_index, central)) = &central {
+                        ui.separator();
+                        ui.label(format!("Orbiting Body {}", central_index));
+                        if let Some(elements) = OrbitalElements::new(selected_polygon, central, gravitational_constant) {
+                            ui.columns(2, |ui| {
+                                ui[0].label("Semi-major Axis");
+                                ui[1].label(format!("{:.3}", elements.semi_major_axis));
+                                ui[0].label("Eccentricity");
+                                ui[1].label(format!("{:.3}", elements.eccentricity));
+                                ui[0].label("Period");
+                                ui[1].label(elements.period.map_or("Escaping".to_string(), |period| format!("{:.3}", period)));
+                                ui[0].label("Periapsis");
+                                ui[1].label(format!("{:.3}", elements.periapsis));
+                                ui[0].label("Apoapsis");
+                                ui[1].label(elements.apoapsis.map_or("Escaping".to_string(), |apoapsis| format!("{:.3}", apoapsis)));
+                            });
+                        }
+                        ui.columns(2, |ui| {
+                            ui[0].add(egui::DragValue::new(orbit_eccentricity).speed(0.01).range(0.0..=f32::MAX).prefix("e: "));
+                            if ui[1].button("Set Orbit").clicked() {
+                                selected_polygon.set_orbit(central, *orbit_eccentricity, gravitational_constant);
+                            }
+                        });
+                    }
 
                     let param_color = &mut selected_polygon.color;
                     let mut color: [f32; 3] = [param_color.r, param_color.g, param_color.b];
@@ -645,8 +1444,238 @@ impl RenderSystem {
                         ui[1].color_edit_button_rgb(&mut color);
                     });
                     selected_polygon.change_color(ColorRGBA::new(color[0], color[1], color[2], 1.0));
+
+                    for (i, weld_joint) in weld_joints.iter_mut().enumerate() {
+                        if weld_joint.body_a != selected_index && weld_joint.body_b != selected_index {
+                            continue;
+                        }
+                        egui::CollapsingHeader::new(format!("Weld Joint {}", i)).show(ui, |ui| {
+                            weld_joint_settings(ui, weld_joint);
+                        });
+                    }
+                    for (i, pivot_joint) in pivot_joints.iter_mut().enumerate() {
+                        if pivot_joint.body_a != selected_index && pivot_joint.body_b != selected_index {
+                            continue;
+                        }
+                        egui::CollapsingHeader::new(format!("Pivot Joint {}", i)).show(ui, |ui| {
+                            pivot_joint_settings(ui, pivot_joint);
+                        });
+                    }
+                    for (i, prismatic_joint) in prismatic_joints.iter_mut().enumerate() {
+                        if prismatic_joint.body_a != selected_index && prismatic_joint.body_b != selected_index {
+                            continue;
+                        }
+                        egui::CollapsingHeader::new(format!("Prismatic Joint {}", i)).show(ui, |ui| {
+                            ui.columns(2, |ui| {
+                                ui[0].label("Motor");
+                                ui[1].add(egui::Checkbox::new(&mut prismatic_joint.enable_motor, "Enabled"));
+                            });
+                            if prismatic_joint.enable_motor {
+                                ui.columns(2, |ui| {
+                                    ui[0].label("Motor Speed");
+                                    ui[1].add(egui::DragValue::new(&mut prismatic_joint.motor_speed).speed(0.01));
+                                });
+                                ui.columns(2, |ui| {
+                                    ui[0].label("Max Motor Force");
+                                    ui[1].add(egui::DragValue::new(&mut prismatic_joint.max_motor_force).speed(0.1));
+                                    if prismatic_joint.max_motor_force < 0.0 {
+                                        prismatic_joint.max_motor_force = 0.0
+                                    };
+                                });
+                            }
+                            ui.columns(2, |ui| {
+                                ui[0].label("Translation Limits");
+                                ui[1].add(egui::Checkbox::new(&mut prismatic_joint.enable_limit, "Enabled"));
+                            });
+                            if prismatic_joint.enable_limit {
+                                ui.columns(3, |ui| {
+                                    let mut lower = prismatic_joint.lower_translation;
+                                    let mut upper = prismatic_joint.upper_translation;
+                                    ui[0].label("Lower/Upper");
+                                    ui[1].add(egui::DragValue::new(&mut lower).speed(0.01));
+                                    ui[2].add(egui::DragValue::new(&mut upper).speed(0.01));
+                                    prismatic_joint.lower_translation = lower.min(upper);
+                                    prismatic_joint.upper_translation = upper.max(lower);
+                                });
+                            }
+                        });
+                    }
+                    for (i, distance_joint) in distance_joints.iter_mut().enumerate() {
+                        if distance_joint.body_a != selected_index && distance_joint.body_b != selected_index {
+                            continue;
+                        }
+                        egui::CollapsingHeader::new(format!("Distance Joint {}", i)).show(ui, |ui| {
+                            ui.columns(2, |ui| {
+                                ui[0].label("Length Limits");
+                                ui[1].add(egui::Checkbox::new(&mut distance_joint.enable_limit, "Enabled"));
+                            });
+                            if distance_joint.enable_limit {
+                                ui.columns(3, |ui| {
+                                    let mut min_length = distance_joint.min_length;
+                                    let mut max_length = distance_joint.max_length;
+                                    ui[0].label("Min/Max");
+                                    ui[1].add(egui::DragValue::new(&mut min_length).speed(0.01));
+                                    ui[2].add(egui::DragValue::new(&mut max_length).speed(0.01));
+                                    distance_joint.set_limits(min_length, max_length);
+                                });
+                            } else {
+                                ui.columns(2, |ui| {
+                                    ui[0].label("Length");
+                                    ui[1].add(egui::DragValue::new(&mut distance_joint.length).speed(0.01));
+                                    if distance_joint.length < 0.0 {
+                                        distance_joint.length = 0.0
+                                    };
+                                });
+                            }
+                        });
+                    }
+                    for (i, rope_joint) in rope_joints.iter_mut().enumerate() {
+                        if rope_joint.joint.body_a != selected_index && rope_joint.joint.body_b != selected_index {
+                            continue;
+                        }
+                        egui::CollapsingHeader::new(format!("Rope Joint {}", i)).show(ui, |ui| {
+                            ui.columns(2, |ui| {
+                                ui[0].label("Max Length");
+                                ui[1].add(egui::DragValue::new(&mut rope_joint.joint.max_length).speed(0.01));
+                                if rope_joint.joint.max_length < 0.0 {
+                                    rope_joint.joint.max_length = 0.0
+                                };
+                            });
+                        });
+                    }
+                    for (i, wheel_joint) in wheel_joints.iter_mut().enumerate() {
+                        if wheel_joint.body_a != selected_index && wheel_joint.body_b != selected_index {
+                            continue;
+                        }
+                        egui::CollapsingHeader::new(format!("Wheel Joint {}", i)).show(ui, |ui| {
+                            ui.columns(2, |ui| {
+                                ui[0].label("Suspension Stiffness");
+                                ui[1].add(egui::DragValue::new(&mut wheel_joint.stiffness).speed(0.1));
+                                if wheel_joint.stiffness < 0.0 {
+                                    wheel_joint.stiffness = 0.0
+                                };
+                            });
+                            ui.columns(2, |ui| {
+                                ui[0].label("Suspension Damping");
+                                ui[1].add(egui::DragValue::new(&mut wheel_joint.damping).speed(0.01));
+                                if wheel_joint.damping < 0.0 {
+                                    wheel_joint.damping = 0.0
+                                };
+                            });
+                            ui.columns(2, |ui| {
+                                ui[0].label("Motor");
+                                ui[1].add(egui::Checkbox::new(&mut wheel_joint.enable_motor, "Enabled"));
+                            });
+                            if wheel_joint.enable_motor {
+                                ui.columns(2, |ui| {
+                                    ui[0].label("Motor Speed");
+                                    ui[1].add(egui::DragValue::new(&mut wheel_joint.motor_speed).speed(0.01));
+                                });
+                                ui.columns(2, |ui| {
+                                    ui[0].label("Max Motor Torque");
+                                    ui[1].add(egui::DragValue::new(&mut wheel_joint.max_motor_torque).speed(0.1));
+                                    if wheel_joint.max_motor_torque < 0.0 {
+                                        wheel_joint.max_motor_torque = 0.0
+                                    };
+                                });
+                            }
+                        });
+                    }
+                    for (i, gear_joint) in gear_joints.iter_mut().enumerate() {
+                        let bodies = [gear_joint.joint_a, gear_joint.joint_b].map(|target| match target {
+                            GearTarget::Pivot(index) => (pivot_joints[index].body_a, pivot_joints[index].body_b),
+                            GearTarget::Prismatic(index) => (prismatic_joints[index].body_a, prismatic_joints[index].body_b),
+                        });
+                        if !bodies.iter().any(|&(body_a, body_b)| body_a == selected_index || body_b == selected_index) {
+                            continue;
+                        }
+                        egui::CollapsingHeader::new(format!("Gear Joint {}", i)).show(ui, |ui| {
+                            ui.columns(2, |ui| {
+                                ui[0].label("Ratio");
+                                if ui[1].add(egui::DragValue::new(&mut gear_joint.ratio).speed(0.01)).changed() {
+                                    retuned_gear = Some(i);
+                                }
+                            });
+                            ui.columns(2, |ui| {
+                                ui[0].label("Beta");
+                                ui[1].add(egui::DragValue::new(&mut gear_joint.beta).speed(0.01).range(0.0..=1.0));
+                            });
+                            if ui.button("Delete").clicked() {
+                                deleted_gear = Some(i);
+                            }
+                        });
+                    }
                 });
+            // A new ratio moves the coupled coordinate, restart it from the current pose instead of snapping back.
+            if let Some(i) = retuned_gear {
+                let gear_joint = &physics_system.gear_joints[i];
+                let constant = gear_joint.coordinate(&physics_system.pivot_joints, &physics_system.prismatic_joints, &physics_system.polygons);
+                physics_system.gear_joints[i].constant = constant;
+            }
+            if let Some(i) = deleted_gear {
+                physics_system.remove_gear_joint(i);
+            }
+        } else if let Some(selected_joint) = ui_system.selected_joint {
+            let mut delete = false;
+            egui::Window::new("Joint Editor")
+                .resizable(false)
+                .vscroll(false)
+                .default_open(true)
+                .anchor(Align2::LEFT_CENTER, [0.0, 0.0])
+                .default_height(275.0)
+                .title_bar(false)
+                .show(self.egui_renderer.context(), |ui| {
+                    let (name, body_a, body_b, anchor_a, anchor_b, beta, reference_angle) = match selected_joint {
+                        SelectedJoint::Weld(index) => {
+                            let joint = &mut physics_system.weld_joints[index];
+                            ("Weld Joint", joint.body_a, joint.body_b, &mut joint.local_anchor_a, &mut joint.local_anchor_b, &mut joint.beta, &mut joint.reference_angle)
+                        }
+                        SelectedJoint::Pivot(index) => {
+                            let joint = &mut physics_system.pivot_joints[index];
+                            ("Pivot Joint", joint.body_a, joint.body_b, &mut joint.local_anchor_a, &mut joint.local_anchor_b, &mut joint.beta, &mut joint.reference_angle)
+                        }
+                    };
+                    ui.heading(format!("{} Editor", name));
+                    ui.columns(2, |ui| {
+                        ui[0].label("Bodies");
+                        ui[1].label(format!("{} - {}", body_a, body_b));
+                    });
+                    ui.columns(3, |ui| {
+                        ui[0].label("Anchor A");
+                        ui[1].add(egui::DragValue::new(&mut anchor_a.x).speed(0.01));
+                        ui[2].add(egui::DragValue::new(&mut anchor_a.y).speed(0.01));
+                    });
+                    ui.columns(3, |ui| {
+                        ui[0].label("Anchor B");
+                        ui[1].add(egui::DragValue::new(&mut anchor_b.x).speed(0.01));
+                        ui[2].add(egui::DragValue::new(&mut anchor_b.y).speed(0.01));
+                    });
+                    ui.columns(2, |ui| {
+                        ui[0].label("Beta");
+                        ui[1].add(egui::DragValue::new(beta).speed(0.01).range(0.0..=1.0));
+                    });
+                    ui.columns(2, |ui| {
+                        let mut reference_degrees = reference_angle.to_degrees();
+                        ui[0].label("Reference Angle");
+                        ui[1].add(egui::DragValue::new(&mut reference_degrees).speed(0.5));
+                        *reference_angle = reference_degrees.to_radians();
+                    });
+                    match selected_joint {
+                        SelectedJoint::Weld(index) => weld_joint_settings(ui, &mut physics_system.weld_joints[index]),
+                        SelectedJoint::Pivot(index) => pivot_joint_settings(ui, &mut physics_system.pivot_joints[index]),
+                    }
+                    if ui.button("Delete").clicked() {
+                        delete = true;
+                    }
+                });
+            if delete {
+                match selected_joint {
+                    SelectedJoint::Weld(index) => physics_system.remove_weld_joint(index, ui_system),
+                    SelectedJoint::Pivot(index) => physics_system.remove_pivot_joint(index, ui_system),
+                }
+            }
         } else if ui_system.selected_spring.is_some() {
+            let mut delete = false;
             let selected_spring = &mut physics_system.springs[ui_system.selected_spring.unwrap()];
             egui::Window::new("Spring Editor")
                 .resizable(false)
@@ -693,7 +1722,20 @@ impl RenderSystem {
                             egui::DragValue::new(&mut selected_spring.rest_length).speed(0.01),
                         );
                     });
+                    spring_law_settings(ui, &mut selected_spring.law);
+                    ui.columns(2, |ui| {
+                        ui[0].label("Force");
+                        ui[1].label(format!("{:.2}", selected_spring.force));
+                    });
+                    break_threshold(ui, "Break Force", &mut selected_spring.break_force);
+                    break_threshold(ui, "Break Stretch", &mut selected_spring.break_stretch);
+                    if ui.button("Delete").clicked() {
+                        delete = true;
+                    }
                 });
+            if delete {
+                physics_system.remove_spring(ui_system.selected_spring.unwrap(), ui_system);
+            }
         } else {
             egui::Window::new("Physics Body Editor")
                 .resizable(false)
@@ -844,3 +1886,236 @@ impl RenderSystem {
             });
     }
 }
+
+/// Plots every series against time, the plot can be dragged and zoomed and double clicking fits it to the data.
+fn line_chart(ui: &mut egui::Ui, id: &str, height: f32, series: Vec<(&str, egui::Color32, Vec<[f64; 2]>)>) {
+    Plot::new(id)
+        .height(height)
+        .legend(Legend::default())
+        .x_axis_label("Time (s)")
+        .show(ui, |plot_ui| {
+            for (name, color, points) in series {
+                plot_ui.line(Line::new(PlotPoints::from(points)).name(name).color(color));
+            }
+        });
+}
+
+/// Kind, strength and falloff of a force field, `placed` also shows its center and region.
+fn force_field_settings(ui: &mut egui::Ui, field: &mut ForceField, placed: bool) {
+    let center = field.center().unwrap_or_default();
+    ui.columns(2, |ui| {
+        ui[0].label("Kind");
+        ui[1].horizontal(|ui| {
+            if ui.selectable_label(matches!(field.kind, FieldKind::Wind { .. }), "Wind").clicked()
+                && !matches!(field.kind, FieldKind::Wind { .. })
+            {
+                field.kind = FieldKind::Wind { direction: Vec2::X };
+            }
+            if ui.selectable_label(matches!(field.kind, FieldKind::Radial { .. }), "Radial").clicked() {
+                field.kind = FieldKind::Radial { center };
+            }
+            if ui.selectable_label(matches!(field.kind, FieldKind::Vortex { .. }), "Vortex").clicked() {
+                field.kind = FieldKind::Vortex { center };
+            }
+        });
+    });
+    ui.columns(2, |ui| {
+        ui[0].label("Strength");
+        ui[1].add(egui::DragValue::new(&mut field.strength).speed(0.1));
+    });
+    match &mut field.kind {
+        FieldKind::Wind { direction } => {
+            ui.columns(2, |ui| {
+                let mut angle_degrees = direction.to_angle().to_degrees();
+                ui[0].label("Direction");
+                ui[1].add(egui::DragValue::new(&mut angle_degrees).speed(0.5));
+                *direction = Vec2::from_angle(angle_degrees.to_radians());
+            });
+        }
+        FieldKind::Radial { center } | FieldKind::Vortex { center } => {
+            if placed {
+                ui.columns(3, |ui| {
+                    ui[0].label("Center");
+                    ui[1].add(egui::DragValue::new(&mut center.x).speed(0.01));
+                    ui[2].add(egui::DragValue::new(&mut center.y).speed(0.01));
+                });
+            }
+            egui::ComboBox::from_label("Falloff")
+                .selected_text(format!("{:?}", field.falloff))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut field.falloff, Falloff::None, "None");
+                    ui.selectable_value(&mut field.falloff, Falloff::Linear, "Linear");
+                    ui.selectable_value(&mut field.falloff, Falloff::InverseSquare, "InverseSquare");
+                });
+            if field.falloff != Falloff::None {
+                ui.columns(2, |ui| {
+                    ui[0].label("Radius");
+                    ui[1].add(egui::DragValue::new(&mut field.radius).speed(0.01).range(0.0001..=f32::MAX));
+                });
+            }
+        }
+    }
+    if placed {
+        match &mut field.region {
+            FieldRegion::Everywhere => {
+                ui.label("Region: Everywhere");
+            }
+            FieldRegion::Aabb { min, max } => {
+                ui.columns(3, |ui| {
+                    ui[0].label("Region Min");
+                    ui[1].add(egui::DragValue::new(&mut min.x).speed(0.01));
+                    ui[2].add(egui::DragValue::new(&mut min.y).speed(0.01));
+                });
+                ui.columns(3, |ui| {
+                    ui[0].label("Region Max");
+                    ui[1].add(egui::DragValue::new(&mut max.x).speed(0.01));
+                    ui[2].add(egui::DragValue::new(&mut max.y).speed(0.01));
+                });
+                *max = max.max(*min);
+            }
+            FieldRegion::Polygon(vertices) => {
+                ui.label(format!("Region: Polygon with {} vertices", vertices.len()));
+            }
+        }
+    }
+}
+
+/// Checkbox enabling a break threshold followed by its value.
+fn break_threshold(ui: &mut egui::Ui, label: &str, threshold: &mut Option<f32>) {
+    ui.columns(2, |ui| {
+        let mut breakable = threshold.is_some();
+        ui[0].label(label);
+        ui[1].add(egui::Checkbox::new(&mut breakable, "Breakable"));
+        if breakable != threshold.is_some() {
+            *threshold = if breakable { Some(10.0) } else { None };
+        }
+    });
+    if let Some(value) = threshold {
+        ui.columns(2, |ui| {
+            ui[0].label("Threshold");
+            ui[1].add(egui::DragValue::new(value).speed(0.1));
+            if *value < 0.0 {
+                *value = 0.0
+            };
+        });
+    }
+}
+
+/// Force law picker with the settings of the chosen law, shared by the spawner and the spring editor.
+fn spring_law_settings(ui: &mut egui::Ui, law: &mut SpringLaw) {
+    egui::ComboBox::from_label("Spring Law")
+        .selected_text(match law {
+            SpringLaw::Linear => "Linear",
+            SpringLaw::CompressionOnly => "Compression Only",
+            SpringLaw::TensionOnly => "Tension Only",
+            SpringLaw::Bilinear { .. } => "Bilinear",
+            SpringLaw::Stiffening { .. } => "Stiffening",
+            SpringLaw::Torsional { .. } => "Torsional",
+            SpringLaw::Frequency { .. } => "Frequency",
+        })
+        .show_ui(ui, |ui| {
+            ui.selectable_value(law, SpringLaw::Linear, "Linear");
+            ui.selectable_value(law, SpringLaw::CompressionOnly, "Compression Only");
+            ui.selectable_value(law, SpringLaw::TensionOnly, "Tension Only");
+            if ui.selectable_label(matches!(law, SpringLaw::Bilinear { .. }), "Bilinear").clicked() {
+                *law = SpringLaw::Bilinear { threshold: 0.5, ratio: 3.0 };
+            }
+            if ui.selectable_label(matches!(law, SpringLaw::Stiffening { .. }), "Stiffening").clicked() {
+                *law = SpringLaw::Stiffening { cubic: 10.0 };
+            }
+            if ui.selectable_label(matches!(law, SpringLaw::Torsional { .. }), "Torsional").clicked() {
+                *law = SpringLaw::Torsional { rest_angle: 0.0 };
+            }
+            if ui.selectable_label(matches!(law, SpringLaw::Frequency { .. }), "Frequency").clicked() {
+                *law = SpringLaw::Frequency { frequency: 2.0, damping_ratio: 1.0 };
+            }
+        });
+    match law {
+        SpringLaw::Bilinear { threshold, ratio } => {
+            ui.columns(2, |ui| {
+                ui[0].label("Threshold");
+                ui[1].add(egui::DragValue::new(threshold).speed(0.01).range(0.0..=f32::MAX));
+            });
+            ui.columns(2, |ui| {
+                ui[0].label("Stiffness Ratio");
+                ui[1].add(egui::DragValue::new(ratio).speed(0.01).range(0.0..=f32::MAX));
+            });
+        }
+        SpringLaw::Stiffening { cubic } => {
+            ui.columns(2, |ui| {
+                ui[0].label("Cubic Stiffness");
+                ui[1].add(egui::DragValue::new(cubic).speed(0.1));
+            });
+        }
+        SpringLaw::Torsional { rest_angle } => {
+            ui.columns(2, |ui| {
+                let mut rest_degrees = rest_angle.to_degrees();
+                ui[0].label("Rest Angle");
+                ui[1].add(egui::DragValue::new(&mut rest_degrees).speed(0.5));
+                *rest_angle = rest_degrees.to_radians();
+            });
+        }
+        SpringLaw::Frequency { frequency, damping_ratio } => {
+            ui.columns(2, |ui| {
+                ui[0].label("Frequency (Hz)");
+                ui[1].add(egui::DragValue::new(frequency).speed(0.05).range(0.0..=60.0));
+            });
+            ui.columns(2, |ui| {
+                ui[0].label("Damping Ratio");
+                ui[1].add(egui::DragValue::new(damping_ratio).speed(0.01).range(0.0..=2.0));
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Reaction and break settings shared by the body editor and the joint inspector.
+fn weld_joint_settings(ui: &mut egui::Ui, weld_joint: &mut WeldJoint) {
+    ui.columns(2, |ui| {
+        ui[0].label("Reaction Force/Torque");
+        ui[1].label(format!("{:.2} / {:.2}", weld_joint.reaction_force.length(), weld_joint.reaction_torque));
+    });
+    break_threshold(ui, "Break Force", &mut weld_joint.break_force);
+    break_threshold(ui, "Break Torque", &mut weld_joint.break_torque);
+}
+
+/// Motor, limit and break settings shared by the body editor and the joint inspector.
+fn pivot_joint_settings(ui: &mut egui::Ui, pivot_joint: &mut PivotJoint) {
+    ui.columns(2, |ui| {
+        ui[0].label("Motor");
+        ui[1].add(egui::Checkbox::new(&mut pivot_joint.enable_motor, "Enabled"));
+    });
+    if pivot_joint.enable_motor {
+        ui.columns(2, |ui| {
+            ui[0].label("Motor Speed");
+            ui[1].add(egui::DragValue::new(&mut pivot_joint.motor_speed).speed(0.01));
+        });
+        ui.columns(2, |ui| {
+            ui[0].label("Max Motor Torque");
+            ui[1].add(egui::DragValue::new(&mut pivot_joint.max_motor_torque).speed(0.1));
+            if pivot_joint.max_motor_torque < 0.0 {
+                pivot_joint.max_motor_torque = 0.0
+            };
+        });
+    }
+    ui.columns(2, |ui| {
+        ui[0].label("Angle Limits");
+        ui[1].add(egui::Checkbox::new(&mut pivot_joint.enable_limit, "Enabled"));
+    });
+    if pivot_joint.enable_limit {
+        ui.columns(3, |ui| {
+            let mut lower_degrees = pivot_joint.lower_angle.to_degrees();
+            let mut upper_degrees = pivot_joint.upper_angle.to_degrees();
+            ui[0].label("Lower/Upper");
+            ui[1].add(egui::DragValue::new(&mut lower_degrees).speed(0.5));
+            ui[2].add(egui::DragValue::new(&mut upper_degrees).speed(0.5));
+            pivot_joint.lower_angle = lower_degrees.min(upper_degrees).to_radians();
+            pivot_joint.upper_angle = upper_degrees.max(lower_degrees).to_radians();
+        });
+    }
+    ui.columns(2, |ui| {
+        ui[0].label("Reaction Force");
+        ui[1].label(format!("{:.2}", pivot_joint.reaction_force.length()));
+    });
+    break_threshold(ui, "Break Force", &mut pivot_joint.break_force);
+}
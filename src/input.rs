@@ -1,8 +1,8 @@
 use glam::Vec2;
-use crate::body_builder::{BodyBuilder, SpringParams};
-use crate::enums::{BodyType, DraggingState, InputMode, Keys, Menu, Mouse};
-use crate::spring::Spring;
-use crate::{Camera, ColorRGBA, Parameters, PivotJoint, Rigidbody, WeldJoint};
+use crate::body_builder::{BodyBuilder, DragParams};
+use crate::enums::{BodyType, DraggingState, InputMode, Keys, Menu, Mouse, SelectedJoint};
+use crate::spring::{Spring, SpringLaw};
+use crate::{dominant_attractor, Camera, ColorRGBA, DistanceJoint, MouseJoint, Parameters, PivotJoint, PrismaticJoint, Rigidbody, Rope, RopeJoint, WeldJoint};
 use winit::dpi::PhysicalPosition;
 use winit::event::{ElementState, MouseButton, MouseScrollDelta};
 use winit::event_loop::ActiveEventLoop;
@@ -18,6 +18,7 @@ pub struct UiSystem{
 
     pub selected_polygon: Option<usize>,
     pub selected_spring: Option<usize>,
+    pub selected_joint: Option<SelectedJoint>,
     pub spring_polygon: Option<usize>,
     pub mouse_spring: Option<usize>,
     pub spawn_ghost_polygon: Option<usize>,
@@ -26,8 +27,10 @@ pub struct UiSystem{
     pub dragging: DraggingState,
     pub menus: [bool; 16],
 
-    pub drag_params: SpringParams,
+    pub drag_params: DragParams,
     pub spawn_parameters: BodyBuilder,
+    /// Eccentricity of the orbit the editor puts the selected body on.
+    pub orbit_eccentricity: f32,
 
     pub camera: Camera,
     pub window_dimensions: Vec2,
@@ -182,14 +185,11 @@ impl UiSystem {
             _ => {}
         }
     }
-    pub fn handle_input(&mut self, physics_system: &mut PhysicsSystem, color_system: &mut ColorSystem) {
+    pub fn handle_input(&mut self, physics_system: &mut PhysicsSystem, color_system: &mut ColorSystem, parameters: &Parameters) {
         let position = self.get_mouse_world_position();
         if self.pressed_keys[Keys::L as usize] == 1 {
             if self.under_mouse_is_clear(physics_system) == true{
-                physics_system.polygons
-                    .push(BodyBuilder::create_rigidbody(&self.spawn_parameters, &color_system.color_palette));
-                let length = physics_system.polygons.len() - 1;
-                physics_system.polygons[length].translate(position);
+                self.spawn_rigidbody(physics_system, color_system, position, parameters.gravitational_constant);
             }
         }
 
@@ -213,9 +213,38 @@ impl UiSystem {
         }
 
         if self.pressed_buttons[Mouse::Left as usize] == 1
-            && (self.input_mode == InputMode::Drag
-                || (self.input_mode == InputMode::Spawn
-                    && self.spawn_parameters.body_type == BodyType::Spring))
+            && self.input_mode == InputMode::Drag
+            && (self.dragging == DraggingState::Dragging || self.dragging == DraggingState::StartDragging)
+            && let Some(body) = self.selected_polygon
+        {
+            if body >= physics_system.polygons.len() {
+                self.selected_polygon = None;
+                return
+            }
+            let drag_params = &self.drag_params;
+            if self.dragging == DraggingState::StartDragging {
+                physics_system.mouse_joint = Some(MouseJoint::new(
+                    position,
+                    &physics_system.polygons,
+                    body,
+                    drag_params.max_force,
+                    drag_params.frequency,
+                    drag_params.damping_ratio,
+                ));
+            }
+            // Settings changed in the menu apply to the ongoing drag
+            if let Some(mouse_joint) = &mut physics_system.mouse_joint {
+                mouse_joint.target = position;
+                mouse_joint.max_force = drag_params.max_force;
+                mouse_joint.frequency = drag_params.frequency;
+                mouse_joint.damping_ratio = drag_params.damping_ratio;
+            }
+            self.dragging = DraggingState::Dragging;
+        }
+
+        if self.pressed_buttons[Mouse::Left as usize] == 1
+            && self.input_mode == InputMode::Spawn
+            && self.spawn_parameters.body_type.is_drag_link()
             &&  (self.dragging == DraggingState::Dragging
                 || self.dragging == DraggingState::StartDragging)
         {
@@ -236,32 +265,26 @@ impl UiSystem {
                 if self.dragging == DraggingState::StartDragging {
                     let mut anchor_pos = mouse_polygon.center - selected_polygon.center;
                     rotate_in_place(&mut anchor_pos, Vec2::ZERO, -selected_polygon.angle);
+                    anchor_pos = selected_polygon.snap_anchor(anchor_pos, self.spawn_parameters.anchor_snap);
                     physics_system.polygons.push(mouse_polygon);
                     let length = physics_system.polygons.len() - 1;
-                    let spring;
-                    if self.input_mode == InputMode::Spawn {
-                        spring = Spring::new(
-                            self.selected_polygon.unwrap(),
-                            length,
-                            anchor_pos,
-                            Vec2::ZERO,
-                            self.spawn_parameters.spring_params.rest_length,
-                            self.spawn_parameters.spring_params.stiffness,
-                            self.spawn_parameters.spring_params.dampening,
-                            &physics_system.polygons,
-                        );
-                    } else  {
-                        spring = Spring::new(
-                            self.selected_polygon.unwrap(),
-                            length,
-                            anchor_pos,
-                            Vec2::ZERO,
-                            0.0,
-                            physics_system.polygons[self.selected_polygon.unwrap()].mass * 5.0,
-                            1.0 * (11.0 * physics_system.polygons[self.selected_polygon.unwrap()].mass).sqrt() ,
-                            &physics_system.polygons,
-                        );
-                    }
+                    let spring_params = &self.spawn_parameters.spring_params;
+                    let (rest_length, stiffness, dampening) = if self.spawn_parameters.body_type == BodyType::Spring {
+                        (spring_params.rest_length, spring_params.stiffness, spring_params.dampening)
+                    } else {
+                        // Slack preview line, the joint itself is created when dropped on another body
+                        (0.0, 0.0, 0.0)
+                    };
+                    let spring = Spring::new(
+                        self.selected_polygon.unwrap(),
+                        length,
+                        anchor_pos,
+                        Vec2::ZERO,
+                        rest_length,
+                        stiffness,
+                        dampening,
+                        &physics_system.polygons,
+                    );
                     physics_system.springs.push(spring);
                     self.mouse_spring = Some(physics_system.springs.len() - 1);
                     self.spring_polygon = Some(physics_system.polygons.len() - 1);
@@ -301,34 +324,80 @@ impl UiSystem {
         self.mouse_pos = (position.x as f32, position.y as f32).into();
     }
 
-    pub fn handle_mouse_input(&mut self, state: ElementState, button: MouseButton, physics_system: &mut PhysicsSystem, color_system: &ColorSystem) {
+    /// Spawns a body with the spawner settings at `position`, put on an orbit when the spawner asks for one.
+    fn spawn_rigidbody(&self, physics_system: &mut PhysicsSystem, color_system: &ColorSystem, position: Vec2, gravitational_constant: f32) {
+        let index = physics_system.polygons.len();
+        physics_system.polygons
+            .push(BodyBuilder::create_rigidbody(&self.spawn_parameters, &color_system.color_palette));
+        physics_system.polygons[index].translate(position);
+        if let Some(eccentricity) = self.spawn_parameters.rigidbody_params.orbit_eccentricity
+            && let Some(central) = dominant_attractor(&physics_system.polygons, index, gravitational_constant)
+        {
+            let central = physics_system.polygons[central].clone();
+            physics_system.polygons[index].set_orbit(&central, eccentricity, gravitational_constant);
+        }
+    }
+
+    pub fn handle_mouse_input(&mut self, state: ElementState, button: MouseButton, physics_system: &mut PhysicsSystem, color_system: &ColorSystem, parameters: &Parameters) {
         let position = self.get_mouse_world_position();
         if button == MouseButton::Left {
             if state.is_pressed() && !self.is_pointer_used {
                 if self.input_mode == InputMode::Spawn{
-                    if self.spawn_parameters.body_type == BodyType::Spring {
+                    if self.spawn_parameters.body_type.is_drag_link() {
                         self.pressed_buttons[Mouse::Left as usize] = 1;
                         self.selected_polygon = self.get_polygon_under_mouse(physics_system);
                         if self.selected_polygon.is_some() { self.dragging = DraggingState::StartDragging }
-                    } else if self.spawn_parameters.body_type == BodyType::Rectangle || self.spawn_parameters.body_type == BodyType::RegularPolygon{
+                    } else if self.spawn_parameters.body_type.is_rigidbody() {
                         if self.under_mouse_is_clear(physics_system) == true {
                             self.pressed_buttons[Mouse::Left as usize] = 1;
-                            physics_system.polygons
-                                .push(BodyBuilder::create_rigidbody(&self.spawn_parameters, &color_system.color_palette));
-                            let length = physics_system.polygons.len() - 1;
-                            physics_system.polygons[length].translate(position);
+                            self.spawn_rigidbody(physics_system, color_system, position, parameters.gravitational_constant);
                         }
-                    } else if self.spawn_parameters.body_type == BodyType::WeldJoint || self.spawn_parameters.body_type == BodyType::PivotJoint{
+                    } else if self.spawn_parameters.body_type == BodyType::SoftBody {
+                        if self.get_polygon_under_mouse(physics_system).is_none() {
+                            let soft_body = self.spawn_parameters.create_soft_body(
+                                position,
+                                &color_system.color_palette,
+                                &mut physics_system.polygons,
+                                &mut physics_system.springs,
+                            );
+                            physics_system.soft_bodies.push(soft_body);
+                        }
+                    } else if self.spawn_parameters.body_type == BodyType::ForceField {
+                        physics_system.force_fields.push(self.spawn_parameters.create_force_field(position));
+                        self.menus[Menu::ForceFields as usize] = true;
+                    } else if self.spawn_parameters.body_type == BodyType::FluidRegion {
+                        physics_system.fluid_regions.push(self.spawn_parameters.create_fluid_region(position));
+                        self.menus[Menu::FluidRegions as usize] = true;
+                    } else if self.spawn_parameters.body_type == BodyType::ParticleFluid {
+                        self.spawn_parameters.spawn_particles(position, &mut physics_system.particle_fluids);
+                        self.menus[Menu::ParticleFluids as usize] = true;
+                    } else if self.spawn_parameters.body_type == BodyType::Explosion {
+                        let mut explosion = self.spawn_parameters.explosion.clone();
+                        explosion.center = position;
+                        physics_system.detonate(&explosion);
+                    } else if self.spawn_parameters.body_type.is_overlap_joint() {
                         let under_mouse = self.get_all_polygons_under_mouse(physics_system);
                         if under_mouse.len() >= 2 {
+                            // Every pair shares the anchor snapped onto the topmost body
+                            let body = &physics_system.polygons[under_mouse[0]];
+                            let position = body.transform_point(self.spawn_anchor(physics_system, under_mouse[0], position));
                             for i in 0..under_mouse.len() {
                                 for j in i+1..under_mouse.len() {
-                                    let anchor_a = physics_system.polygons[under_mouse[i]].center - position;
-                                    let anchor_b = physics_system.polygons[under_mouse[j]].center - position;
+                                    let anchor_a = physics_system.polygons[under_mouse[i]].inverse_transform_point(position);
+                                    let anchor_b = physics_system.polygons[under_mouse[j]].inverse_transform_point(position);
+                                    let break_force = self.spawn_parameters.joint_params.break_force;
                                     if self.spawn_parameters.body_type == BodyType::WeldJoint {
-                                        physics_system.weld_joints.push(WeldJoint::new(anchor_a, anchor_b, &mut physics_system.polygons, under_mouse[i], under_mouse[j]));
+                                        let mut weld_joint = WeldJoint::new(anchor_a, anchor_b, &mut physics_system.polygons, under_mouse[i], under_mouse[j]);
+                                        weld_joint.break_force = break_force;
+                                        physics_system.weld_joints.push(weld_joint);
                                     } else if self.spawn_parameters.body_type == BodyType::PivotJoint{
-                                        physics_system.pivot_joints.push(PivotJoint::new(anchor_a, anchor_b, &mut physics_system.polygons, under_mouse[i], under_mouse[j]));
+                                        let mut pivot_joint = PivotJoint::new(anchor_a, anchor_b, &mut physics_system.polygons, under_mouse[i], under_mouse[j]);
+                                        pivot_joint.break_force = break_force;
+                                        physics_system.pivot_joints.push(pivot_joint);
+                                    } else if self.spawn_parameters.body_type == BodyType::PrismaticJoint {
+                                        let world_axis = Vec2::from_angle(self.spawn_parameters.joint_params.axis_angle);
+                                        let local_axis = physics_system.polygons[under_mouse[i]].rotation_matrix().transpose() * world_axis;
+                                        physics_system.prismatic_joints.push(PrismaticJoint::new(anchor_a, anchor_b, local_axis, &mut physics_system.polygons, under_mouse[i], under_mouse[j]));
                                     }
                                 }
                             }
@@ -337,11 +406,20 @@ impl UiSystem {
                 }
                 else if self.input_mode == InputMode::Edit {
                     self.pressed_buttons[Mouse::Left as usize] = 1;
-                    self.selected_polygon = self.get_polygon_under_mouse(physics_system);
-                    if self.selected_polygon.is_some() { self.menus[Menu::Editor as usize] = true}
-                    else {
+                    // Joint markers sit on top of the bodies they connect, so they are picked first
+                    self.selected_joint = self.get_joint_under_mouse(physics_system);
+                    if self.selected_joint.is_some() {
                         self.selected_polygon = None;
-                        self.selected_spring = self.get_spring_under_mouse(physics_system);
+                        self.selected_spring = None;
+                        self.menus[Menu::Editor as usize] = true;
+                    } else {
+                        self.selected_polygon = self.get_polygon_under_mouse(physics_system);
+                        if self.selected_polygon.is_some() { self.menus[Menu::Editor as usize] = true}
+                        else {
+                            self.selected_polygon = None;
+                            self.selected_spring = self.get_spring_under_mouse(physics_system);
+                            if self.selected_spring.is_some() { self.menus[Menu::Editor as usize] = true }
+                        }
                     }
                 } else if self.input_mode == InputMode::Drag {
                     self.pressed_buttons[Mouse::Left as usize] = 1;
@@ -350,10 +428,7 @@ impl UiSystem {
                 }
             }  else if !state.is_pressed() || self.dragging == DraggingState::StopDragging {
                 if self.input_mode == InputMode::Drag {
-                    if self.spring_polygon.is_some() {physics_system.remove_rigidbody(self.spring_polygon.unwrap(), self); }
-                    if self.mouse_spring.is_some() {physics_system.remove_spring(self.mouse_spring.unwrap(), self); }
-                    self.mouse_spring = None;
-                    self.spring_polygon = None;
+                    physics_system.mouse_joint = None;
                     self.dragging = DraggingState::NotDragging;
                     self.selected_polygon = None;
                     self.pressed_buttons[Mouse::Left as usize] = 0;
@@ -362,10 +437,48 @@ impl UiSystem {
                     let mouse_polygon = Rigidbody::rectangle(0.03, 0.03, position, f32::MAX / 100000.0, 1.0, ColorRGBA::white());
                     let polygon2_index = self.get_polygon_under_mouse(physics_system);
                     if polygon2_index.is_some() && self.mouse_spring.is_some() && self.selected_polygon.unwrap() != polygon2_index.unwrap() {
-                        let polygon2 = &mut physics_system.polygons[polygon2_index.unwrap()];
-                        let anchor_pos = mouse_polygon.center - polygon2.center;
-                        physics_system.springs[self.mouse_spring.unwrap()].body_b = polygon2_index.unwrap();
-                        physics_system.springs[self.mouse_spring.unwrap()].anchor_b = anchor_pos;
+                        if self.spawn_parameters.body_type == BodyType::Spring {
+                            let anchor_pos = self.spawn_anchor(physics_system, polygon2_index.unwrap(), mouse_polygon.center);
+                            physics_system.springs[self.mouse_spring.unwrap()].body_b = polygon2_index.unwrap();
+                            physics_system.springs[self.mouse_spring.unwrap()].anchor_b = anchor_pos;
+                            physics_system.springs[self.mouse_spring.unwrap()].break_force = self.spawn_parameters.spring_params.break_force;
+                            let mut law = self.spawn_parameters.spring_params.law;
+                            // Torsional springs hold the bodies at the angle they were connected at
+                            let spring = &mut physics_system.springs[self.mouse_spring.unwrap()];
+                            if let SpringLaw::Torsional { rest_angle } = &mut law {
+                                *rest_angle = physics_system.polygons[spring.body_b].angle - physics_system.polygons[spring.body_a].angle;
+                            }
+                            spring.law = law;
+                        } else {
+                            let body_a = self.selected_polygon.unwrap();
+                            let body_b = polygon2_index.unwrap();
+                            let anchor_a = physics_system.springs[self.mouse_spring.unwrap()].anchor_a;
+                            let anchor_b = self.spawn_anchor(physics_system, body_b, position);
+                            physics_system.remove_spring(self.mouse_spring.unwrap(), self);
+                            if self.spawn_parameters.body_type == BodyType::Rope {
+                                let start = physics_system.polygons[body_a].transform_point(anchor_a);
+                                let end = physics_system.polygons[body_b].transform_point(anchor_b);
+                                Rope::new(
+                                    start,
+                                    end,
+                                    Some(body_a),
+                                    Some(body_b),
+                                    &self.spawn_parameters.chain_params,
+                                    &mut physics_system.polygons,
+                                    &mut physics_system.pivot_joints,
+                                    &mut physics_system.distance_joints,
+                                );
+                            } else if self.spawn_parameters.body_type == BodyType::DistanceJoint {
+                                let joint_params = &self.spawn_parameters.joint_params;
+                                let mut distance_joint = DistanceJoint::new(anchor_a, anchor_b, &physics_system.polygons, body_a, body_b);
+                                if joint_params.enable_limit {
+                                    distance_joint.set_limits(joint_params.min_length, joint_params.max_length);
+                                }
+                                physics_system.distance_joints.push(distance_joint);
+                            } else {
+                                physics_system.rope_joints.push(RopeJoint::new(anchor_a, anchor_b, &physics_system.polygons, body_a, body_b));
+                            }
+                        }
                     } else if self.mouse_spring.is_some() {
                         physics_system.remove_spring(self.mouse_spring.unwrap(), self);
                     }
@@ -388,7 +501,7 @@ impl UiSystem {
                     let position = physics_system.weld_joints[i].get_anchor_world_position(&physics_system.polygons);
                     polygon.move_to(position);
                     if self.is_colliding_mouse(&polygon){
-                        physics_system.remove_weld_joint(i);
+                        physics_system.remove_weld_joint(i, self);
                         erased = true;
                         break;
                     }
@@ -399,7 +512,65 @@ impl UiSystem {
                     let position = physics_system.pivot_joints[i].get_anchor_world_position(&physics_system.polygons);
                     polygon.move_to(position);
                     if self.is_colliding_mouse(&polygon) && !erased{
-                        physics_system.remove_pivot_joint(i);
+                        physics_system.remove_pivot_joint(i, self);
+                        erased = true;
+                        break;
+                    }
+                }
+
+                for i in (0..physics_system.prismatic_joints.len()).rev() {
+                    let mut polygon = BodyBuilder::create_joint();
+                    let position = physics_system.prismatic_joints[i].get_anchor_world_position(&physics_system.polygons);
+                    polygon.move_to(position);
+                    if self.is_colliding_mouse(&polygon) && !erased{
+                        physics_system.remove_prismatic_joint(i);
+                        erased = true;
+                        break;
+                    }
+                }
+
+                for i in (0..physics_system.distance_joints.len()).rev() {
+                    let link = physics_system.distance_joints[i].create_link(&physics_system.polygons);
+                    if self.is_colliding_mouse(&link) && !erased{
+                        physics_system.remove_distance_joint(i);
+                        erased = true;
+                        break;
+                    }
+                }
+
+                for i in (0..physics_system.rope_joints.len()).rev() {
+                    let link = physics_system.rope_joints[i].create_link(&physics_system.polygons);
+                    if self.is_colliding_mouse(&link) && !erased{
+                        physics_system.remove_rope_joint(i);
+                        erased = true;
+                        break;
+                    }
+                }
+
+                for i in (0..physics_system.wheel_joints.len()).rev() {
+                    let mut polygon = BodyBuilder::create_joint();
+                    let position = physics_system.wheel_joints[i].get_anchor_world_position(&physics_system.polygons);
+                    polygon.move_to(position);
+                    if self.is_colliding_mouse(&polygon) && !erased{
+                        physics_system.remove_wheel_joint(i);
+                        erased = true;
+                        break;
+                    }
+                }
+
+                for i in (0..physics_system.pulley_joints.len()).rev() {
+                    let links = physics_system.pulley_joints[i].create_links(&physics_system.polygons);
+                    if links.iter().any(|link| self.is_colliding_mouse(link)) && !erased{
+                        physics_system.remove_pulley_joint(i);
+                        erased = true;
+                        break;
+                    }
+                }
+
+                for i in (0..physics_system.gear_joints.len()).rev() {
+                    let link = physics_system.gear_joints[i].create_link(&physics_system.pivot_joints, &physics_system.prismatic_joints, &physics_system.polygons);
+                    if self.is_colliding_mouse(&link) && !erased{
+                        physics_system.remove_gear_joint(i);
                         erased = true;
                         break;
                     }
@@ -17,8 +17,27 @@ mod world_init;
 mod timing;
 mod weld_joint;
 mod pivot_joint;
+mod prismatic_joint;
+mod distance_joint;
+mod rope_joint;
+mod wheel_joint;
+mod pulley_joint;
+mod gear_joint;
+mod soft_body;
+mod chain;
+mod mouse_joint;
+mod force_field;
+mod fluid_region;
+mod particle_fluid;
+mod explosion;
+mod pairwise;
+mod orbit;
+mod telemetry;
 mod collision_resolution;
 mod energy;
+pub mod fixture;
+pub mod material;
+pub mod triangulation;
 
 pub use crate::app::*;
 pub use crate::color::ColorRGBA;
@@ -27,5 +46,23 @@ pub use crate::spring::*;
 pub use crate::world::*;
 pub use crate::weld_joint::WeldJoint;
 pub use crate::pivot_joint::PivotJoint;
+pub use crate::prismatic_joint::PrismaticJoint;
+pub use crate::distance_joint::DistanceJoint;
+pub use crate::rope_joint::RopeJoint;
+pub use crate::wheel_joint::WheelJoint;
+pub use crate::pulley_joint::PulleyJoint;
+pub use crate::gear_joint::{GearJoint, GearTarget};
+pub use crate::soft_body::{SoftBody, SoftBodyParams};
+pub use crate::chain::{ChainLink, ChainParams, Cloth, Rope};
+pub use crate::mouse_joint::MouseJoint;
+pub use crate::force_field::{Falloff, FieldKind, FieldRegion, ForceField};
+pub use crate::fluid_region::FluidRegion;
+pub use crate::particle_fluid::{FluidParticle, ParticleFluid};
+pub use crate::explosion::Explosion;
+pub use crate::pairwise::PairwiseConstants;
+pub use crate::orbit::{dominant_attractor, orbit_velocity, predict_trajectories, OrbitalElements};
+pub use crate::telemetry::{Telemetry, TelemetrySample};
+pub use crate::fixture::Fixture;
+pub use crate::material::Material;
 pub use glam::{Vec2, Vec4};
 
@@ -0,0 +1,73 @@
+use std::fs;
+use std::io;
+
+/// File user material presets are saved to, relative to the working directory.
+pub const MATERIAL_PRESETS_PATH: &str = "materials.txt";
+
+/// Physical properties shared by every body made of the same stuff.
+/// Densities are relative to water, so a body with density 1.0 has a mass equal to its area.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Material {
+    pub name: String,
+    pub density: f32,
+    pub restitution: f32,
+    pub friction: f32,
+}
+
+impl Material {
+    pub fn new(name: &str, density: f32, restitution: f32, friction: f32) -> Self {
+        Material { name: name.to_string(), density, restitution, friction }
+    }
+    pub fn wood() -> Self {
+        Self::new("Wood", 0.6, 0.4, 0.5)
+    }
+    pub fn steel() -> Self {
+        Self::new("Steel", 7.8, 0.3, 0.4)
+    }
+    pub fn rubber() -> Self {
+        Self::new("Rubber", 1.1, 0.85, 0.9)
+    }
+    pub fn ice() -> Self {
+        Self::new("Ice", 0.92, 0.1, 0.03)
+    }
+
+    /// Built-in material library.
+    pub fn presets() -> Vec<Material> {
+        vec![Self::wood(), Self::steel(), Self::rubber(), Self::ice()]
+    }
+
+    /// Reads user presets saved by [`Material::save_presets`], one `name,density,restitution,friction` per line.
+    /// Lines that fail to parse are skipped.
+    pub fn load_presets(path: &str) -> Vec<Material> {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return vec![];
+        };
+        contents
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+                if fields.len() != 4 || fields[0].is_empty() {
+                    return None;
+                }
+                Some(Material::new(
+                    fields[0],
+                    fields[1].parse().ok()?,
+                    fields[2].parse().ok()?,
+                    fields[3].parse().ok()?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Writes every material that isn't one of the built-in presets.
+    pub fn save_presets(path: &str, materials: &[Material]) -> io::Result<()> {
+        let presets = Self::presets();
+        let mut contents = String::new();
+        for material in materials.iter().filter(|material| !presets.contains(material)) {
+            // Commas would break the line format
+            let name = material.name.replace(',', " ");
+            contents += &format!("{},{},{},{}\n", name, material.density, material.restitution, material.friction);
+        }
+        fs::write(path, contents)
+    }
+}
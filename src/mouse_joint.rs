@@ -0,0 +1,89 @@
+use std::f32::consts::PI;
+use glam::{Mat2, Vec2};
+use crate::distance_joint::create_link;
+use crate::{ColorRGBA, Rigidbody};
+
+/// Soft constraint pulling a point of a body towards a world space target, used to drag bodies with the mouse.
+#[derive(Clone)]
+pub struct MouseJoint {
+    local_anchor: Vec2,
+    pub(crate) body: usize,
+    /// World space point the anchor is pulled towards.
+    pub target: Vec2,
+
+    /// Largest force the joint pulls with.
+    pub max_force: f32,
+    /// Oscillations per second of the anchor around the target, higher is stiffer.
+    pub frequency: f32,
+    /// 1.0 is critically damped, lower values overshoot the target.
+    pub damping_ratio: f32,
+}
+
+impl MouseJoint {
+    /// Grabs `body` at the world point `target`.
+    pub fn new(target: Vec2, rigidbodys: &[Rigidbody], body: usize, max_force: f32, frequency: f32, damping_ratio: f32) -> Self {
+        Self {
+            local_anchor: rigidbodys[body].inverse_transform_point(target),
+            body,
+            target,
+            max_force,
+            frequency,
+            damping_ratio,
+        }
+    }
+
+    pub fn get_anchor_world_position(&self, rigidbodys: &[Rigidbody]) -> Vec2 {
+        rigidbodys[self.body].transform_point(self.local_anchor)
+    }
+
+    /// Energy of the spring pulling the anchor towards the target, ignoring the `max_force` clamp.
+    pub fn potential_energy(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        let body = &rigidbodys[self.body];
+        let omega = 2.0 * PI * self.frequency;
+        let stretch = self.get_anchor_world_position(rigidbodys) - self.target;
+        0.5 * body.mass * omega * omega * stretch.length_squared()
+    }
+
+    pub fn solve_velocity_constraints(&self, rigidbodys: &mut [Rigidbody], dt: f32) {
+        let body = &mut rigidbodys[self.body];
+        let inv_m = 1.0 / body.mass;
+        let inv_i = 1.0 / body.moment_of_inertia;
+
+        // Spring and damper constants giving the requested frequency and damping ratio for this body
+        let omega = 2.0 * PI * self.frequency;
+        let stiffness = body.mass * omega * omega;
+        let damping = 2.0 * body.mass * self.damping_ratio * omega;
+        let softness = dt * (damping + dt * stiffness);
+        if softness <= 0.0 {
+            return;
+        }
+        let gamma = 1.0 / softness;
+
+        let r = body.rotation_matrix() * self.local_anchor;
+        let k = Mat2::from_cols(
+            Vec2::new(inv_m + inv_i * r.y * r.y + gamma, -inv_i * r.x * r.y),
+            Vec2::new(-inv_i * r.x * r.y, inv_m + inv_i * r.x * r.x + gamma),
+        );
+        if k.determinant().abs() <= 1e-12 {
+            return;
+        }
+
+        let c = body.center + r - self.target;
+        let bias = c * dt * stiffness * gamma;
+        let c_dot = body.velocity + body.angular_velocity * r.perp();
+        let mut impulse = k.inverse() * -(c_dot + bias);
+
+        let max_impulse = self.max_force * dt;
+        if impulse.length() > max_impulse {
+            impulse = impulse.normalize() * max_impulse;
+        }
+
+        body.velocity += impulse * inv_m;
+        body.angular_velocity += inv_i * r.perp_dot(impulse);
+    }
+
+    /// Line from the grabbed point to the target.
+    pub fn create_link(&self, rigidbodys: &[Rigidbody]) -> Rigidbody {
+        create_link(self.get_anchor_world_position(rigidbodys), self.target, ColorRGBA::new(1.0, 1.0, 1.0, 0.5))
+    }
+}
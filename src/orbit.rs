@@ -0,0 +1,248 @@
+use std::f32::consts::TAU;
+use glam::Vec2;
+use crate::distance_joint::create_link;
+use crate::{PairwiseConstants, Rigidbody};
+
+/// Steps a predicted trajectory is split into, whatever the prediction time.
+const PREDICTION_STEPS: usize = 300;
+/// Scenes with more bodies than this draw no predicted trajectories, the prediction grows with the square of the count.
+const MAX_PREDICTED_BODIES: usize = 200;
+
+/// Shape and timing of the two body orbit of `body` around `central`, treating the pair as isolated point masses
+/// and ignoring the softening.
+#[derive(Clone, Copy, Debug)]
+pub struct OrbitalElements {
+    /// Negative for hyperbolic paths, infinite for parabolic ones.
+    pub semi_major_axis: f32,
+    /// Zero for circles, below one for ellipses and one or above for paths escaping the central body.
+    pub eccentricity: f32,
+    /// Time of one revolution, `None` for escaping paths.
+    pub period: Option<f32>,
+    /// Closest distance to the central body.
+    pub periapsis: f32,
+    /// Farthest distance from the central body, `None` for escaping paths.
+    pub apoapsis: Option<f32>,
+    /// World space angle of the direction from the central body to the periapsis, in radians.
+    pub argument_of_periapsis: f32,
+}
+
+impl OrbitalElements {
+    /// Elements of the orbit `body` is on around `central` under N-body gravity with `gravitational_constant`.
+    /// `None` when the two bodies do not attract each other.
+    pub fn new(body: &Rigidbody, central: &Rigidbody, gravitational_constant: f32) -> Option<Self> {
+        let mu = gravitational_parameter(body, central, gravitational_constant);
+        let offset = body.center - central.center;
+        let distance = offset.length();
+        if mu <= 0.0 || distance <= f32::EPSILON {
+            return None;
+        }
+        let velocity = body.velocity - central.velocity;
+        let eccentricity_vector = (offset * (velocity.length_squared() - mu / distance) - velocity * offset.dot(velocity)) / mu;
+        let eccentricity = eccentricity_vector.length();
+        let semi_major_axis = 1.0 / (2.0 / distance - velocity.length_squared() / mu);
+        let angular_momentum = offset.perp_dot(velocity);
+        let bound = eccentricity < 1.0 && semi_major_axis > 0.0;
+        Some(Self {
+            semi_major_axis,
+            eccentricity,
+            period: bound.then(|| TAU * (semi_major_axis.powi(3) / mu).sqrt()),
+            // From the angular momentum so circles and parabolas stay finite
+            periapsis: angular_momentum * angular_momentum / mu / (1.0 + eccentricity),
+            apoapsis: bound.then_some(semi_major_axis * (1.0 + eccentricity)),
+            // Circles have no periapsis, measured from the body instead
+            argument_of_periapsis: if eccentricity > 1e-4 { eccentricity_vector / eccentricity } else { offset / distance }.to_angle(),
+        })
+    }
+}
+
+/// Sum of the masses times the gravitational constant, scaled like the N-body force of the pair.
+/// Static bodies have no gravity multiplier and give zero.
+pub fn gravitational_parameter(body: &Rigidbody, central: &Rigidbody, gravitational_constant: f32) -> f32 {
+    if body.gravity_multiplier == 0.0 || central.gravity_multiplier == 0.0 {
+        return 0.0;
+    }
+    gravitational_constant * body.gravity_multiplier * central.gravity_multiplier * (body.mass + central.mass)
+}
+
+/// Velocity that puts `body` on a counter clockwise orbit around `central` with `eccentricity`, with the current
+/// position as the periapsis. Zero gives a circle, one and above paths escaping the central body.
+/// `None` when the two bodies do not attract each other.
+pub fn orbit_velocity(body: &Rigidbody, central: &Rigidbody, eccentricity: f32, gravitational_constant: f32) -> Option<Vec2> {
+    let mu = gravitational_parameter(body, central, gravitational_constant);
+    let offset = body.center - central.center;
+    let distance = offset.length();
+    if mu <= 0.0 || distance <= f32::EPSILON {
+        return None;
+    }
+    let speed = (mu * (1.0 + eccentricity.max(0.0)) / distance).sqrt();
+    Some(central.velocity + offset.perp() / distance * speed)
+}
+
+/// Body pulling hardest on the body at `index` through N-body gravity, the one it is most likely orbiting.
+pub fn dominant_attractor(rigidbodys: &[Rigidbody], index: usize, gravitational_constant: f32) -> Option<usize> {
+    let body = rigidbodys.get(index)?;
+    rigidbodys
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| *j != index)
+        .map(|(j, other)| {
+            let pull = gravitational_parameter(body, other, gravitational_constant) / body.center.distance_squared(other.center);
+            (j, pull)
+        })
+        .filter(|(_, pull)| *pull > 0.0 && pull.is_finite())
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(j, _)| j)
+}
+
+/// Positions of every body over the next `duration` seconds under the pairwise forces and `gravity` alone,
+/// ignoring collisions, joints and force fields. Static bodies stay put, bodies keep their angles.
+/// Costs as much as a few hundred updates of the pairwise forces.
+pub fn predict_trajectories(rigidbodys: &[Rigidbody], constants: &PairwiseConstants, gravity: Vec2, duration: f32) -> Vec<Vec<Vec2>> {
+    let step = duration / PREDICTION_STEPS as f32;
+    let mut positions: Vec<Vec2> = rigidbodys.iter().map(|body| body.center).collect();
+    let mut velocities: Vec<Vec2> = rigidbodys.iter().map(|body| body.velocity).collect();
+    let mut trajectories: Vec<Vec<Vec2>> = positions.iter().map(|position| vec![*position]).collect();
+    let accelerations = |positions: &[Vec2]| -> Vec<Vec2> {
+        rigidbodys
+            .iter()
+            .enumerate()
+            .map(|(i, body)| {
+                if body.is_static {
+                    return Vec2::ZERO;
+                }
+                let mut force = Vec2::ZERO;
+                for (j, other) in rigidbodys.iter().enumerate() {
+                    if i != j {
+                        force += constants.force(body, positions[i], other, positions[j]);
+                    }
+                }
+                force / body.mass + gravity * body.gravity_multiplier
+            })
+            .collect()
+    };
+    if step <= 0.0 || rigidbodys.is_empty() {
+        return trajectories;
+    }
+    // Leapfrog, keeps predicted orbits closed where a plain step would spiral out
+    let mut acceleration = accelerations(&positions);
+    for _ in 0..PREDICTION_STEPS {
+        for i in 0..rigidbodys.len() {
+            if rigidbodys[i].is_static {
+                continue;
+            }
+            velocities[i] += acceleration[i] * step * 0.5;
+            positions[i] += velocities[i] * step;
+        }
+        acceleration = accelerations(&positions);
+        for i in 0..rigidbodys.len() {
+            if rigidbodys[i].is_static {
+                continue;
+            }
+            velocities[i] += acceleration[i] * step * 0.5;
+            trajectories[i].push(positions[i]);
+        }
+    }
+    trajectories
+}
+
+/// Line segments along the predicted trajectory of every moving body, in the body's color fading with time.
+pub fn trajectory_lines(rigidbodys: &[Rigidbody], constants: &PairwiseConstants, gravity: Vec2, duration: f32) -> Vec<Rigidbody> {
+    let mut lines = vec![];
+    // Segments shorter than this are merged so slow bodies stay cheap to draw
+    let min_length = 0.02;
+    for (body, trajectory) in rigidbodys.iter().zip(predict_trajectories(rigidbodys, constants, gravity, duration)) {
+        let mut previous = trajectory[0];
+        for (k, point) in trajectory.iter().enumerate().skip(1) {
+            if point.distance(previous) < min_length {
+                continue;
+            }
+            let mut color = body.color;
+            color.a = 0.6 * (1.0 - k as f32 / trajectory.len() as f32);
+            lines.push(create_link(previous, *point, color));
+            previous = *point;
+        }
+    }
+    lines
+}
+
+/// Predicted trajectory lines kept between frames, recomputed only when a body or a setting they depend on changed.
+#[derive(Clone, Default)]
+pub(crate) struct TrajectoryPrediction {
+    settings: Option<(PairwiseConstants, Vec2, f32)>,
+    states: Vec<[f32; 9]>,
+    pub(crate) lines: Vec<Rigidbody>,
+}
+
+impl TrajectoryPrediction {
+    pub(crate) fn update(&mut self, rigidbodys: &[Rigidbody], constants: &PairwiseConstants, gravity: Vec2, duration: f32) {
+        if duration <= 0.0 || rigidbodys.len() > MAX_PREDICTED_BODIES {
+            self.settings = None;
+            self.states.clear();
+            self.lines.clear();
+            return;
+        }
+        let settings = Some((*constants, gravity, duration));
+        let states: Vec<[f32; 9]> = rigidbodys.iter().map(prediction_state).collect();
+        if settings == self.settings && states == self.states {
+            return;
+        }
+        self.lines = trajectory_lines(rigidbodys, constants, gravity, duration);
+        self.settings = settings;
+        self.states = states;
+    }
+}
+
+/// Everything about a body the prediction depends on.
+fn prediction_state(body: &Rigidbody) -> [f32; 9] {
+    [
+        body.center.x,
+        body.center.y,
+        body.velocity.x,
+        body.velocity.y,
+        body.angle,
+        body.mass,
+        body.gravity_multiplier,
+        body.charge,
+        body.magnetic_moment,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColorRGBA;
+
+    #[test]
+    fn circular_orbit_elements() {
+        let central = Rigidbody::circle(1.0, Vec2::ZERO, 1000.0, 0.0, ColorRGBA::white());
+        let mut body = Rigidbody::circle(0.1, Vec2::new(10.0, 0.0), 1.0, 0.0, ColorRGBA::white());
+        body.set_orbit(&central, 0.0, 1.0);
+        let elements = OrbitalElements::new(&body, &central, 1.0).unwrap();
+        assert!(elements.eccentricity < 1e-4);
+        assert!((elements.semi_major_axis - 10.0).abs() < 1e-3);
+        assert!((elements.periapsis - 10.0).abs() < 1e-3);
+        let period = TAU * (1000.0f32 / 1001.0).sqrt();
+        assert!((elements.period.unwrap() - period).abs() < 1e-3);
+    }
+
+    #[test]
+    fn elliptic_orbit_starts_at_periapsis() {
+        let central = Rigidbody::circle(1.0, Vec2::ZERO, 1000.0, 0.0, ColorRGBA::white());
+        let mut body = Rigidbody::circle(0.1, Vec2::new(0.0, 5.0), 1.0, 0.0, ColorRGBA::white());
+        body.set_orbit(&central, 0.5, 1.0);
+        let elements = OrbitalElements::new(&body, &central, 1.0).unwrap();
+        assert!((elements.eccentricity - 0.5).abs() < 1e-4);
+        assert!((elements.periapsis - 5.0).abs() < 1e-3);
+        assert!((elements.apoapsis.unwrap() - 15.0).abs() < 1e-2);
+        assert!((elements.argument_of_periapsis - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+    }
+
+    #[test]
+    fn static_bodies_do_not_attract() {
+        let mut central = Rigidbody::circle(1.0, Vec2::ZERO, 1000.0, 0.0, ColorRGBA::white());
+        central.gravity_multiplier = 0.0;
+        let body = Rigidbody::circle(0.1, Vec2::new(10.0, 0.0), 1.0, 0.0, ColorRGBA::white());
+        assert!(OrbitalElements::new(&body, &central, 1.0).is_none());
+        assert_eq!(dominant_attractor(&[body, central], 0, 1.0), None);
+    }
+}
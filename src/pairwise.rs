@@ -0,0 +1,228 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use glam::Vec2;
+use crate::distance_joint::create_link;
+use crate::{ColorRGBA, Parameters, Rigidbody};
+
+/// Length of one step when tracing a field line.
+const FIELD_LINE_STEP: f32 = 0.1;
+/// Steps after which a field line that ran off into empty space is cut.
+const FIELD_LINE_STEPS: usize = 200;
+/// Field lines started around every source.
+const FIELD_LINES_PER_SOURCE: usize = 12;
+
+/// Strengths of the forces bodies exert on each other at a distance, taken from the [`Parameters`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PairwiseConstants {
+    pub gravitational: f32,
+    pub coulomb: f32,
+    pub magnetic: f32,
+    /// Length added in quadrature to every distance, keeps the forces finite when bodies get close.
+    pub softening: f32,
+}
+
+impl PairwiseConstants {
+    pub fn new(parameters: &Parameters) -> Self {
+        Self {
+            gravitational: parameters.gravitational_constant,
+            coulomb: parameters.coulomb_constant,
+            magnetic: parameters.magnetic_constant,
+            softening: parameters.softening,
+        }
+    }
+
+    /// Squared distance across `offset` including the softening, never zero.
+    fn softened_distance_squared(&self, offset: Vec2) -> f32 {
+        offset.length_squared() + self.softening * self.softening + 1e-14
+    }
+
+    /// Electric field at `point` of a charge sitting at `center`.
+    pub fn electric_field(&self, point: Vec2, center: Vec2, charge: f32) -> Vec2 {
+        let offset = point - center;
+        let distance_squared = self.softened_distance_squared(offset);
+        offset * (self.coulomb * charge / (distance_squared * distance_squared.sqrt()))
+    }
+
+    /// Magnetic field at `point` of a dipole with `moment` sitting at `center`.
+    pub fn magnetic_field(&self, point: Vec2, center: Vec2, moment: Vec2) -> Vec2 {
+        let offset = point - center;
+        let distance_squared = self.softened_distance_squared(offset);
+        (offset * 3.0 * moment.dot(offset) / distance_squared - moment) * (self.magnetic / (distance_squared * distance_squared.sqrt()))
+    }
+
+    /// Force `other` exerts on `body` with the two bodies at `position` and `other_position`.
+    /// Covers N-body gravity, the Coulomb force between charges and the force between magnetic dipoles.
+    pub fn force(&self, body: &Rigidbody, position: Vec2, other: &Rigidbody, other_position: Vec2) -> Vec2 {
+        let offset = other_position - position;
+        let distance_squared = self.softened_distance_squared(offset);
+        let inverse_cube = 1.0 / (distance_squared * distance_squared.sqrt());
+        let mut force = Vec2::ZERO;
+        if self.gravitational != 0.0 && body.gravity_multiplier != 0.0 && other.gravity_multiplier != 0.0 {
+            // Product of both masses, the integrator divides by the mass of `body` so its own mass drops out
+            force += offset
+                * (self.gravitational * body.mass * body.gravity_multiplier * other.mass * other.gravity_multiplier * inverse_cube);
+        }
+        if self.coulomb != 0.0 && body.charge != 0.0 && other.charge != 0.0 {
+            // Like charges repel
+            force -= offset * (self.coulomb * body.charge * other.charge * inverse_cube);
+        }
+        if self.magnetic != 0.0 && body.magnetic_moment != 0.0 && other.magnetic_moment != 0.0 {
+            // Gradient of the dipole energy, `separation` points from the other dipole to this one
+            let separation = -offset;
+            let moment = body.dipole_moment();
+            let other_moment = other.dipole_moment();
+            let along = moment.dot(separation);
+            let other_along = other_moment.dot(separation);
+            force += (moment * other_along + other_moment * along + separation * moment.dot(other_moment)
+                - separation * 5.0 * along * other_along / distance_squared)
+                * (3.0 * self.magnetic * inverse_cube / distance_squared);
+        }
+        force
+    }
+
+    /// Torque turning the dipole of `body` towards the magnetic field of `other`.
+    pub fn torque(&self, body: &Rigidbody, other: &Rigidbody) -> f32 {
+        if self.magnetic == 0.0 || body.magnetic_moment == 0.0 || other.magnetic_moment == 0.0 {
+            return 0.0;
+        }
+        body.dipole_moment().perp_dot(self.magnetic_field(body.center, other.center, other.dipole_moment()))
+    }
+
+    /// Potential energy of the N-body gravity, the charges and the dipoles of a pair of bodies.
+    pub fn energy(&self, body: &Rigidbody, other: &Rigidbody) -> f32 {
+        let offset = other.center - body.center;
+        let distance = self.softened_distance_squared(offset).sqrt();
+        let mut energy = 0.0;
+        // Also skips static bodies, whose huge masses would overflow the product
+        if self.gravitational != 0.0 && body.gravity_multiplier != 0.0 && other.gravity_multiplier != 0.0 {
+            energy -= self.gravitational * body.mass * body.gravity_multiplier * other.mass * other.gravity_multiplier / distance;
+        }
+        energy += self.coulomb * body.charge * other.charge / distance;
+        energy -= body.dipole_moment().dot(self.magnetic_field(body.center, other.center, other.dipole_moment()));
+        energy
+    }
+
+    /// Electric and magnetic field of every charge and dipole at `point`.
+    pub fn fields(&self, point: Vec2, rigidbodys: &[Rigidbody]) -> (Vec2, Vec2) {
+        let mut electric = Vec2::ZERO;
+        let mut magnetic = Vec2::ZERO;
+        for body in rigidbodys {
+            if body.charge != 0.0 {
+                electric += self.electric_field(point, body.center, body.charge);
+            }
+            if body.magnetic_moment != 0.0 {
+                magnetic += self.magnetic_field(point, body.center, body.dipole_moment());
+            }
+        }
+        (electric, magnetic)
+    }
+
+    /// Lines following the electric field out of the positive charges, or into the negative charges when there
+    /// are no positive ones, and the magnetic field out of the north pole of every dipole.
+    pub fn field_lines(&self, rigidbodys: &[Rigidbody]) -> Vec<Rigidbody> {
+        let mut lines = vec![];
+        if self.coulomb != 0.0 {
+            let color = ColorRGBA::new(1.0, 0.85, 0.2, 0.6);
+            let sign = if rigidbodys.iter().any(|body| body.charge > 0.0) { 1.0 } else { -1.0 };
+            for body in rigidbodys.iter().filter(|body| body.charge * sign > 0.0) {
+                for k in 0..FIELD_LINES_PER_SOURCE {
+                    let direction = Vec2::from_angle(k as f32 * TAU / FIELD_LINES_PER_SOURCE as f32);
+                    let start = body.center + direction * body.radius;
+                    let sink = |other: &Rigidbody| other.charge * sign < 0.0;
+                    let field = |point: Vec2| self.fields(point, rigidbodys).0 * sign;
+                    lines.extend(trace_field_line(start, rigidbodys, sink, field, color));
+                }
+            }
+        }
+        if self.magnetic != 0.0 {
+            let color = ColorRGBA::new(0.4, 0.8, 1.0, 0.6);
+            for body in rigidbodys.iter().filter(|body| body.magnetic_moment != 0.0) {
+                // Spread over the half of the outline facing the north pole
+                let north = body.dipole_moment().normalize_or_zero();
+                for k in 0..FIELD_LINES_PER_SOURCE {
+                    let angle = (k as f32 + 0.5) / FIELD_LINES_PER_SOURCE as f32 * PI - FRAC_PI_2;
+                    let start = body.center + Vec2::from_angle(angle).rotate(north) * body.radius;
+                    let sink = |other: &Rigidbody| other.magnetic_moment != 0.0;
+                    let field = |point: Vec2| self.fields(point, rigidbodys).1;
+                    lines.extend(trace_field_line(start, rigidbodys, sink, field, color));
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// Follows `field` from `start` in fixed steps until it runs into the bounding circle of a body matching `sink`,
+/// the field vanishes or the step limit is reached.
+fn trace_field_line(
+    start: Vec2,
+    rigidbodys: &[Rigidbody],
+    sink: impl Fn(&Rigidbody) -> bool,
+    field: impl Fn(Vec2) -> Vec2,
+    color: ColorRGBA,
+) -> Vec<Rigidbody> {
+    let mut segments = vec![];
+    let mut point = start;
+    for _ in 0..FIELD_LINE_STEPS {
+        // Midpoint step, follows curved lines much closer than a plain step
+        let Some(direction) = field(point).try_normalize() else {
+            break;
+        };
+        let Some(direction) = field(point + direction * FIELD_LINE_STEP * 0.5).try_normalize() else {
+            break;
+        };
+        let next = point + direction * FIELD_LINE_STEP;
+        segments.push(create_link(point, next, color));
+        point = next;
+        if rigidbodys.iter().any(|body| sink(body) && body.center.distance(point) < body.radius) {
+            break;
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The force on a body has to be minus the gradient of the pair energy with respect to its position.
+    #[test]
+    fn force_is_energy_gradient() {
+        let constants = PairwiseConstants { gravitational: 1.5, coulomb: 2.0, magnetic: 0.7, softening: 0.1 };
+        let mut body = Rigidbody::circle(0.2, Vec2::new(0.3, -0.2), 2.0, 0.0, ColorRGBA::white());
+        body.charge = 1.0;
+        body.magnetic_moment = 1.2;
+        body.angle = 0.4;
+        let mut other = Rigidbody::circle(0.2, Vec2::new(1.4, 0.9), 3.0, 0.0, ColorRGBA::white());
+        other.charge = -0.5;
+        other.magnetic_moment = 0.8;
+        other.angle = 2.1;
+
+        let force = constants.force(&body, body.center, &other, other.center);
+        let step = 1e-3;
+        let energy_at = |offset: Vec2| {
+            let mut moved = body.clone();
+            moved.center += offset;
+            constants.energy(&moved, &other)
+        };
+        let gradient = Vec2::new(
+            (energy_at(Vec2::X * step) - energy_at(-Vec2::X * step)) / (2.0 * step),
+            (energy_at(Vec2::Y * step) - energy_at(-Vec2::Y * step)) / (2.0 * step),
+        );
+        assert!(force.abs_diff_eq(-gradient, 1e-2 * force.length()));
+    }
+
+    #[test]
+    fn forces_are_opposite() {
+        let constants = PairwiseConstants { gravitational: 1.0, coulomb: 1.0, magnetic: 1.0, softening: 0.0 };
+        let mut body = Rigidbody::circle(0.2, Vec2::ZERO, 2.0, 0.0, ColorRGBA::white());
+        body.charge = 1.0;
+        body.magnetic_moment = 1.0;
+        let mut other = Rigidbody::circle(0.2, Vec2::new(2.0, 1.0), 5.0, 0.0, ColorRGBA::white());
+        other.charge = 2.0;
+        other.magnetic_moment = 0.5;
+        other.angle = 1.0;
+        let force = constants.force(&body, body.center, &other, other.center);
+        let reaction = constants.force(&other, other.center, &body, body.center);
+        assert!(force.abs_diff_eq(-reaction, 1e-5));
+    }
+}
@@ -0,0 +1,404 @@
+use std::f32::consts::PI;
+use glam::Vec2;
+use crate::rigidbody::outline;
+use crate::{ColorRGBA, Rigidbody};
+
+/// Most sub steps a fluid takes in one physics update, larger time steps are left unstable instead of stalling.
+const MAX_SUBSTEPS: usize = 32;
+/// Strength of the artificial viscosity damping particles running into each other, keeps runny fluids stable.
+const ARTIFICIAL_VISCOSITY: f32 = 0.05;
+
+/// Particle indices bucketed by grid cell, so neighbours are only searched for in the adjacent cells.
+/// Cells are hashed into a table twice the size of the particle count, unrelated cells may share a bucket.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SpatialHash {
+    cell_size: f32,
+    /// Start of every bucket in `entries`, followed by the total count.
+    bucket_starts: Vec<usize>,
+    entries: Vec<usize>,
+}
+
+impl SpatialHash {
+    fn bucket(&self, x: i32, y: i32) -> usize {
+        let hash = (x.wrapping_mul(92837111) ^ y.wrapping_mul(689287499)) as u32;
+        hash as usize % (self.bucket_starts.len() - 1)
+    }
+
+    fn cell(&self, position: Vec2) -> (i32, i32) {
+        ((position.x / self.cell_size).floor() as i32, (position.y / self.cell_size).floor() as i32)
+    }
+
+    /// Sorts `positions` into cells of `cell_size`, which has to be at least the search radius.
+    pub(crate) fn rebuild(&mut self, cell_size: f32, positions: &[Vec2]) {
+        self.cell_size = cell_size;
+        self.bucket_starts.clear();
+        self.bucket_starts.resize(positions.len() * 2 + 2, 0);
+        let buckets: Vec<usize> = positions
+            .iter()
+            .map(|&position| {
+                let (x, y) = self.cell(position);
+                self.bucket(x, y)
+            })
+            .collect();
+        for &bucket in &buckets {
+            self.bucket_starts[bucket + 1] += 1;
+        }
+        for i in 1..self.bucket_starts.len() {
+            self.bucket_starts[i] += self.bucket_starts[i - 1];
+        }
+        // Fills every bucket from its end, leaving the starts in place
+        let mut ends = self.bucket_starts[1..].to_vec();
+        self.entries.clear();
+        self.entries.resize(positions.len(), 0);
+        for (i, &bucket) in buckets.iter().enumerate().rev() {
+            ends[bucket] -= 1;
+            self.entries[ends[bucket]] = i;
+        }
+    }
+
+    /// Indices in the cell of `position` and the eight around it, a superset of the neighbours within one cell size.
+    pub(crate) fn neighbours(&self, position: Vec2) -> impl Iterator<Item = usize> + '_ {
+        let (x, y) = self.cell(position);
+        let mut buckets = [0; 9];
+        for (i, bucket) in buckets.iter_mut().enumerate() {
+            *bucket = self.bucket(x + i as i32 % 3 - 1, y + i as i32 / 3 - 1);
+        }
+        // Neighbouring cells hashed into the same bucket would list its particles twice
+        buckets.sort_unstable();
+        let mut unique = 1;
+        for i in 1..buckets.len() {
+            if buckets[i] != buckets[unique - 1] {
+                buckets[unique] = buckets[i];
+                unique += 1;
+            }
+        }
+        buckets
+            .into_iter()
+            .take(unique)
+            .flat_map(|bucket| &self.entries[self.bucket_starts[bucket]..self.bucket_starts[bucket + 1]])
+            .copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FluidParticle {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    /// Density from the last step, rest density for a particle inside the fluid at rest.
+    pub density: f32,
+    pub pressure: f32,
+}
+
+/// Liquid made of particles, simulated with smoothed particle hydrodynamics.
+/// Particles collide with the rigidbodies and push back on them, so bodies float, sink and splash.
+#[derive(Clone, Debug)]
+pub struct ParticleFluid {
+    pub particles: Vec<FluidParticle>,
+    /// Distance between particles at rest, the particles interact up to twice this distance.
+    pub spacing: f32,
+    /// Relative to water like body densities.
+    pub rest_density: f32,
+    /// Pressure per unit of compression, stiffer fluids compress less but take more sub steps.
+    pub stiffness: f32,
+    /// Evens out the velocities of neighbouring particles, higher values flow like honey.
+    pub viscosity: f32,
+    /// Pulls particles at the surface towards the fluid, holding drops together.
+    pub surface_tension: f32,
+    /// Friction of the particles sliding along bodies, from 0 to 1.
+    pub friction: f32,
+    pub gravity_multiplier: f32,
+    pub color: ColorRGBA,
+    grid: SpatialHash,
+    /// Particles within the smoothing radius of each particle, found once per sub step.
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl ParticleFluid {
+    pub fn new(spacing: f32) -> Self {
+        Self {
+            particles: vec![],
+            spacing: spacing.max(0.001),
+            rest_density: 1.0,
+            stiffness: 1000.0,
+            viscosity: 0.02,
+            surface_tension: 0.5,
+            friction: 0.1,
+            gravity_multiplier: 1.0,
+            color: ColorRGBA::new(0.2, 0.5, 1.0, 0.8),
+            grid: SpatialHash::default(),
+            neighbours: vec![],
+        }
+    }
+
+    pub fn set_viscosity(&mut self, viscosity: f32) -> &mut Self {
+        self.viscosity = viscosity.max(0.0);
+        self
+    }
+
+    pub fn set_surface_tension(&mut self, surface_tension: f32) -> &mut Self {
+        self.surface_tension = surface_tension.max(0.0);
+        self
+    }
+
+    pub fn add_particle(&mut self, position: Vec2, velocity: Vec2) -> &mut Self {
+        self.particles.push(FluidParticle { position, velocity, density: self.rest_density, pressure: 0.0 });
+        self
+    }
+
+    /// Fills the box from `min` to `max` with particles at rest spacing.
+    pub fn add_block(&mut self, min: Vec2, max: Vec2, velocity: Vec2) -> &mut Self {
+        let count = ((max - min).abs() / self.spacing).floor().max(Vec2::ONE);
+        let start = min.min(max) + Vec2::splat(self.spacing * 0.5);
+        for i in 0..count.x as usize {
+            for j in 0..count.y as usize {
+                self.add_particle(start + Vec2::new(i as f32, j as f32) * self.spacing, velocity);
+            }
+        }
+        self
+    }
+
+    pub fn smoothing_radius(&self) -> f32 {
+        self.spacing * 2.0
+    }
+
+    /// Radius particles keep from the bodies, also the half size of their sprites.
+    pub fn particle_radius(&self) -> f32 {
+        self.spacing * 0.5
+    }
+
+    /// Mass that gives a square lattice at rest spacing exactly the rest density.
+    pub fn particle_mass(&self) -> f32 {
+        let h = self.smoothing_radius();
+        let reach = (h / self.spacing).ceil() as i32;
+        let mut lattice_sum = 0.0;
+        for i in -reach..=reach {
+            for j in -reach..=reach {
+                lattice_sum += poly6(Vec2::new(i as f32, j as f32).length() * self.spacing, h);
+            }
+        }
+        self.rest_density / lattice_sum
+    }
+
+    /// Advances the particles by `dt` in sub steps short enough for the pressure waves and exchanges impulses
+    /// with `rigidbodys`, `gravity` is the world gravity.
+    pub fn step(&mut self, rigidbodys: &mut [Rigidbody], gravity: Vec2, dt: f32) {
+        if self.particles.is_empty() || dt <= 0.0 {
+            return;
+        }
+        let h = self.smoothing_radius();
+        // Pressure waves may cross at most a fraction of a particle per sub step, the viscosity limit keeps
+        // the diffusion stable
+        let sound_speed = self.stiffness.max(1.0).sqrt();
+        let mut max_dt = 0.4 * h / sound_speed;
+        if self.viscosity > 0.0 {
+            max_dt = max_dt.min(0.1 * h * h * self.rest_density / self.viscosity);
+        }
+        let substeps = ((dt / max_dt).ceil() as usize).clamp(1, MAX_SUBSTEPS);
+        let sub_dt = dt / substeps as f32;
+        let mass = self.particle_mass();
+        for _ in 0..substeps {
+            self.find_neighbours();
+            self.update_densities(mass);
+            let accelerations = self.accelerations(mass, gravity);
+            // Speed limit against blow ups, a particle never skips more than half a smoothing radius per sub step
+            let max_speed = 0.5 * h / sub_dt;
+            for (particle, acceleration) in self.particles.iter_mut().zip(accelerations) {
+                particle.velocity = (particle.velocity + acceleration * sub_dt).clamp_length_max(max_speed);
+                particle.position += particle.velocity * sub_dt;
+            }
+            self.collide_with_bodies(rigidbodys, mass);
+        }
+    }
+
+    fn find_neighbours(&mut self) {
+        let h = self.smoothing_radius();
+        let positions: Vec<Vec2> = self.particles.iter().map(|particle| particle.position).collect();
+        self.grid.rebuild(h, &positions);
+        self.neighbours.resize_with(self.particles.len(), Vec::new);
+        for (i, neighbours) in self.neighbours.iter_mut().enumerate() {
+            let position = self.particles[i].position;
+            neighbours.clear();
+            neighbours.extend(
+                self.grid.neighbours(position).filter(|&j| position.distance_squared(self.particles[j].position) < h * h),
+            );
+        }
+    }
+
+    fn update_densities(&mut self, mass: f32) {
+        let h = self.smoothing_radius();
+        for i in 0..self.particles.len() {
+            let position = self.particles[i].position;
+            let density: f32 = self.neighbours[i]
+                .iter()
+                .map(|&j| mass * poly6(position.distance(self.particles[j].position), h))
+                .sum();
+            let particle = &mut self.particles[i];
+            particle.density = density.max(f32::EPSILON);
+            // Only compression pushes back, stretched regions at the surface are held together by the surface tension
+            particle.pressure = (self.stiffness * (density - self.rest_density)).max(0.0);
+        }
+    }
+
+    fn accelerations(&self, mass: f32, gravity: Vec2) -> Vec<Vec2> {
+        let h = self.smoothing_radius();
+        let spiky = 30.0 / (PI * h.powi(5));
+        let laplacian = 40.0 / (PI * h.powi(5));
+        let sound_speed = self.stiffness.max(1.0).sqrt();
+        let mut accelerations = vec![gravity * self.gravity_multiplier; self.particles.len()];
+        for (i, particle) in self.particles.iter().enumerate() {
+            let mut force = Vec2::ZERO;
+            for &j in &self.neighbours[i] {
+                if i == j {
+                    continue;
+                }
+                let other = &self.particles[j];
+                let offset = particle.position - other.position;
+                let distance = offset.length();
+                // Particles on top of each other are pushed apart in a direction picked from their indices
+                let direction = if distance > 1e-6 { offset / distance } else { Vec2::from_angle((i + j) as f32) };
+
+                let mut pressure = (particle.pressure + other.pressure) / (2.0 * other.density);
+                let approach = (particle.velocity - other.velocity).dot(offset);
+                if approach < 0.0 {
+                    let mean_density = (particle.density + other.density) * 0.5;
+                    pressure -= ARTIFICIAL_VISCOSITY * sound_speed * h * approach
+                        / (mean_density * (distance * distance + 0.01 * h * h))
+                        * particle.density;
+                }
+                force += direction * mass * pressure * spiky * (h - distance) * (h - distance);
+
+                force += (other.velocity - particle.velocity) * self.viscosity * mass / other.density * laplacian * (h - distance);
+
+                // Cohesion, boosted for particles missing neighbours so mostly the surface is pulled in
+                let correction = 2.0 * self.rest_density / (particle.density + other.density);
+                force -= direction * self.surface_tension * mass * self.rest_density * correction * poly6(distance, h);
+            }
+            accelerations[i] += force / particle.density;
+        }
+        accelerations
+    }
+
+    /// Pushes particles out of the bodies and applies the impulse that stops them to the bodies in return.
+    fn collide_with_bodies(&mut self, rigidbodys: &mut [Rigidbody], mass: f32) {
+        let radius = self.particle_radius();
+        // Boxes around the bodies grown by the particle radius, most particles are far from every body
+        let bounds: Vec<Option<(Vec2, Vec2)>> = rigidbodys
+            .iter()
+            .map(|body| {
+                if !body.collision {
+                    return None;
+                }
+                let points: Vec<Vec2> = body
+                    .colliders()
+                    .iter()
+                    .flat_map(|collider| outline(collider.shape, collider.vertices, collider.center, body.angle))
+                    .collect();
+                let min = points.iter().copied().reduce(Vec2::min)?;
+                let max = points.iter().copied().reduce(Vec2::max)?;
+                Some((min - Vec2::splat(radius), max + Vec2::splat(radius)))
+            })
+            .collect();
+        for particle in &mut self.particles {
+            for (body, bounds) in rigidbodys.iter_mut().zip(&bounds) {
+                let Some((min, max)) = bounds else {
+                    continue;
+                };
+                if particle.position.cmplt(*min).any() || particle.position.cmpgt(*max).any() {
+                    continue;
+                }
+                let local = body.inverse_transform_point(particle.position);
+                let surface = body.project_onto_outline(local);
+                let inside = body.contains_local_point(local);
+                let depth = local.distance(surface);
+                if !inside && depth >= radius {
+                    continue;
+                }
+                let local_normal = if inside { surface - local } else { local - surface };
+                let Some(local_normal) = local_normal.try_normalize() else {
+                    continue;
+                };
+                let normal = body.rotation_matrix() * local_normal;
+                let penetration = if inside { depth + radius } else { radius - depth };
+                particle.position += normal * penetration;
+
+                let (inverse_mass, inverse_inertia) = if body.is_static {
+                    (0.0, 0.0)
+                } else {
+                    (1.0 / body.mass, 1.0 / body.moment_of_inertia)
+                };
+                let arm = particle.position - normal * radius - body.center;
+                let relative_velocity = particle.velocity - (body.velocity + arm.perp() * body.angular_velocity);
+                let normal_speed = relative_velocity.dot(normal);
+                if normal_speed >= 0.0 {
+                    continue;
+                }
+                let arm_normal = arm.perp_dot(normal);
+                let effective_mass = 1.0 / (1.0 / mass + inverse_mass + arm_normal * arm_normal * inverse_inertia);
+                let mut impulse = normal * -normal_speed * effective_mass;
+                let tangent_velocity = relative_velocity - normal * normal_speed;
+                if let Some(tangent) = tangent_velocity.try_normalize() {
+                    let arm_tangent = arm.perp_dot(tangent);
+                    let tangent_mass = 1.0 / (1.0 / mass + inverse_mass + arm_tangent * arm_tangent * inverse_inertia);
+                    let friction = (tangent_velocity.length() * tangent_mass).min(self.friction * impulse.length());
+                    impulse -= tangent * friction;
+                }
+                particle.velocity += impulse / mass;
+                body.velocity -= impulse * inverse_mass;
+                body.angular_velocity -= arm.perp_dot(impulse) * inverse_inertia;
+                body.contact_impulse -= impulse;
+            }
+        }
+    }
+
+    /// Square sprite for every particle, as one mesh.
+    pub fn sprites(&self) -> (Vec<Vec2>, Vec<u32>) {
+        let half_size = self.particle_radius();
+        let mut vertices = Vec::with_capacity(self.particles.len() * 4);
+        let mut indices = Vec::with_capacity(self.particles.len() * 6);
+        for particle in &self.particles {
+            let first = vertices.len() as u32;
+            vertices.push(particle.position + Vec2::new(-half_size, -half_size));
+            vertices.push(particle.position + Vec2::new(half_size, -half_size));
+            vertices.push(particle.position + Vec2::new(half_size, half_size));
+            vertices.push(particle.position + Vec2::new(-half_size, half_size));
+            indices.extend_from_slice(&[first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+        (vertices, indices)
+    }
+}
+
+/// 2D poly6 smoothing kernel, integrates to one over the disk of radius `h`.
+fn poly6(distance: f32, h: f32) -> f32 {
+    if distance >= h {
+        return 0.0;
+    }
+    let difference = h * h - distance * distance;
+    4.0 / (PI * h.powi(8)) * difference * difference * difference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spatial_hash_finds_every_neighbour_once() {
+        let positions: Vec<Vec2> = (0..400)
+            .map(|i| Vec2::new((i % 20) as f32 * 0.13 - 1.0, (i / 20) as f32 * 0.17 - 2.0))
+            .collect();
+        let cell_size = 0.3;
+        let mut hash = SpatialHash::default();
+        hash.rebuild(cell_size, &positions);
+        for &position in &positions {
+            let mut found: Vec<usize> = hash.neighbours(position).collect();
+            let count = found.len();
+            found.sort_unstable();
+            found.dedup();
+            assert_eq!(found.len(), count);
+            for (j, other) in positions.iter().enumerate() {
+                if position.distance(*other) < cell_size {
+                    assert!(found.binary_search(&j).is_ok());
+                }
+            }
+        }
+    }
+}
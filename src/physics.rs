@@ -1,9 +1,45 @@
 use glam::Vec2;
 use crate::{Parameters, Rigidbody, Spring};
-use crate::energy::Energy;
+use crate::distance_joint::DistanceJoint;
+use crate::energy::{Energy, EnergyHistory};
+use crate::explosion::Explosion;
+use crate::force_field::Falloff;
+use crate::force_field::ForceField;
+use crate::fluid_region::FluidRegion;
+use crate::particle_fluid::ParticleFluid;
+use crate::gear_joint::GearJoint;
 use crate::ode_solver::{dormand_prince_step};
+use crate::pairwise::PairwiseConstants;
 use crate::pivot_joint::PivotJoint;
+use crate::prismatic_joint::PrismaticJoint;
+use crate::pulley_joint::PulleyJoint;
+use crate::mouse_joint::MouseJoint;
+use crate::orbit::TrajectoryPrediction;
+use crate::rope_joint::RopeJoint;
+use crate::soft_body::SoftBody;
+use crate::telemetry::Telemetry;
 use crate::weld_joint::WeldJoint;
+use crate::wheel_joint::WheelJoint;
+
+/// Kind of constraint reported by a [`BreakEvent`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BreakableConstraint {
+    WeldJoint,
+    PivotJoint,
+    Spring,
+}
+
+/// Emitted when a joint or spring exceeds its break threshold, body indices are the ones at the time of the break.
+#[derive(Clone, Debug)]
+pub struct BreakEvent {
+    pub constraint: BreakableConstraint,
+    pub body_a: usize,
+    pub body_b: usize,
+    /// Force the constraint was carrying when it broke.
+    pub force: f32,
+    /// Simulation time of the break.
+    pub time: f32,
+}
 
 //const G: f64 = 6.674 * 0.00000000001;
 pub struct PhysicsSystem {
@@ -11,46 +47,88 @@ pub struct PhysicsSystem {
     pub polygons: Vec<Rigidbody>,
     pub(crate) weld_joints: Vec<WeldJoint>,
     pub(crate) pivot_joints: Vec<PivotJoint>,
+    pub(crate) prismatic_joints: Vec<PrismaticJoint>,
+    pub(crate) distance_joints: Vec<DistanceJoint>,
+    pub(crate) rope_joints: Vec<RopeJoint>,
+    pub(crate) wheel_joints: Vec<WheelJoint>,
+    pub(crate) pulley_joints: Vec<PulleyJoint>,
+    pub(crate) gear_joints: Vec<GearJoint>,
+    pub(crate) soft_bodies: Vec<SoftBody>,
+    pub(crate) force_fields: Vec<ForceField>,
+    pub(crate) fluid_regions: Vec<FluidRegion>,
+    pub(crate) particle_fluids: Vec<ParticleFluid>,
+    /// Joint dragging a body towards the mouse in the drag mode.
+    pub(crate) mouse_joint: Option<MouseJoint>,
     pub dt: f32,
     pub energy: Energy,
+    /// Energy and momentum over time, sampled while the simulation runs.
+    pub energy_history: EnergyHistory,
+    /// Bodies pinned for telemetry, recorded every physics update while the simulation runs.
+    pub telemetry: Vec<Telemetry>,
+    /// Every break so far, broken constraints are removed at the end of the frame.
+    pub break_events: Vec<BreakEvent>,
+    /// Simulated time, advanced by `dt` every physics update.
+    pub time: f32,
+    /// Predicted paths of the bodies, drawn when the prediction time is set.
+    pub(crate) trajectory_prediction: TrajectoryPrediction,
 }
 
 impl PhysicsSystem {
-    pub fn calculate_gravitational_energy(rigidbodys: &Vec<Rigidbody>, g: f32) -> f32{
+    /// Potential energy of the N-body gravity, the charges and the dipoles between every pair of bodies.
+    pub fn calculate_pairwise_energy(rigidbodys: &[Rigidbody], constants: &PairwiseConstants) -> f32 {
         let mut potential = 0.0;
+        if constants.gravitational == 0.0 && constants.coulomb == 0.0 && constants.magnetic == 0.0 {
+            return potential;
+        }
         for i in 0..rigidbodys.len() {
             for j in (i + 1)..rigidbodys.len() {
-                let r = rigidbodys[j].center - rigidbodys[i].center;
-                let distance = r.length();
-                potential -= g * rigidbodys[i].mass * rigidbodys[j].mass / distance * rigidbodys[i].gravity_multiplier * rigidbodys[j].gravity_multiplier;
+                potential += constants.energy(&rigidbodys[i], &rigidbodys[j]);
             }
         }
         potential
     }
 
+    /// Energy stored in the suspension springs of wheel joints and in the mouse joint.
+    pub fn calculate_constraint_energy(&self) -> f64 {
+        let mut energy = 0.0;
+        for wheel_joint in &self.wheel_joints {
+            energy += wheel_joint.potential_energy(&self.polygons) as f64;
+        }
+        if let Some(mouse_joint) = &self.mouse_joint {
+            energy += mouse_joint.potential_energy(&self.polygons) as f64;
+        }
+        energy
+    }
+
+    /// Blasts the bodies around `center` with `impulse` spread over every direction, bodies shield the ones
+    /// behind them. Returns the indices of the pushed bodies, see [`Explosion`] for more control.
+    pub fn explode(&mut self, center: Vec2, radius: f32, impulse: f32, falloff: Falloff) -> Vec<usize> {
+        self.detonate(&Explosion::new(center, radius, impulse, falloff))
+    }
+
+    pub fn detonate(&mut self, explosion: &Explosion) -> Vec<usize> {
+        explosion.apply(&mut self.polygons)
+    }
+
+    /// Steps only the N-body gravity between the bodies with the Dormand-Prince solver. The regular update
+    /// already applies it with the other forces of [`PairwiseConstants`].
     pub fn gravity_step(&mut self, g: f32){
+        let constants = PairwiseConstants { gravitational: g, ..Default::default() };
         let snapshot = self.polygons.clone();
         let mut next_bodies: Vec<Rigidbody> = Vec::with_capacity(self.polygons.len());
 
         for i in 0..self.polygons.len() {
             let p = &self.polygons[i];
 
-            let compute_accel = |dt_offset: f32, my_pos: Vec2, _my_vel: Vec2| {
-                let mut accel = Vec2::ZERO;
+            let compute_force = |dt_offset: f32, my_pos: Vec2, _my_vel: Vec2| {
+                let mut force = Vec2::ZERO;
                 for (j, other) in snapshot.iter().enumerate() {
                     if i == j {continue; }
-
-                    let other_pos_at_t = other.center + other.velocity * dt_offset;
-
-                    let diff = other_pos_at_t - my_pos;
-                    let dist_sq = diff.length_squared() + 1e-14;
-                    accel = accel + diff * (g * other.gravity_multiplier * other.mass / (dist_sq * dist_sq.sqrt()));
+                    force += constants.force(p, my_pos, other, other.center + other.velocity * dt_offset);
                 }
-                accel
+                force
             };
-            // Inside your loop
-            let (new_pos, new_vel) = dormand_prince_step(0.0, p.center, p.velocity, self.dt, p.mass, &compute_accel);
-            //self.dt = suggested_dt; // Update global simulation speed based on need
+            let (new_pos, new_vel) = dormand_prince_step(0.0, p.center, p.velocity, self.dt, p.mass, &compute_force);
             let mut p1 = p.clone();
             p1.move_to(new_pos);
             p1.velocity = new_vel;
@@ -68,28 +146,98 @@ impl PhysicsSystem {
             g = Vec2 { x: 0.0, y: 0.0 };
         }
         for spring in &mut self.springs {
+            let was_broken = spring.broken;
             spring.apply(self.dt, &mut self.polygons);
+            if spring.broken && !was_broken {
+                self.break_events.push(BreakEvent {
+                    constraint: BreakableConstraint::Spring,
+                    body_a: spring.body_a,
+                    body_b: spring.body_b,
+                    force: spring.force,
+                    time: self.time,
+                });
+            }
+        }
+        for soft_body in &self.soft_bodies {
+            soft_body.apply_pressure(&mut self.polygons, self.dt);
+        }
+        for fluid_region in &self.fluid_regions {
+            fluid_region.apply_buoyancy(&mut self.polygons, g, self.dt);
+        }
+        for particle_fluid in &mut self.particle_fluids {
+            particle_fluid.step(&mut self.polygons, g, self.dt);
         }
 
         //self.get_gravity(parameters.gravitational_constant);
         //self.gravity_step(parameters.gravitational_constant);
+        let constants = PairwiseConstants::new(parameters);
         let snapshot = self.polygons.clone();
         let mut next_bodies: Vec<Rigidbody> = Vec::with_capacity(self.polygons.len());
         for i in 0..self.polygons.len() {
-            self.polygons[i].update_rigidbody(&snapshot, &mut next_bodies, i, g, parameters.gravitational_constant, self.dt);
+            self.polygons[i].update_rigidbody(&snapshot, &mut next_bodies, i, g, &constants, &self.force_fields, self.dt);
         }
         self.polygons = next_bodies;
         for weld_joint in &mut self.weld_joints {
+            let was_broken = weld_joint.broken;
             for _ in 0..1 {
                 weld_joint.solve_velocity_constraints(&mut self.polygons, self.dt);
             }
+            if weld_joint.broken && !was_broken {
+                self.break_events.push(BreakEvent {
+                    constraint: BreakableConstraint::WeldJoint,
+                    body_a: weld_joint.body_a,
+                    body_b: weld_joint.body_b,
+                    force: weld_joint.reaction_force.length(),
+                    time: self.time,
+                });
+            }
         }
 
         for pivot_joint in &mut self.pivot_joints {
+            let was_broken = pivot_joint.broken;
             for _ in 0..1 {
                 pivot_joint.solve_velocity_constraints(&mut self.polygons, self.dt);
             }
+            if pivot_joint.broken && !was_broken {
+                self.break_events.push(BreakEvent {
+                    constraint: BreakableConstraint::PivotJoint,
+                    body_a: pivot_joint.body_a,
+                    body_b: pivot_joint.body_b,
+                    force: pivot_joint.reaction_force.length(),
+                    time: self.time,
+                });
+            }
+        }
+
+        for prismatic_joint in &mut self.prismatic_joints {
+            prismatic_joint.solve_velocity_constraints(&mut self.polygons, self.dt);
+        }
+
+        for distance_joint in &mut self.distance_joints {
+            distance_joint.solve_velocity_constraints(&mut self.polygons, self.dt);
+        }
+
+        for rope_joint in &mut self.rope_joints {
+            rope_joint.solve_velocity_constraints(&mut self.polygons, self.dt);
         }
 
+        for wheel_joint in &mut self.wheel_joints {
+            wheel_joint.solve_velocity_constraints(&mut self.polygons, self.dt);
+        }
+
+        for pulley_joint in &mut self.pulley_joints {
+            pulley_joint.solve_velocity_constraints(&mut self.polygons, self.dt);
+        }
+
+        for gear_joint in &self.gear_joints {
+            gear_joint.solve_velocity_constraints(&self.pivot_joints, &self.prismatic_joints, &mut self.polygons, self.dt);
+        }
+
+        if let Some(mouse_joint) = &self.mouse_joint {
+            mouse_joint.solve_velocity_constraints(&mut self.polygons, self.dt);
+        }
+
+        self.time += self.dt;
+
     }
 }
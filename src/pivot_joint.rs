@@ -1,20 +1,35 @@
 use glam::{Mat2, Vec2};
 use crate::Rigidbody;
-use crate::utility::rotate;
 
 /// 2D Ball-and-Socket (pivot/pin) joint: constrains anchors to coincide, allows free rotation.
 #[derive(Clone)]
 pub struct PivotJoint {
-    local_anchor_a: Vec2,
-    local_anchor_b: Vec2,
+    pub(crate) local_anchor_a: Vec2,
+    pub(crate) local_anchor_b: Vec2,
     pub(crate) body_a: usize,
     pub(crate) body_b: usize,
-    start_angle: f32,
-    pub a_index: usize,
-    pub b_index: usize,
+    /// Relative angle `b.angle - a.angle` at creation, joint angles are measured from it.
+    pub(crate) reference_angle: f32,
 
     /// Baumgarte stabilization factor for positional drift (small: 0.01..0.2)
     pub beta: f32,
+
+    /// Drives the relative angular velocity towards `motor_speed` using at most `max_motor_torque`.
+    pub enable_motor: bool,
+    pub motor_speed: f32,
+    pub max_motor_torque: f32,
+
+    /// Keeps the joint angle between `lower_angle` and `upper_angle` (radians).
+    pub enable_limit: bool,
+    pub lower_angle: f32,
+    pub upper_angle: f32,
+
+    /// Largest force the pin holds before it breaks, unbreakable when `None`.
+    pub break_force: Option<f32>,
+    /// Constraint force body b received at the anchor during the last solve.
+    pub reaction_force: Vec2,
+    /// Set once `break_force` was exceeded, a broken joint no longer acts on the bodies.
+    pub broken: bool,
 }
 
 impl PivotJoint {
@@ -31,25 +46,96 @@ impl PivotJoint {
             b = &mut right[0];
         }
         a.connected_anchors.push(body_b);
-        let a_index = a.connected_anchors.len() - 1;
         b.connected_anchors.push(body_a);
-        let b_index = b.connected_anchors.len() - 1;
         
         Self {
             local_anchor_a,
             local_anchor_b,
             body_a,
             body_b,
-            a_index,
-            b_index,
-            start_angle: a.angle,
+            reference_angle: b.angle - a.angle,
             beta: 0.12,
+            enable_motor: false,
+            motor_speed: 0.0,
+            max_motor_torque: 0.0,
+            enable_limit: false,
+            lower_angle: 0.0,
+            upper_angle: 0.0,
+            break_force: None,
+            reaction_force: Vec2::ZERO,
+            broken: false,
+        }
+    }
+
+    pub fn set_break_force(&mut self, break_force: f32) -> &mut Self {
+        self.break_force = Some(break_force);
+        self
+    }
+
+    pub fn set_motor(&mut self, motor_speed: f32, max_motor_torque: f32) -> &mut Self {
+        self.enable_motor = true;
+        self.motor_speed = motor_speed;
+        self.max_motor_torque = max_motor_torque;
+        self
+    }
+
+    pub fn set_limits(&mut self, lower_angle: f32, upper_angle: f32) -> &mut Self {
+        self.enable_limit = true;
+        self.lower_angle = lower_angle.min(upper_angle);
+        self.upper_angle = upper_angle.max(lower_angle);
+        self
+    }
+
+    /// Rotation of body b relative to body a since the joint was created.
+    pub fn joint_angle(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        rigidbodys[self.body_b].angle - rigidbodys[self.body_a].angle - self.reference_angle
+    }
+
+    /// Angular impulse `impulse` applied to b and its reaction to a.
+    fn apply_angular_impulse(a: &mut Rigidbody, b: &mut Rigidbody, impulse: f32) {
+        a.angular_velocity -= impulse / a.moment_of_inertia;
+        b.angular_velocity += impulse / b.moment_of_inertia;
+    }
+
+    /// Motor and limit impulses, solved before the point constraint so it can correct what they add.
+    fn solve_angular_constraints(&self, a: &mut Rigidbody, b: &mut Rigidbody, dt: f32) {
+        let k = 1.0 / a.moment_of_inertia + 1.0 / b.moment_of_inertia;
+        if k == 0.0 {
+            return;
+        }
+
+        if self.enable_motor {
+            let relative_speed = b.angular_velocity - a.angular_velocity;
+            let max_impulse = self.max_motor_torque * dt;
+            let impulse = (-(relative_speed - self.motor_speed) / k).clamp(-max_impulse, max_impulse);
+            Self::apply_angular_impulse(a, b, impulse);
+        }
+
+        if self.enable_limit {
+            let angle = b.angle - a.angle - self.reference_angle;
+            // Speculative while inside the range, Baumgarte once the limit has been passed
+            let bias = |separation: f32| {
+                if separation > 0.0 { separation / dt } else { self.beta * separation / dt }
+            };
+
+            let lower_separation = angle - self.lower_angle;
+            let relative_speed = b.angular_velocity - a.angular_velocity;
+            let impulse = (-(relative_speed + bias(lower_separation)) / k).max(0.0);
+            Self::apply_angular_impulse(a, b, impulse);
+
+            let upper_separation = self.upper_angle - angle;
+            let relative_speed = b.angular_velocity - a.angular_velocity;
+            let impulse = (-(-relative_speed + bias(upper_separation)) / k).max(0.0);
+            Self::apply_angular_impulse(a, b, -impulse);
         }
     }
 
     /// Solve velocity-level linear constraints (2D block solve).
     /// Call multiple times per physics step inside the solver iteration loop.
-    pub fn solve_velocity_constraints(&self, rigidbodys: &mut Vec<Rigidbody>, dt: f32) {
+    pub fn solve_velocity_constraints(&mut self, rigidbodys: &mut Vec<Rigidbody>, dt: f32) {
+        if self.broken {
+            return;
+        }
         let a;
         let b;
         if self.body_a > self.body_b {
@@ -61,6 +147,8 @@ impl PivotJoint {
             a = &mut left[self.body_a];
             b = &mut right[0];
         }
+        self.solve_angular_constraints(a, b, dt);
+
         // world-space anchor offsets
         let ra = a.rotation_matrix() * self.local_anchor_a;
         let rb = b.rotation_matrix() * self.local_anchor_b;
@@ -134,9 +222,14 @@ impl PivotJoint {
 
         b.velocity += impulse * inv_mb;
         b.angular_velocity += inv_ib * rb.perp_dot(impulse);
+
+        self.reaction_force = impulse / dt;
+        if let Some(break_force) = self.break_force && self.reaction_force.length() > break_force {
+            self.broken = true;
+        }
     }
 
-    pub fn get_anchor_world_position(&self, rigidbodys: &Vec<Rigidbody>) -> Vec2 {
-        rigidbodys[self.body_a].center + rotate(self.local_anchor_a, Vec2::ZERO, rigidbodys[self.body_a].angle - self.start_angle)
+    pub fn get_anchor_world_position(&self, rigidbodys: &[Rigidbody]) -> Vec2 {
+        rigidbodys[self.body_a].transform_point(self.local_anchor_a)
     }
 }
\ No newline at end of file
@@ -0,0 +1,213 @@
+use glam::{Mat2, Vec2};
+use crate::distance_joint::apply_impulse;
+use crate::{ColorRGBA, Rigidbody};
+
+/// 2D slider joint: body b translates along an axis fixed in body a, relative rotation is locked.
+#[derive(Clone)]
+pub struct PrismaticJoint {
+    local_anchor_a: Vec2,
+    local_anchor_b: Vec2,
+    /// Unit slide direction in body a's local frame.
+    local_axis_a: Vec2,
+    pub(crate) body_a: usize,
+    pub(crate) body_b: usize,
+    reference_angle: f32,
+
+    /// Baumgarte stabilization factor for positional drift (small: 0.01..0.2)
+    pub beta: f32,
+
+    /// Drives the relative speed along the axis towards `motor_speed` using at most `max_motor_force`.
+    pub enable_motor: bool,
+    pub motor_speed: f32,
+    pub max_motor_force: f32,
+
+    /// Keeps the translation along the axis between `lower_translation` and `upper_translation`.
+    pub enable_limit: bool,
+    pub lower_translation: f32,
+    pub upper_translation: f32,
+}
+
+impl PrismaticJoint {
+    pub fn new(local_anchor_a: Vec2, local_anchor_b: Vec2, local_axis_a: Vec2, rigidbodys: &mut [Rigidbody], body_a: usize, body_b: usize) -> Self {
+        let a;
+        let b;
+        if body_a > body_b {
+            let (left, right) = rigidbodys.split_at_mut(body_a);
+            b = &mut left[body_b];
+            a = &mut right[0];
+        } else {
+            let (left, right) = rigidbodys.split_at_mut(body_b);
+            a = &mut left[body_a];
+            b = &mut right[0];
+        }
+        a.connected_anchors.push(body_b);
+        b.connected_anchors.push(body_a);
+
+        Self {
+            local_anchor_a,
+            local_anchor_b,
+            local_axis_a: local_axis_a.normalize_or(Vec2::X),
+            body_a,
+            body_b,
+            reference_angle: b.angle - a.angle,
+            beta: 0.1,
+            enable_motor: false,
+            motor_speed: 0.0,
+            max_motor_force: 0.0,
+            enable_limit: false,
+            lower_translation: 0.0,
+            upper_translation: 0.0,
+        }
+    }
+
+    pub fn set_motor(&mut self, motor_speed: f32, max_motor_force: f32) -> &mut Self {
+        self.enable_motor = true;
+        self.motor_speed = motor_speed;
+        self.max_motor_force = max_motor_force;
+        self
+    }
+
+    pub fn set_limits(&mut self, lower_translation: f32, upper_translation: f32) -> &mut Self {
+        self.enable_limit = true;
+        self.lower_translation = lower_translation.min(upper_translation);
+        self.upper_translation = upper_translation.max(lower_translation);
+        self
+    }
+
+    /// Distance of anchor b from anchor a measured along the axis.
+    pub fn joint_translation(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        let a = &rigidbodys[self.body_a];
+        let b = &rigidbodys[self.body_b];
+        let d = b.transform_point(self.local_anchor_b) - a.transform_point(self.local_anchor_a);
+        d.dot(a.rotation_matrix() * self.local_axis_a)
+    }
+
+    /// World space slide direction.
+    pub fn get_axis(&self, rigidbodys: &[Rigidbody]) -> Vec2 {
+        rigidbodys[self.body_a].rotation_matrix() * self.local_axis_a
+    }
+
+    /// World axis and the angular lever arms of body a and b for the translation's rate of change.
+    pub(crate) fn axial_jacobian(&self, rigidbodys: &[Rigidbody]) -> (Vec2, f32, f32) {
+        let a = &rigidbodys[self.body_a];
+        let b = &rigidbodys[self.body_b];
+        let ra = a.rotation_matrix() * self.local_anchor_a;
+        let rb = b.rotation_matrix() * self.local_anchor_b;
+        let d = (b.center + rb) - (a.center + ra);
+        let axis = a.rotation_matrix() * self.local_axis_a;
+        (axis, (d + ra).perp_dot(axis), rb.perp_dot(axis))
+    }
+
+    pub fn solve_velocity_constraints(&self, rigidbodys: &mut [Rigidbody], dt: f32) {
+        let a;
+        let b;
+        if self.body_a > self.body_b {
+            let (left, right) = rigidbodys.split_at_mut(self.body_a);
+            b = &mut left[self.body_b];
+            a = &mut right[0];
+        } else {
+            let (left, right) = rigidbodys.split_at_mut(self.body_b);
+            a = &mut left[self.body_a];
+            b = &mut right[0];
+        }
+
+        let ra = a.rotation_matrix() * self.local_anchor_a;
+        let rb = b.rotation_matrix() * self.local_anchor_b;
+        let d = (b.center + rb) - (a.center + ra);
+        let axis = a.rotation_matrix() * self.local_axis_a;
+
+        let inv_ma = 1.0 / a.mass;
+        let inv_mb = 1.0 / b.mass;
+        let inv_ia = 1.0 / a.moment_of_inertia;
+        let inv_ib = 1.0 / b.moment_of_inertia;
+
+        // Axial lever arms, body a's arm reaches to anchor b since the axis is fixed in a
+        let a1 = (d + ra).perp_dot(axis);
+        let a2 = rb.perp_dot(axis);
+        let axial_mass = inv_ma + inv_mb + inv_ia * a1 * a1 + inv_ib * a2 * a2;
+        let axial_speed = |a: &Rigidbody, b: &Rigidbody| {
+            axis.dot(b.velocity - a.velocity) + a2 * b.angular_velocity - a1 * a.angular_velocity
+        };
+
+        if self.enable_motor && axial_mass > 0.0 {
+            let max_impulse = self.max_motor_force * dt;
+            let impulse = (-(axial_speed(a, b) - self.motor_speed) / axial_mass).clamp(-max_impulse, max_impulse);
+            apply_impulse(a, b, axis, a1, a2, impulse);
+        }
+
+        if self.enable_limit && axial_mass > 0.0 {
+            let translation = d.dot(axis);
+            // Speculative while inside the range, Baumgarte once the limit has been passed
+            let bias = |separation: f32| {
+                if separation > 0.0 { separation / dt } else { self.beta * separation / dt }
+            };
+
+            let lower_separation = translation - self.lower_translation;
+            let impulse = (-(axial_speed(a, b) + bias(lower_separation)) / axial_mass).max(0.0);
+            apply_impulse(a, b, axis, a1, a2, impulse);
+
+            let upper_separation = self.upper_translation - translation;
+            let impulse = (-(-axial_speed(a, b) + bias(upper_separation)) / axial_mass).max(0.0);
+            apply_impulse(a, b, axis, a1, a2, -impulse);
+        }
+
+        // Perpendicular and angular constraints solved together as a 2x2 block
+        let perp = axis.perp();
+        let s1 = (d + ra).perp_dot(perp);
+        let s2 = rb.perp_dot(perp);
+
+        let k11 = inv_ma + inv_mb + inv_ia * s1 * s1 + inv_ib * s2 * s2;
+        let k12 = inv_ia * s1 + inv_ib * s2;
+        let mut k22 = inv_ia + inv_ib;
+        if k22 == 0.0 {
+            k22 = 1.0;
+        }
+        let k = Mat2::from_cols(Vec2::new(k11, k12), Vec2::new(k12, k22));
+
+        let c_dot = Vec2::new(
+            perp.dot(b.velocity - a.velocity) + s2 * b.angular_velocity - s1 * a.angular_velocity,
+            b.angular_velocity - a.angular_velocity,
+        );
+        let c = Vec2::new(perp.dot(d), b.angle - a.angle - self.reference_angle);
+        let bias = (self.beta / dt) * c;
+
+        const EPS: f32 = 1e-6;
+        if k.determinant().abs() <= EPS {
+            return;
+        }
+        let lambda = k.inverse() * -(c_dot + bias);
+
+        let impulse = perp * lambda.x;
+        a.velocity -= impulse * inv_ma;
+        a.angular_velocity -= inv_ia * (lambda.x * s1 + lambda.y);
+        b.velocity += impulse * inv_mb;
+        b.angular_velocity += inv_ib * (lambda.x * s2 + lambda.y);
+    }
+
+    pub fn get_anchor_world_position(&self, rigidbodys: &[Rigidbody]) -> Vec2 {
+        rigidbodys[self.body_a].transform_point(self.local_anchor_a)
+    }
+
+    /// Thin rectangle along the slide axis, spanning the limits when they are enabled.
+    pub fn create_axis(&self, rigidbodys: &[Rigidbody]) -> Rigidbody {
+        let anchor = rigidbodys[self.body_a].transform_point(self.local_anchor_a);
+        let axis = self.get_axis(rigidbodys);
+        let (start, end) = if self.enable_limit {
+            (self.lower_translation, self.upper_translation)
+        } else {
+            let translation = self.joint_translation(rigidbodys);
+            (translation.min(0.0) - 0.5, translation.max(0.0) + 0.5)
+        };
+        let mut line = Rigidbody::rectangle(
+            (end - start).max(0.01),
+            0.02,
+            anchor + axis * (start + end) * 0.5,
+            1.0,
+            0.0,
+            ColorRGBA::new(1.0, 1.0, 1.0, 0.5),
+        );
+        line.rotate(axis.to_angle());
+        line.collision = false;
+        line
+    }
+}
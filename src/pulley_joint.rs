@@ -0,0 +1,127 @@
+use glam::Vec2;
+use crate::distance_joint::create_link;
+use crate::{ColorRGBA, Rigidbody};
+
+/// Ideal pulley: two bodies hang from fixed ground anchors and `length_a + ratio * length_b` stays constant.
+#[derive(Clone)]
+pub struct PulleyJoint {
+    /// World space points the two sides of the rope run over.
+    pub ground_anchor_a: Vec2,
+    pub ground_anchor_b: Vec2,
+    local_anchor_a: Vec2,
+    local_anchor_b: Vec2,
+    pub(crate) body_a: usize,
+    pub(crate) body_b: usize,
+
+    /// Block and tackle ratio, side b moves `1 / ratio` as far as side a.
+    pub ratio: f32,
+    /// Total rope length `length_a + ratio * length_b`.
+    pub constant: f32,
+
+    /// Baumgarte stabilization factor for positional drift (small: 0.01..0.2)
+    pub beta: f32,
+}
+
+impl PulleyJoint {
+    /// The rope length is taken from the current anchor positions.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ground_anchor_a: Vec2,
+        ground_anchor_b: Vec2,
+        local_anchor_a: Vec2,
+        local_anchor_b: Vec2,
+        ratio: f32,
+        rigidbodys: &[Rigidbody],
+        body_a: usize,
+        body_b: usize,
+    ) -> Self {
+        let ratio = ratio.max(f32::EPSILON);
+        let length_a = rigidbodys[body_a].transform_point(local_anchor_a).distance(ground_anchor_a);
+        let length_b = rigidbodys[body_b].transform_point(local_anchor_b).distance(ground_anchor_b);
+        Self {
+            ground_anchor_a,
+            ground_anchor_b,
+            local_anchor_a,
+            local_anchor_b,
+            body_a,
+            body_b,
+            ratio,
+            constant: length_a + ratio * length_b,
+            beta: 0.1,
+        }
+    }
+
+    /// Current lengths of the two sides of the rope.
+    pub fn lengths(&self, rigidbodys: &[Rigidbody]) -> (f32, f32) {
+        let (anchor_a, anchor_b) = self.get_anchor_world_positions(rigidbodys);
+        (anchor_a.distance(self.ground_anchor_a), anchor_b.distance(self.ground_anchor_b))
+    }
+
+    pub fn solve_velocity_constraints(&self, rigidbodys: &mut [Rigidbody], dt: f32) {
+        let a;
+        let b;
+        if self.body_a > self.body_b {
+            let (left, right) = rigidbodys.split_at_mut(self.body_a);
+            b = &mut left[self.body_b];
+            a = &mut right[0];
+        } else {
+            let (left, right) = rigidbodys.split_at_mut(self.body_b);
+            a = &mut left[self.body_a];
+            b = &mut right[0];
+        }
+
+        let ra = a.rotation_matrix() * self.local_anchor_a;
+        let rb = b.rotation_matrix() * self.local_anchor_b;
+        let side_a = a.center + ra - self.ground_anchor_a;
+        let side_b = b.center + rb - self.ground_anchor_b;
+        let length_a = side_a.length();
+        let length_b = side_b.length();
+        // A side running straight through its ground anchor has no direction
+        if length_a <= 1e-6 || length_b <= 1e-6 {
+            return;
+        }
+        let ua = side_a / length_a;
+        let ub = side_b / length_b;
+
+        let arm_a = ra.perp_dot(ua);
+        let arm_b = rb.perp_dot(ub);
+        let mass_a = 1.0 / a.mass + arm_a * arm_a / a.moment_of_inertia;
+        let mass_b = 1.0 / b.mass + arm_b * arm_b / b.moment_of_inertia;
+        let k = mass_a + self.ratio * self.ratio * mass_b;
+        if k <= 0.0 {
+            return;
+        }
+
+        let speed_a = ua.dot(a.velocity) + arm_a * a.angular_velocity;
+        let speed_b = ub.dot(b.velocity) + arm_b * b.angular_velocity;
+        let c_dot = -speed_a - self.ratio * speed_b;
+        let c = self.constant - length_a - self.ratio * length_b;
+        let impulse = -(c_dot + (self.beta / dt) * c) / k;
+
+        // The rope pulls both bodies towards their ground anchors
+        let impulse_a = -impulse * ua;
+        let impulse_b = -self.ratio * impulse * ub;
+        a.velocity += impulse_a / a.mass;
+        a.angular_velocity += ra.perp_dot(impulse_a) / a.moment_of_inertia;
+        b.velocity += impulse_b / b.mass;
+        b.angular_velocity += rb.perp_dot(impulse_b) / b.moment_of_inertia;
+    }
+
+    pub fn get_anchor_world_positions(&self, rigidbodys: &[Rigidbody]) -> (Vec2, Vec2) {
+        (
+            rigidbodys[self.body_a].transform_point(self.local_anchor_a),
+            rigidbodys[self.body_b].transform_point(self.local_anchor_b),
+        )
+    }
+
+    /// Both sides of the rope plus the segment running over the ground anchors.
+    pub fn create_links(&self, rigidbodys: &[Rigidbody]) -> [Rigidbody; 3] {
+        let (anchor_a, anchor_b) = self.get_anchor_world_positions(rigidbodys);
+        let color = ColorRGBA::new(0.8, 0.6, 0.4, 1.0);
+        [
+            create_link(self.ground_anchor_a, anchor_a, color),
+            create_link(self.ground_anchor_b, anchor_b, color),
+            create_link(self.ground_anchor_a, self.ground_anchor_b, color),
+        ]
+    }
+}
@@ -1,5 +1,5 @@
-use crate::spring::Spring;
-use crate::{ColorRGBA, PivotJoint, Rigidbody, WeldJoint, World};
+use crate::physics::PhysicsSystem;
+use crate::{ColorRGBA, PairwiseConstants, Parameters, World};
 use egui_wgpu::wgpu;
 use std::iter;
 use std::sync::Arc;
@@ -64,12 +64,9 @@ impl Vertex {
 }
 
 impl World {
-    pub fn get_vertices_and_indices(
-        polygons: &Vec<Rigidbody>,
-        springs: &Vec<Spring>,
-        weld_joints: &Vec<WeldJoint>,
-        pivot_joints: &Vec<PivotJoint>,
-    ) -> (Vec<Vertex>, Vec<u32>) {
+    pub fn get_vertices_and_indices(physics: &PhysicsSystem, parameters: &Parameters) -> (Vec<Vertex>, Vec<u32>) {
+        let polygons = &physics.polygons;
+        let springs = &physics.springs;
         let mut vertices: Vec<Vertex> = Vec::with_capacity(
             polygons.iter().map(|p| p.vertices.len() + 1).sum::<usize>()
                 + springs
@@ -96,8 +93,51 @@ impl World {
             start_index += verts.len() as u32 + 1;
         };
 
+        // Fields and trails first so they are drawn behind the bodies
+        for force_field in &physics.force_fields {
+            if let Some((region, region_indices)) = force_field.region_mesh() {
+                process(&region, force_field.color, region[0], &region_indices);
+            }
+            if let Some(marker) = force_field.create_marker() {
+                let (outline, outline_indices) = marker.tessellate();
+                process(&outline, marker.color, marker.center, &outline_indices);
+            }
+        }
+        for telemetry in &physics.telemetry {
+            for segment in telemetry.create_trail() {
+                let (outline, outline_indices) = segment.tessellate();
+                process(&outline, segment.color, segment.center, &outline_indices);
+            }
+        }
+        for segment in &physics.trajectory_prediction.lines {
+            let (outline, outline_indices) = segment.tessellate();
+            process(&outline, segment.color, segment.center, &outline_indices);
+        }
+        if parameters.field_lines {
+            for segment in PairwiseConstants::new(parameters).field_lines(polygons) {
+                let (outline, outline_indices) = segment.tessellate();
+                process(&outline, segment.color, segment.center, &outline_indices);
+            }
+        }
+
         for polygon in polygons {
-            process(&polygon.vertices, polygon.color, polygon.center, &polygon.indices);
+            let (mesh, mesh_indices) = polygon.tessellate();
+            process(&mesh, polygon.color, polygon.center, &mesh_indices);
+        }
+
+        for particle_fluid in &physics.particle_fluids {
+            if !particle_fluid.particles.is_empty() {
+                let (sprites, sprite_indices) = particle_fluid.sprites();
+                process(&sprites, particle_fluid.color, sprites[0], &sprite_indices);
+            }
+        }
+
+        // Drawn over the bodies so submerged parts look tinted
+        for fluid_region in &physics.fluid_regions {
+            if fluid_region.vertices.len() >= 3 {
+                let center = fluid_region.vertices[0];
+                process(&fluid_region.vertices, fluid_region.color, center, &fluid_region.triangulate());
+            }
         }
 
         for spring in springs {
@@ -109,25 +149,80 @@ impl World {
             );
         }
         
-        for weld_joint in weld_joints {
+        for weld_joint in &physics.weld_joints {
             let mut polygon = BodyBuilder::create_joint();
             let position = weld_joint.get_anchor_world_position(&polygons);
             polygon.move_to(position);
-            process(&polygon.vertices, polygon.color, polygon.center, &polygon.indices);
+            let (outline, outline_indices) = polygon.tessellate();
+            process(&outline, polygon.color, polygon.center, &outline_indices);
         }
 
-        for pivot_joint in pivot_joints {
+        for pivot_joint in &physics.pivot_joints {
             let mut polygon = BodyBuilder::create_joint();
             let position = pivot_joint.get_anchor_world_position(&polygons);
             polygon.move_to(position);
-            process(&polygon.vertices, polygon.color, polygon.center, &polygon.indices);
+            let (outline, outline_indices) = polygon.tessellate();
+            process(&outline, polygon.color, polygon.center, &outline_indices);
+        }
+
+        for prismatic_joint in &physics.prismatic_joints {
+            let axis = prismatic_joint.create_axis(polygons);
+            let (outline, outline_indices) = axis.tessellate();
+            process(&outline, axis.color, axis.center, &outline_indices);
+
+            let mut polygon = BodyBuilder::create_joint();
+            polygon.move_to(prismatic_joint.get_anchor_world_position(polygons));
+            let (outline, outline_indices) = polygon.tessellate();
+            process(&outline, polygon.color, polygon.center, &outline_indices);
+        }
+
+        for distance_joint in &physics.distance_joints {
+            let link = distance_joint.create_link(polygons);
+            let (outline, outline_indices) = link.tessellate();
+            process(&outline, link.color, link.center, &outline_indices);
+        }
+
+        for rope_joint in &physics.rope_joints {
+            let link = rope_joint.create_link(polygons);
+            let (outline, outline_indices) = link.tessellate();
+            process(&outline, link.color, link.center, &outline_indices);
+        }
+
+        for wheel_joint in &physics.wheel_joints {
+            let axis = wheel_joint.create_axis(polygons);
+            let (outline, outline_indices) = axis.tessellate();
+            process(&outline, axis.color, axis.center, &outline_indices);
+
+            let mut polygon = BodyBuilder::create_joint();
+            polygon.move_to(wheel_joint.get_anchor_world_position(polygons));
+            let (outline, outline_indices) = polygon.tessellate();
+            process(&outline, polygon.color, polygon.center, &outline_indices);
+        }
+
+        for pulley_joint in &physics.pulley_joints {
+            for link in pulley_joint.create_links(polygons) {
+                let (outline, outline_indices) = link.tessellate();
+                process(&outline, link.color, link.center, &outline_indices);
+            }
+        }
+
+        for gear_joint in &physics.gear_joints {
+            let link = gear_joint.create_link(&physics.pivot_joints, &physics.prismatic_joints, polygons);
+            let (outline, outline_indices) = link.tessellate();
+            process(&outline, link.color, link.center, &outline_indices);
+        }
+
+        if let Some(mouse_joint) = &physics.mouse_joint {
+            let link = mouse_joint.create_link(polygons);
+            let (outline, outline_indices) = link.tessellate();
+            process(&outline, link.color, link.center, &outline_indices);
         }
 
         (vertices, indices)
     }
 
     pub(crate) fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
-        let (vertices, indices) = &Self::get_vertices_and_indices(&self.physics.polygons, &self.physics.springs, &self.physics.weld_joints, &self.physics.pivot_joints);
+        let (vertices, indices) = &Self::get_vertices_and_indices(&self.physics, &self.parameters);
         self.ui.window_dimensions.x = self.render.config.width as f32;
         self.ui.window_dimensions.y = self.render.config.height as f32;
 
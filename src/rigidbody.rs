@@ -1,10 +1,54 @@
+use std::f32::consts::PI;
 use glam::{Mat2, Vec2};
+use crate::collision_detection::{closest_point_on_polygon, closest_point_on_segment, point_in_convex, Collider};
+use crate::fixture::{shape_properties, Fixture};
+use crate::force_field::ForceField;
+use crate::material::Material;
 use crate::color::ColorRGBA;
 use crate::ode_solver::{rk4_angular_step, dormand_prince_step};
+use crate::orbit::orbit_velocity;
+use crate::pairwise::PairwiseConstants;
+use crate::triangulation::{convex_decomposition, signed_area, triangulate};
+
+/// Number of segments used when tessellating round shapes for rendering.
+const CIRCLE_SEGMENTS: u32 = 32;
+
+/// Collision geometry of a rigidbody.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Shape {
+    /// Convex polygon described by `vertices`.
+    #[default]
+    Polygon,
+    /// Exact circle around `center`, `vertices` is empty.
+    Circle { radius: f32 },
+    /// Segment between `vertices[0]` and `vertices[1]` swept by `radius`.
+    Capsule { radius: f32 },
+}
+
+/// Point of a body an anchor is moved to by [`Rigidbody::snap_anchor`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AnchorSnap {
+    /// Anchor stays where it was placed, only clamped onto the body.
+    #[default]
+    Free,
+    /// Nearest polygon vertex.
+    Vertex,
+    /// Nearest midpoint of a polygon edge.
+    EdgeMidpoint,
+    /// Center of mass.
+    Centroid,
+    /// Nearest point on the outline.
+    Outline,
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct Rigidbody {
     pub center: Vec2,
+    pub shape: Shape,
+    /// World space vertices, cached from `local_vertices` whenever the body moves.
     pub vertices: Vec<Vec2>,
+    /// Shape relative to the center of mass at zero rotation.
+    pub local_vertices: Vec<Vec2>,
     pub color: ColorRGBA,
     pub indices: Vec<u32>,
     pub radius: f32,
@@ -21,6 +65,15 @@ pub struct Rigidbody {
     pub eternal: bool,
     pub connected_anchors: Vec<usize>,
     pub is_static: bool,
+    pub friction: f32,
+    /// Net impulse the contacts applied to the body during the last collision resolution.
+    pub contact_impulse: Vec2,
+    /// Electric charge, like charges repel through the Coulomb force.
+    pub charge: f32,
+    /// Strength of the magnetic dipole, pointing along the body's local x axis.
+    pub magnetic_moment: f32,
+    /// Convex collision pieces of compound and concave bodies, empty when the body is its own collider.
+    pub fixtures: Vec<Fixture>,
 }
 impl Rigidbody {
     pub fn rectangle(
@@ -59,7 +112,9 @@ impl Rigidbody {
             velocity: Vec2::ZERO,
             radius: 0.0,
             center: Vec2::ZERO,
+            shape: Shape::Polygon,
             vertices,
+            local_vertices: vec![],
             color,
             indices,
             restitution,
@@ -70,6 +125,11 @@ impl Rigidbody {
             eternal: false,
             connected_anchors: vec![],
             is_static: false,
+            friction: 0.0,
+            contact_impulse: Vec2::ZERO,
+            charge: 0.0,
+            magnetic_moment: 0.0,
+            fixtures: vec![],
         };
         polygon.calculate_properties();
         polygon.center = pos;
@@ -110,7 +170,9 @@ impl Rigidbody {
             velocity: Vec2::ZERO,
             radius: 0.0,
             center: Vec2::ZERO,
+            shape: Shape::Polygon,
             vertices,
+            local_vertices: vec![],
             indices,
             restitution,
             torque: 0.0,
@@ -120,6 +182,11 @@ impl Rigidbody {
             eternal: false,
             connected_anchors: vec![],
             is_static: false,
+            friction: 0.0,
+            contact_impulse: Vec2::ZERO,
+            charge: 0.0,
+            magnetic_moment: 0.0,
+            fixtures: vec![],
         };
         polygon.calculate_properties();
         polygon
@@ -166,7 +233,9 @@ impl Rigidbody {
             color,
             radius,
             center: pos,
+            shape: Shape::Polygon,
             vertices,
+            local_vertices: vec![],
             indices,
             restitution,
             torque: 0.0,
@@ -176,11 +245,136 @@ impl Rigidbody {
             eternal: false,
             connected_anchors: vec![],
             is_static: false,
+            friction: 0.0,
+            contact_impulse: Vec2::ZERO,
+            charge: 0.0,
+            magnetic_moment: 0.0,
+            fixtures: vec![],
         };
         polygon.calculate_properties();
         polygon
     }
 
+    /// Arbitrary simple polygon, `points` are relative to the centroid which is placed at `pos`.
+    /// Rendered with an ear clipping triangulation and collided as a set of convex pieces.
+    pub fn concave(
+        points: Vec<Vec2>,
+        pos: Vec2,
+        mass: f32,
+        restitution: f32,
+        color: ColorRGBA,
+    ) -> Self {
+        let mut vertices = points;
+        if signed_area(&vertices) < 0.0 {
+            vertices.reverse();
+        }
+        let indices = triangulate(&vertices);
+        let density = mass / signed_area(&vertices);
+        let fixtures = convex_decomposition(&vertices, &indices)
+            .into_iter()
+            .map(|part| Fixture::polygon(part, Vec2::ZERO, density, restitution, 0.0))
+            .collect();
+
+        let mut polygon = Rigidbody {
+            mass,
+            color,
+            shape: Shape::Polygon,
+            vertices,
+            indices,
+            restitution,
+            collision: true,
+            gravity_multiplier: 1.0,
+            fixtures,
+            ..Default::default()
+        };
+        polygon.calculate_properties();
+        polygon.move_to(pos);
+        polygon
+    }
+
+    /// Single rigid body made of several fixtures, fixture offsets are relative to `pos`.
+    /// Mass is the sum of the fixture masses and the center of mass follows from their densities.
+    pub fn compound(fixtures: Vec<Fixture>, pos: Vec2, color: ColorRGBA) -> Self {
+        // Render mesh: every fixture outline fanned from its first vertex
+        let mut vertices: Vec<Vec2> = vec![];
+        let mut indices: Vec<u32> = vec![];
+        for fixture in &fixtures {
+            let start = vertices.len() as u32;
+            let fixture_outline = outline(fixture.shape, &fixture.vertices, fixture.center, 0.0);
+            for i in 1..fixture_outline.len().saturating_sub(1) as u32 {
+                indices.extend([start, start + i, start + i + 1]);
+            }
+            vertices.extend(fixture_outline);
+        }
+
+        let mut polygon = Rigidbody {
+            mass: fixtures.iter().map(|fixture| fixture.mass()).sum(),
+            restitution: fixtures.iter().map(|fixture| fixture.restitution).fold(0.0, f32::max),
+            friction: fixtures.iter().map(|fixture| fixture.friction).fold(0.0, f32::max),
+            color,
+            shape: Shape::Polygon,
+            vertices,
+            indices,
+            collision: true,
+            gravity_multiplier: 1.0,
+            fixtures,
+            ..Default::default()
+        };
+        polygon.calculate_properties();
+        polygon.translate(pos);
+        polygon
+    }
+
+    pub fn circle(
+        radius: f32,
+        pos: Vec2,
+        mass: f32,
+        restitution: f32,
+        color: ColorRGBA,
+    ) -> Self {
+        let mut circle = Rigidbody {
+            mass,
+            color,
+            radius,
+            center: pos,
+            shape: Shape::Circle { radius },
+            restitution,
+            collision: true,
+            gravity_multiplier: 1.0,
+            ..Default::default()
+        };
+        circle.calculate_properties();
+        circle
+    }
+
+    /// Capsule lying along the x axis, `length` is the distance between the two cap centers.
+    pub fn capsule(
+        length: f32,
+        radius: f32,
+        pos: Vec2,
+        mass: f32,
+        restitution: f32,
+        color: ColorRGBA,
+    ) -> Self {
+        let vertices: Vec<Vec2> = vec![
+            Vec2::new(-length / 2.0 + pos.x, pos.y),
+            Vec2::new(length / 2.0 + pos.x, pos.y),
+        ];
+        let mut capsule = Rigidbody {
+            mass,
+            color,
+            center: pos,
+            shape: Shape::Capsule { radius },
+            vertices,
+            restitution,
+            collision: true,
+            gravity_multiplier: 1.0,
+            ..Default::default()
+        };
+        capsule.calculate_properties();
+        capsule
+    }
+
     pub fn rotation_matrix(&self) -> Mat2 {
         let (s, c) = self.angle.sin_cos();
         Mat2::from_cols(Vec2::new(c, s), Vec2::new(-s, c))
@@ -190,8 +384,17 @@ impl Rigidbody {
         self.calculate_center_of_mass();
         self.calculate_radius();
         self.calculate_moment_of_inertia();
+        self.update_local_vertices();
     }
     pub fn calculate_radius(&mut self) {
+        if let Shape::Circle { radius } = self.shape {
+            self.radius = radius;
+            return;
+        }
+        let rounding = match self.shape {
+            Shape::Capsule { radius } => radius,
+            _ => 0.0,
+        };
         let mut max_radius = 0.0;
         for vertex in &self.vertices {
             let distance = vertex.distance(self.center);
@@ -199,10 +402,21 @@ impl Rigidbody {
                 max_radius = distance;
             }
         }
-        self.radius = max_radius;
+        self.radius = max_radius + rounding;
     }
 
     pub fn calculate_area(&mut self) {
+        if !self.fixtures.is_empty() {
+            self.area = self.fixtures.iter().map(|fixture| fixture.area()).sum();
+            return;
+        }
+        match self.shape {
+            Shape::Circle { .. } | Shape::Capsule { .. } => {
+                self.area = shape_properties(self.shape, &self.vertices, self.center).0;
+                return;
+            }
+            Shape::Polygon => {}
+        }
         let n = self.vertices.len();
         let mut area = 0.0;
 
@@ -218,6 +432,19 @@ impl Rigidbody {
     }
 
     pub fn calculate_center_of_mass(&mut self) {
+        if let Shape::Circle { .. } = self.shape {
+            return;
+        }
+        if !self.fixtures.is_empty() {
+            let mut mass = 0.0;
+            let mut weighted_center = Vec2::ZERO;
+            for fixture in &self.fixtures {
+                mass += fixture.mass();
+                weighted_center += fixture.center * fixture.mass();
+            }
+            self.center = weighted_center / mass;
+            return;
+        }
         let n = self.vertices.len();
         if n == 0 {
             self.center = Vec2::ZERO;
@@ -275,6 +502,26 @@ impl Rigidbody {
 
 
     pub fn calculate_moment_of_inertia(&mut self) {
+        match self.shape {
+            Shape::Circle { .. } | Shape::Capsule { .. } => {
+                let (area, _, second_moment) = shape_properties(self.shape, &self.vertices, self.center);
+                self.moment_of_inertia = self.mass * second_moment / area;
+                return;
+            }
+            Shape::Polygon => {}
+        }
+        if !self.fixtures.is_empty() {
+            // Parallel axis theorem: every fixture about its own centroid, shifted to the body centroid
+            let mut mass = 0.0;
+            let mut inertia = 0.0;
+            for fixture in &self.fixtures {
+                mass += fixture.mass();
+                inertia += fixture.moment_of_inertia() + fixture.mass() * fixture.center.distance_squared(self.center);
+            }
+            // Rescaled so editing the body mass keeps the fixture mass distribution
+            self.moment_of_inertia = inertia * (self.mass / mass);
+            return;
+        }
         let n = self.vertices.len();
 
         let mut inertia = 0.0;
@@ -298,66 +545,326 @@ impl Rigidbody {
     }
 
     pub fn translate(&mut self, pos: Vec2) -> &mut Self {
-        for vertex in &mut self.vertices {
-            *vertex += pos;
-        }
         self.center += pos;
+        self.update_world_vertices();
         self
     }
     
     pub fn move_to(&mut self, pos: Vec2) -> &mut Self {
-        let diff = pos - self.center;
-        for vertex in &mut self.vertices {
-            *vertex += diff;
-        }
         self.center = pos;
+        self.update_world_vertices();
         self
     }
     
     pub fn rotate(&mut self, angle: f32) -> &mut Self {
-        for vertex in &mut self.vertices {
-            let new_x = ((vertex.x - self.center.x) * angle.cos()
-                - (vertex.y - self.center.y) * angle.sin())
-                + self.center.x;
-            vertex.y = ((vertex.x - self.center.x) * angle.sin()
-                + (vertex.y - self.center.y) * angle.cos())
-                + self.center.y;
-            vertex.x = new_x;
+        self.angle += angle;
+        self.update_world_vertices();
+        self
+    }
+
+    pub fn transform_point(&self, local: Vec2) -> Vec2 {
+        self.center + self.rotation_matrix() * local
+    }
+
+    pub fn inverse_transform_point(&self, world: Vec2) -> Vec2 {
+        self.rotation_matrix().transpose() * (world - self.center)
+    }
+
+    /// Convex pieces in body space as shape, vertices and center, the body itself unless it has fixtures.
+    fn local_pieces(&self) -> Vec<(Shape, &[Vec2], Vec2)> {
+        if self.fixtures.is_empty() {
+            return vec![(self.shape, &self.local_vertices, Vec2::ZERO)];
+        }
+        self.fixtures
+            .iter()
+            .map(|fixture| (fixture.shape, fixture.local_vertices.as_slice(), fixture.local_center))
+            .collect()
+    }
+
+    /// Whether the body space point `local` lies inside the body.
+    pub fn contains_local_point(&self, local: Vec2) -> bool {
+        self.local_pieces().into_iter().any(|(shape, vertices, center)| match shape {
+            Shape::Polygon => point_in_convex(local, vertices),
+            Shape::Circle { radius } => local.distance(center) <= radius,
+            Shape::Capsule { radius } => closest_point_on_segment(local, vertices[0], vertices[1]).distance(local) <= radius,
+        })
+    }
+
+    /// Closest point to the body space point `local` on the body's outline, in body space.
+    /// Bodies with fixtures use the outlines of their pieces, including edges shared between pieces.
+    pub fn project_onto_outline(&self, local: Vec2) -> Vec2 {
+        let mut closest = local;
+        let mut closest_distance = f32::MAX;
+        for (shape, vertices, center) in self.local_pieces() {
+            let candidate = match shape {
+                Shape::Polygon if vertices.is_empty() => continue,
+                Shape::Polygon => closest_point_on_polygon(local, vertices),
+                Shape::Circle { radius } => center + (local - center).normalize_or(Vec2::X) * radius,
+                Shape::Capsule { radius } => {
+                    let spine = closest_point_on_segment(local, vertices[0], vertices[1]);
+                    let normal = (vertices[1] - vertices[0]).perp().normalize_or(Vec2::Y);
+                    spine + (local - spine).normalize_or(normal) * radius
+                }
+            };
+            let distance = candidate.distance_squared(local);
+            if distance < closest_distance {
+                closest_distance = distance;
+                closest = candidate;
+            }
+        }
+        closest
+    }
+
+    /// Keeps a body space anchor on the body, points outside are moved onto the outline.
+    pub fn clamp_anchor(&self, local: Vec2) -> Vec2 {
+        if self.contains_local_point(local) {
+            local
+        } else {
+            self.project_onto_outline(local)
+        }
+    }
+
+    /// Moves a body space anchor to the feature of the body picked by `snap`.
+    /// Round shapes have no vertices or edge midpoints and fall back to the outline.
+    pub fn snap_anchor(&self, local: Vec2, snap: AnchorSnap) -> Vec2 {
+        let mut candidates: Vec<Vec2> = vec![];
+        for (shape, vertices, _) in self.local_pieces() {
+            match (snap, shape) {
+                (AnchorSnap::Vertex, Shape::Polygon) => candidates.extend_from_slice(vertices),
+                (AnchorSnap::EdgeMidpoint, Shape::Polygon) => {
+                    for i in 0..vertices.len() {
+                        candidates.push((vertices[i] + vertices[(i + 1) % vertices.len()]) * 0.5);
+                    }
+                }
+                _ => {}
+            }
+        }
+        match snap {
+            AnchorSnap::Free => self.clamp_anchor(local),
+            AnchorSnap::Centroid => Vec2::ZERO,
+            AnchorSnap::Vertex | AnchorSnap::EdgeMidpoint if !candidates.is_empty() => candidates
+                .into_iter()
+                .min_by(|a, b| a.distance_squared(local).total_cmp(&b.distance_squared(local)))
+                .unwrap(),
+            _ => self.project_onto_outline(local),
+        }
+    }
+
+    /// Rebuilds the cached world space vertices from the local shape and the current transform.
+    /// Always starting from the local shape keeps the body from deforming over long runs.
+    pub fn update_world_vertices(&mut self) {
+        let rotation = self.rotation_matrix();
+        let center = self.center;
+        self.vertices.clear();
+        self.vertices.extend(self.local_vertices.iter().map(|&local| center + rotation * local));
+        for fixture in &mut self.fixtures {
+            fixture.vertices.clear();
+            fixture.vertices.extend(fixture.local_vertices.iter().map(|&local| center + rotation * local));
+            fixture.center = center + rotation * fixture.local_center;
+        }
+    }
+
+    /// Derives the local shape from the world space vertices, needed after editing `vertices` directly.
+    pub fn update_local_vertices(&mut self) {
+        let inverse = self.rotation_matrix().transpose();
+        let center = self.center;
+        self.local_vertices = self.vertices.iter().map(|&world| inverse * (world - center)).collect();
+        for fixture in &mut self.fixtures {
+            fixture.local_vertices = fixture.vertices.iter().map(|&world| inverse * (world - center)).collect();
+            fixture.local_center = inverse * (fixture.center - center);
+        }
+    }
+
+    /// Convex pieces the narrowphase works on, the body itself unless it has fixtures.
+    pub fn colliders(&self) -> Vec<Collider<'_>> {
+        if self.fixtures.is_empty() {
+            return vec![Collider {
+                shape: self.shape,
+                vertices: &self.vertices,
+                center: self.center,
+                restitution: self.restitution,
+                friction: self.friction,
+            }];
+        }
+        self.fixtures.iter().map(|fixture| fixture.collider()).collect()
+    }
+
+    /// Magnetic dipole moment in world space, turning with the body.
+    pub fn dipole_moment(&self) -> Vec2 {
+        Vec2::from_angle(self.angle) * self.magnetic_moment
+    }
+
+    /// Sets the velocity that puts the body on an orbit with `eccentricity` around `central`, see [`orbit_velocity`].
+    /// Leaves the velocity alone when the two bodies do not attract each other.
+    pub fn set_orbit(&mut self, central: &Rigidbody, eccentricity: f32, gravitational_constant: f32) -> &mut Self {
+        if let Some(velocity) = orbit_velocity(self, central, eccentricity, gravitational_constant) {
+            self.velocity = velocity;
         }
         self
     }
 
+    /// Mass per unit area, derived from the current mass.
+    pub fn density(&self) -> f32 {
+        self.mass / self.area.abs()
+    }
+
+    /// Recomputes the mass from `density` and the current area, fixtures all take the same density.
+    pub fn set_density(&mut self, density: f32) {
+        if self.fixtures.is_empty() {
+            self.mass = density * self.area.abs();
+        } else {
+            for fixture in &mut self.fixtures {
+                fixture.density = density;
+            }
+            self.mass = self.fixtures.iter().map(|fixture| fixture.mass()).sum();
+        }
+        self.calculate_moment_of_inertia();
+    }
+
+    pub fn set_material(&mut self, material: &Material) {
+        self.restitution = material.restitution;
+        self.friction = material.friction;
+        for fixture in &mut self.fixtures {
+            fixture.restitution = material.restitution;
+            fixture.friction = material.friction;
+        }
+        self.set_density(material.density);
+    }
+
     pub fn change_color(&mut self, color: ColorRGBA) {
         self.color = color
     }
 
-    pub fn update_rigidbody(&self, polygons: &Vec<Rigidbody>, next: &mut Vec<Rigidbody>, i: usize, gf: Vec2, g: f32, dt: f32) {
+    /// Render mesh in body space, round shapes are fanned around an extra center index one past the outline.
+    /// Uploading this once with the body transform is enough to draw the body on the GPU.
+    pub fn local_mesh(&self) -> (Vec<Vec2>, Vec<u32>) {
+        if self.shape == Shape::Polygon {
+            return (self.local_vertices.clone(), self.indices.clone());
+        }
+        let outline = outline(self.shape, &self.local_vertices, Vec2::ZERO, 0.0);
+
+        let count = outline.len() as u32;
+        let mut indices: Vec<u32> = Vec::with_capacity(outline.len() * 3);
+        for i in 0..count {
+            indices.push(i);
+            indices.push((i + 1) % count);
+            indices.push(count);
+        }
+        (outline, indices)
+    }
+
+    /// World space render mesh generated from the body transform.
+    pub fn tessellate(&self) -> (Vec<Vec2>, Vec<u32>) {
+        let (mut mesh, indices) = self.local_mesh();
+        for vertex in &mut mesh {
+            *vertex = self.transform_point(*vertex);
+        }
+        (mesh, indices)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_rigidbody(&self, polygons: &Vec<Rigidbody>, next: &mut Vec<Rigidbody>, i: usize, gf: Vec2, constants: &PairwiseConstants, fields: &[ForceField], dt: f32) {
         let force = |dt_offset: f32, my_pos: Vec2, _my_vel: Vec2| {
             let mut accel = gf * self.mass * self.gravity_multiplier;
+            if !self.is_static {
+                for field in fields {
+                    accel += field.acceleration(my_pos) * self.mass * self.gravity_multiplier;
+                }
+            }
             for (j, other) in polygons.iter().enumerate() {
                 if i == j {continue; }
 
                 let other_pos_at_t = other.center + other.velocity * dt_offset;
-
-                let diff = other_pos_at_t - my_pos;
-                let dist_sq = diff.length_squared() + 1e-14;
-                accel += diff * (g * other.gravity_multiplier * other.mass / (dist_sq * dist_sq.sqrt()));
+                accel += constants.force(self, my_pos, other, other_pos_at_t);
             }
             accel
         };
         let (new_x, new_v) = dormand_prince_step(0.0, self.center, self.velocity, dt, self.mass, &force);
-        let force = |_: f32, _: f32, _: f32| 0.0;
+        let mut torque = 0.0;
+        if self.magnetic_moment != 0.0 {
+            for (j, other) in polygons.iter().enumerate() {
+                if i != j {
+                    torque += constants.torque(self, other);
+                }
+            }
+        }
+        let force = |_: f32, _: f32, _: f32| torque;
         let (new_angle_b, new_omega_b) = rk4_angular_step(0.0, self.angle, self.angular_velocity, dt, self.moment_of_inertia, &force, );
 
         let mut p1 = self.clone();
-        let diff = new_angle_b - p1.angle;
-        p1.rotate(diff);
         p1.angle = new_angle_b;
         p1.angular_velocity = new_omega_b;
         p1.velocity = new_v;
-        let diff = new_x - p1.center;
-        p1.translate(diff);
+        p1.center = new_x;
+        p1.update_world_vertices();
         next.push(p1);
 
     }
-}
\ No newline at end of file
+}
+
+/// Outline of a shape, round shapes are approximated with `CIRCLE_SEGMENTS` segments starting at `angle`.
+pub(crate) fn outline(shape: Shape, vertices: &[Vec2], center: Vec2, angle: f32) -> Vec<Vec2> {
+    let mut outline: Vec<Vec2> = vec![];
+    match shape {
+        Shape::Polygon => outline.extend_from_slice(vertices),
+        Shape::Circle { radius } => {
+            for i in 0..CIRCLE_SEGMENTS {
+                let angle = angle + i as f32 * 2.0 * PI / CIRCLE_SEGMENTS as f32;
+                outline.push(center + Vec2::from_angle(angle) * radius);
+            }
+        }
+        Shape::Capsule { radius } => {
+            let (start, end) = (vertices[0], vertices[1]);
+            let axis_angle = (end - start).to_angle();
+            let cap_segments = CIRCLE_SEGMENTS / 2;
+            for (cap_center, start_angle) in [(end, axis_angle - PI / 2.0), (start, axis_angle + PI / 2.0)] {
+                for i in 0..=cap_segments {
+                    let angle = start_angle + i as f32 * PI / cap_segments as f32;
+                    outline.push(cap_center + Vec2::from_angle(angle) * radius);
+                }
+            }
+        }
+    }
+    outline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangle_inertia() {
+        let square = Rigidbody::rectangle(1.0, 1.0, Vec2::new(3.0, -2.0), 1.0, 0.0, ColorRGBA::white());
+        assert!((square.area.abs() - 1.0).abs() < 1e-5);
+        assert!((square.moment_of_inertia - 1.0 / 6.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn circle_and_capsule_inertia() {
+        let circle = Rigidbody::circle(2.0, Vec2::ZERO, 3.0, 0.0, ColorRGBA::white());
+        assert!((circle.moment_of_inertia - 0.5 * 3.0 * 4.0).abs() < 1e-4);
+        // A capsule is a rectangle with a circle split over its ends
+        let capsule = Rigidbody::capsule(2.0, 0.5, Vec2::ZERO, 1.0, 0.0, ColorRGBA::white());
+        let density = 1.0 / (2.0 + PI * 0.25);
+        let box_inertia = 2.0 * density * (4.0 + 1.0) / 12.0;
+        let cap_mass = PI * 0.25 * density;
+        let cap_offset = 1.0 + 2.0 / (3.0 * PI);
+        let cap_inertia = cap_mass * 0.25 * (0.5 - 16.0 / (9.0 * PI * PI)) + cap_mass * cap_offset * cap_offset;
+        assert!((capsule.moment_of_inertia - (box_inertia + cap_inertia)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn compound_inertia_uses_parallel_axis() {
+        // Two unit squares side by side make a 2 by 1 rectangle
+        let fixtures = vec![
+            Fixture::rectangle(1.0, 1.0, Vec2::new(-0.5, 0.0), 1.0, 0.0, 0.5),
+            Fixture::rectangle(1.0, 1.0, Vec2::new(0.5, 0.0), 1.0, 0.0, 0.5),
+        ];
+        let compound = Rigidbody::compound(fixtures, Vec2::new(4.0, 1.0), ColorRGBA::white());
+        let rectangle = Rigidbody::rectangle(2.0, 1.0, Vec2::new(4.0, 1.0), 2.0, 0.0, ColorRGBA::white());
+        assert!((compound.mass - 2.0).abs() < 1e-5);
+        assert!(compound.center.abs_diff_eq(Vec2::new(4.0, 1.0), 1e-5));
+        assert!((compound.moment_of_inertia - 5.0 / 6.0).abs() < 1e-4);
+        assert!((compound.moment_of_inertia - rectangle.moment_of_inertia).abs() < 1e-4);
+    }
+}
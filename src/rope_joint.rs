@@ -0,0 +1,58 @@
+use glam::Vec2;
+use crate::distance_joint::create_link;
+use crate::{ColorRGBA, DistanceJoint, Rigidbody};
+
+/// Inextensible rope between two anchors, it stops them moving further than its maximum length apart but never pushes.
+/// A distance joint limited to lengths between zero and the maximum.
+#[derive(Clone)]
+pub struct RopeJoint {
+    pub(crate) joint: DistanceJoint,
+}
+
+impl RopeJoint {
+    /// The rope starts taut, its length is the current distance between the anchors.
+    pub fn new(local_anchor_a: Vec2, local_anchor_b: Vec2, rigidbodys: &[Rigidbody], body_a: usize, body_b: usize) -> Self {
+        let mut joint = DistanceJoint::new(local_anchor_a, local_anchor_b, rigidbodys, body_a, body_b);
+        joint.set_limits(0.0, joint.length);
+        Self { joint }
+    }
+
+    pub fn max_length(&self) -> f32 {
+        self.joint.max_length
+    }
+
+    pub fn set_max_length(&mut self, max_length: f32) -> &mut Self {
+        self.joint.set_limits(0.0, max_length);
+        self
+    }
+
+    /// Baumgarte stabilization factor for positional drift (small: 0.01..0.2)
+    pub fn set_beta(&mut self, beta: f32) -> &mut Self {
+        self.joint.beta = beta;
+        self
+    }
+
+    /// Current distance between the anchors.
+    pub fn current_length(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        self.joint.current_length(rigidbodys)
+    }
+
+    pub fn solve_velocity_constraints(&self, rigidbodys: &mut [Rigidbody], dt: f32) {
+        self.joint.solve_velocity_constraints(rigidbodys, dt);
+    }
+
+    pub fn get_anchor_world_positions(&self, rigidbodys: &[Rigidbody]) -> (Vec2, Vec2) {
+        self.joint.get_anchor_world_positions(rigidbodys)
+    }
+
+    /// Line between the anchors, dimmed while the rope is slack.
+    pub fn create_link(&self, rigidbodys: &[Rigidbody]) -> Rigidbody {
+        let (anchor_a, anchor_b) = self.get_anchor_world_positions(rigidbodys);
+        let color = if anchor_a.distance(anchor_b) < self.joint.max_length - 1e-3 {
+            ColorRGBA::new(0.8, 0.6, 0.4, 0.4)
+        } else {
+            ColorRGBA::new(0.8, 0.6, 0.4, 1.0)
+        };
+        create_link(anchor_a, anchor_b, color)
+    }
+}
@@ -0,0 +1,184 @@
+use std::f32::consts::PI;
+use glam::Vec2;
+use crate::{ColorRGBA, Rigidbody, Spring};
+
+/// Particles and springs of a generated soft body, stiffnesses of zero leave that kind of spring out.
+#[derive(Clone, Debug)]
+pub struct SoftBodyParams {
+    pub particle_radius: f32,
+    pub particle_mass: f32,
+    /// Springs between neighbouring particles.
+    pub stiffness: f32,
+    /// Diagonal springs that keep the cells from shearing, across the ring for ring soft bodies.
+    pub shear_stiffness: f32,
+    /// Springs skipping one particle that resist bending.
+    pub bend_stiffness: f32,
+    pub damping: f32,
+    /// Gas pressure pushing the outline outwards at the rest area, zero disables the pressure model.
+    pub pressure: f32,
+    pub color: ColorRGBA,
+}
+
+impl Default for SoftBodyParams {
+    fn default() -> Self {
+        Self {
+            particle_radius: 0.08,
+            particle_mass: 0.1,
+            stiffness: 60.0,
+            shear_stiffness: 30.0,
+            bend_stiffness: 10.0,
+            damping: 0.5,
+            pressure: 0.0,
+            color: ColorRGBA::random_hsl(),
+        }
+    }
+}
+
+/// Outline of a soft body made of particle rigidbodies, inflated by a pressure force when `pressure` is non zero.
+#[derive(Clone, Debug)]
+pub struct SoftBody {
+    /// Every particle of the soft body.
+    pub particles: Vec<usize>,
+    /// Particles around the outline in counter clockwise order.
+    pub outline: Vec<usize>,
+    /// Pressure at `rest_area`, the pressure scales with `rest_area / area` like an ideal gas.
+    pub pressure: f32,
+    pub rest_area: f32,
+}
+
+impl SoftBody {
+    /// Ring of `segments` particles around `center`, appended to `rigidbodys` and `springs`.
+    pub fn ring(
+        center: Vec2,
+        radius: f32,
+        segments: usize,
+        params: &SoftBodyParams,
+        rigidbodys: &mut Vec<Rigidbody>,
+        springs: &mut Vec<Spring>,
+    ) -> Self {
+        let segments = segments.max(3);
+        let first = rigidbodys.len();
+        for i in 0..segments {
+            let angle = i as f32 * 2.0 * PI / segments as f32;
+            rigidbodys.push(Self::particle(center + Vec2::from_angle(angle) * radius, params));
+        }
+        let outline: Vec<usize> = (first..first + segments).collect();
+
+        for i in 0..segments {
+            Self::link(outline[i], outline[(i + 1) % segments], params.stiffness, params, rigidbodys, springs);
+            if segments > 3 {
+                Self::link(outline[i], outline[(i + 2) % segments], params.bend_stiffness, params, rigidbodys, springs);
+            }
+            // Braces across the ring, each pair once
+            if i < segments / 2 {
+                Self::link(outline[i], outline[i + segments / 2], params.shear_stiffness, params, rigidbodys, springs);
+            }
+        }
+
+        Self::from_outline(outline.clone(), outline, params.pressure, rigidbodys)
+    }
+
+    /// Grid of `columns` by `rows` particles `spacing` apart, centered on `center`, appended to `rigidbodys` and `springs`.
+    pub fn grid(
+        center: Vec2,
+        columns: usize,
+        rows: usize,
+        spacing: f32,
+        params: &SoftBodyParams,
+        rigidbodys: &mut Vec<Rigidbody>,
+        springs: &mut Vec<Spring>,
+    ) -> Self {
+        let columns = columns.max(2);
+        let rows = rows.max(2);
+        let first = rigidbodys.len();
+        let origin = center - Vec2::new(columns as f32 - 1.0, rows as f32 - 1.0) * spacing * 0.5;
+        for row in 0..rows {
+            for column in 0..columns {
+                rigidbodys.push(Self::particle(origin + Vec2::new(column as f32, row as f32) * spacing, params));
+            }
+        }
+        let index = |column: usize, row: usize| first + row * columns + column;
+
+        for row in 0..rows {
+            for column in 0..columns {
+                if column + 1 < columns {
+                    Self::link(index(column, row), index(column + 1, row), params.stiffness, params, rigidbodys, springs);
+                }
+                if row + 1 < rows {
+                    Self::link(index(column, row), index(column, row + 1), params.stiffness, params, rigidbodys, springs);
+                }
+                if column + 1 < columns && row + 1 < rows {
+                    Self::link(index(column, row), index(column + 1, row + 1), params.shear_stiffness, params, rigidbodys, springs);
+                    Self::link(index(column + 1, row), index(column, row + 1), params.shear_stiffness, params, rigidbodys, springs);
+                }
+                if column + 2 < columns {
+                    Self::link(index(column, row), index(column + 2, row), params.bend_stiffness, params, rigidbodys, springs);
+                }
+                if row + 2 < rows {
+                    Self::link(index(column, row), index(column, row + 2), params.bend_stiffness, params, rigidbodys, springs);
+                }
+            }
+        }
+
+        // Bottom row, right column, top row backwards, left column backwards
+        let mut outline = vec![];
+        outline.extend((0..columns).map(|column| index(column, 0)));
+        outline.extend((1..rows).map(|row| index(columns - 1, row)));
+        outline.extend((0..columns - 1).rev().map(|column| index(column, rows - 1)));
+        outline.extend((1..rows - 1).rev().map(|row| index(0, row)));
+
+        Self::from_outline((first..first + columns * rows).collect(), outline, params.pressure, rigidbodys)
+    }
+
+    fn from_outline(particles: Vec<usize>, outline: Vec<usize>, pressure: f32, rigidbodys: &[Rigidbody]) -> Self {
+        let mut soft_body = Self { particles, outline, pressure, rest_area: 0.0 };
+        soft_body.rest_area = soft_body.area(rigidbodys);
+        soft_body
+    }
+
+    fn particle(pos: Vec2, params: &SoftBodyParams) -> Rigidbody {
+        let mut particle = Rigidbody::circle(params.particle_radius, pos, params.particle_mass, 0.2, params.color);
+        particle.friction = 0.5;
+        particle
+    }
+
+    /// Spring at its current length between the centers of `body_a` and `body_b`.
+    fn link(body_a: usize, body_b: usize, stiffness: f32, params: &SoftBodyParams, rigidbodys: &Vec<Rigidbody>, springs: &mut Vec<Spring>) {
+        if stiffness <= 0.0 {
+            return;
+        }
+        let rest_length = rigidbodys[body_a].center.distance(rigidbodys[body_b].center);
+        springs.push(Spring::new(body_a, body_b, Vec2::ZERO, Vec2::ZERO, rest_length, stiffness, params.damping, rigidbodys));
+    }
+
+    /// Area enclosed by the outline particles.
+    pub fn area(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        let n = self.outline.len();
+        let mut area = 0.0;
+        for i in 0..n {
+            let a = rigidbodys[self.outline[i]].center;
+            let b = rigidbodys[self.outline[(i + 1) % n]].center;
+            area += a.perp_dot(b);
+        }
+        area * 0.5
+    }
+
+    /// Pushes every outline edge outwards with the gas pressure, split between the edge's two particles.
+    pub fn apply_pressure(&self, rigidbodys: &mut [Rigidbody], dt: f32) {
+        let n = self.outline.len();
+        if self.pressure == 0.0 || n < 3 {
+            return;
+        }
+        let area = self.area(rigidbodys).max(self.rest_area * 0.05);
+        let pressure = self.pressure * self.rest_area / area;
+        for i in 0..n {
+            let a = self.outline[i];
+            let b = self.outline[(i + 1) % n];
+            // Counter clockwise outline, so the outward normal is the edge turned clockwise
+            let edge = rigidbodys[b].center - rigidbodys[a].center;
+            let force = -edge.perp() * pressure * 0.5;
+            rigidbodys[a].velocity += force / rigidbodys[a].mass * dt;
+            rigidbodys[b].velocity += force / rigidbodys[b].mass * dt;
+        }
+    }
+}
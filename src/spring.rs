@@ -4,6 +4,26 @@ use crate::ode_solver::{rk4_angular_step, rk4_step};
 use crate::rigidbody::Rigidbody;
 use crate::{ColorRGBA};
 
+/// Force law of a [`Spring`], every law except `Frequency` uses the spring's `stiffness` and `damping`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SpringLaw {
+    /// Hooke spring, force proportional to the stretch.
+    #[default]
+    Linear,
+    /// Only pushes the bodies apart while shorter than the rest length.
+    CompressionOnly,
+    /// Only pulls the bodies together while longer than the rest length, like an elastic band.
+    TensionOnly,
+    /// `stiffness` up to a stretch of `threshold`, `stiffness * ratio` beyond it.
+    Bilinear { threshold: f32, ratio: f32 },
+    /// Hardening spring adding `cubic * stretch^3` to the linear force.
+    Stiffening { cubic: f32 },
+    /// Torque keeping `angle_b - angle_a` at `rest_angle`, `stiffness` is per radian.
+    Torsional { rest_angle: f32 },
+    /// Stiffness and damping derived from the reduced mass of the bodies, 1.0 is critically damped.
+    Frequency { frequency: f32, damping_ratio: f32 },
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Spring {
     pub body_a: usize,
@@ -14,6 +34,15 @@ pub struct Spring {
     pub(crate) rest_length: f32,
     pub(crate) stiffness: f32,
     pub(crate) damping: f32,
+    /// Largest force the spring carries before it breaks, unbreakable when `None`.
+    pub break_force: Option<f32>,
+    /// Largest stretch or compression from `rest_length` before the spring breaks.
+    pub break_stretch: Option<f32>,
+    /// Magnitude of the force applied during the last step.
+    pub force: f32,
+    /// Set once a break threshold was exceeded, a broken spring no longer applies forces.
+    pub broken: bool,
+    pub law: SpringLaw,
 }
 
 impl Spring {
@@ -63,10 +92,95 @@ impl Spring {
             rest_length,
             stiffness,
             damping,
+            break_force: None,
+            break_stretch: None,
+            force: 0.0,
+            broken: false,
+            law: SpringLaw::Linear,
+        }
+    }
+
+    pub fn set_law(&mut self, law: SpringLaw) -> &mut Self {
+        self.law = law;
+        self
+    }
+
+    /// Stiffness and damping the spring acts with, `Frequency` springs derive them from the body masses.
+    pub fn coefficients(&self, rigidbodys: &[Rigidbody]) -> (f32, f32) {
+        match self.law {
+            SpringLaw::Frequency { frequency, damping_ratio } => {
+                let mass_a = rigidbodys[self.body_a].mass;
+                let mass_b = rigidbodys[self.body_b].mass;
+                let reduced_mass = mass_a * mass_b / (mass_a + mass_b);
+                let omega = 2.0 * PI * frequency;
+                (reduced_mass * omega * omega, 2.0 * reduced_mass * damping_ratio * omega)
+            }
+            _ => (self.stiffness, self.damping),
+        }
+    }
+
+    /// Signed force along the spring for a given stretch and stretching speed, negative pulls the anchors together.
+    fn scalar_force(&self, stretch: f32, speed: f32, stiffness: f32, damping: f32) -> f32 {
+        let elastic = match self.law {
+            SpringLaw::CompressionOnly if stretch > 0.0 => return 0.0,
+            SpringLaw::TensionOnly if stretch < 0.0 => return 0.0,
+            SpringLaw::Bilinear { threshold, ratio } if stretch.abs() > threshold => {
+                stretch.signum() * stiffness * (threshold + ratio * (stretch.abs() - threshold))
+            }
+            SpringLaw::Stiffening { cubic } => stiffness * stretch + cubic * stretch.powi(3),
+            _ => stiffness * stretch,
+        };
+        -elastic - damping * speed
+    }
+
+    /// Elastic energy stored in the spring, matching the force of its law.
+    pub fn potential_energy(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        let (stiffness, _) = self.coefficients(rigidbodys);
+        let a = &rigidbodys[self.body_a];
+        let b = &rigidbodys[self.body_b];
+        if let SpringLaw::Torsional { rest_angle } = self.law {
+            let deflection = b.angle - a.angle - rest_angle;
+            return 0.5 * stiffness * deflection * deflection;
         }
+        let distance = b.transform_point(self.anchor_b).distance(a.transform_point(self.anchor_a));
+        let stretch = distance - self.rest_length;
+        match self.law {
+            SpringLaw::CompressionOnly if stretch > 0.0 => 0.0,
+            SpringLaw::TensionOnly if stretch < 0.0 => 0.0,
+            SpringLaw::Bilinear { threshold, ratio } if stretch.abs() > threshold => {
+                let beyond = stretch.abs() - threshold;
+                0.5 * stiffness * threshold * threshold + stiffness * threshold * beyond + 0.5 * stiffness * ratio * beyond * beyond
+            }
+            SpringLaw::Stiffening { cubic } => 0.5 * stiffness * stretch * stretch + 0.25 * cubic * stretch.powi(4),
+            _ => 0.5 * stiffness * stretch * stretch,
+        }
+    }
+
+    /// Marks the spring broken when the force or stretch passed its thresholds.
+    fn check_break(&mut self, stretch: f32) {
+        if let Some(break_force) = self.break_force && self.force > break_force {
+            self.broken = true;
+        }
+        if let Some(break_stretch) = self.break_stretch && stretch.abs() > break_stretch {
+            self.broken = true;
+        }
+    }
+
+    pub fn set_break_force(&mut self, break_force: f32) -> &mut Self {
+        self.break_force = Some(break_force);
+        self
+    }
+
+    pub fn set_break_stretch(&mut self, break_stretch: f32) -> &mut Self {
+        self.break_stretch = Some(break_stretch);
+        self
     }
 
     pub fn apply(&mut self, dt: f32, rigidbodys: &mut Vec<Rigidbody>) {
+        if self.broken {
+            return;
+        }
+        let (stiffness, damping) = self.coefficients(rigidbodys);
         let (a, b) = {
             let (low, high) = if self.body_a > self.body_b {
                 let (left, right) = rigidbodys.split_at_mut(self.body_a);
@@ -78,6 +192,23 @@ impl Spring {
             (low, high)
         };
 
+        if let SpringLaw::Torsional { rest_angle } = self.law {
+            let deflection = b.angle - a.angle - rest_angle;
+            let torque = -stiffness * deflection - damping * (b.angular_velocity - a.angular_velocity);
+            self.force = torque.abs();
+            self.check_break(deflection);
+            if self.broken {
+                return;
+            }
+            let torque_fn_a = |_t: f32, _theta: f32, _omega: f32| -torque;
+            let torque_fn_b = |_t: f32, _theta: f32, _omega: f32| torque;
+            (_, a.angular_velocity) =
+                rk4_angular_step(0.0, a.angle, a.angular_velocity, dt, a.moment_of_inertia, &torque_fn_a);
+            (_, b.angular_velocity) =
+                rk4_angular_step(0.0, b.angle, b.angular_velocity, dt, b.moment_of_inertia, &torque_fn_b);
+            return;
+        }
+
         // --- Compute world-space anchors using rotation matrices ---
         let rot_a = Mat2::from_angle(a.angle);
         let rot_b = Mat2::from_angle(b.angle);
@@ -99,9 +230,13 @@ impl Spring {
         let vel_b = b.velocity + b.angular_velocity * (world_anchor_b - b.center).perp();
         let relative_velocity = vel_b - vel_a;
 
-        let spring_force = -self.stiffness * stretch * direction;
-        let damping_force = -self.damping * relative_velocity.dot(direction) * direction;
-        let total_force = spring_force + damping_force;
+        let total_force = self.scalar_force(stretch, relative_velocity.dot(direction), stiffness, damping) * direction;
+
+        self.force = total_force.length();
+        self.check_break(stretch);
+        if self.broken {
+            return;
+        }
 
         // --- Apply forces and torques ---
         let r_a = world_anchor_a - a.center;
@@ -0,0 +1,122 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use glam::Vec2;
+use crate::distance_joint::create_link;
+use crate::{ColorRGBA, Rigidbody};
+
+/// File the telemetry of every pinned body is exported to, relative to the working directory.
+pub const TELEMETRY_PATH: &str = "telemetry.csv";
+
+/// State of a pinned body after one physics update.
+#[derive(Clone, Copy, Debug)]
+pub struct TelemetrySample {
+    pub time: f32,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub angle: f32,
+    pub angular_velocity: f32,
+    /// Net force of the contacts touching the body, averaged over the update.
+    pub contact_force: Vec2,
+}
+
+/// Recorded motion of one body, drawn as a fading trail behind it.
+#[derive(Clone, Debug)]
+pub struct Telemetry {
+    pub body: usize,
+    pub samples: VecDeque<TelemetrySample>,
+    /// Oldest samples are dropped once this many are stored.
+    pub capacity: usize,
+    /// Seconds of motion the trail reaches back, zero hides the trail.
+    pub trail_duration: f32,
+    pub trail_color: ColorRGBA,
+}
+
+impl Telemetry {
+    pub fn new(body: usize, trail_color: ColorRGBA) -> Self {
+        Self {
+            body,
+            samples: VecDeque::new(),
+            capacity: 20000,
+            trail_duration: 3.0,
+            trail_color,
+        }
+    }
+
+    /// Stores the state of the pinned body at `time`, `dt` turns the contact impulse into a force.
+    pub fn record(&mut self, rigidbodys: &[Rigidbody], time: f32, dt: f32) {
+        let Some(body) = rigidbodys.get(self.body) else {
+            return;
+        };
+        let contact_force = if dt > 0.0 { body.contact_impulse / dt } else { Vec2::ZERO };
+        self.samples.push_back(TelemetrySample {
+            time,
+            position: body.center,
+            velocity: body.velocity,
+            angle: body.angle,
+            angular_velocity: body.angular_velocity,
+            contact_force,
+        });
+        while self.samples.len() > self.capacity.max(1) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Line segments along the last `trail_duration` seconds of the path, fading out with age.
+    pub fn create_trail(&self) -> Vec<Rigidbody> {
+        let mut trail = vec![];
+        let Some(last) = self.samples.back() else {
+            return trail;
+        };
+        if self.trail_duration <= 0.0 {
+            return trail;
+        }
+        let start = self.samples.partition_point(|sample| sample.time < last.time - self.trail_duration);
+        // Segments shorter than this are merged so long recordings stay cheap to draw
+        let min_length = 0.02;
+        let mut previous = self.samples[start.min(self.samples.len() - 1)];
+        for sample in self.samples.range(start..) {
+            if sample.position.distance(previous.position) < min_length {
+                continue;
+            }
+            let age = (last.time - sample.time) / self.trail_duration;
+            let mut color = self.trail_color;
+            color.a *= (1.0 - age).clamp(0.0, 1.0);
+            trail.push(create_link(previous.position, sample.position, color));
+            previous = *sample;
+        }
+        trail
+    }
+
+    /// Every sample as comma separated values, without a header so several bodies can share a file.
+    pub fn to_csv_rows(&self) -> String {
+        let mut contents = String::new();
+        for sample in &self.samples {
+            contents += &format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                self.body,
+                sample.time,
+                sample.position.x,
+                sample.position.y,
+                sample.velocity.x,
+                sample.velocity.y,
+                sample.angle,
+                sample.angular_velocity,
+                sample.contact_force.x,
+                sample.contact_force.y,
+            );
+        }
+        contents
+    }
+
+    /// Writes the samples of every telemetry in `telemetry` to one file, one row per body and sample.
+    pub fn export_csv(path: &str, telemetry: &[Telemetry]) -> io::Result<()> {
+        let mut contents = String::from(
+            "body,time,position_x,position_y,velocity_x,velocity_y,angle,angular_velocity,contact_force_x,contact_force_y\n",
+        );
+        for pinned in telemetry {
+            contents += &pinned.to_csv_rows();
+        }
+        fs::write(path, contents)
+    }
+}
@@ -0,0 +1,249 @@
+use glam::Vec2;
+
+pub fn signed_area(vertices: &[Vec2]) -> f32 {
+    let n = vertices.len();
+    let mut area = 0.0;
+    for i in 0..n {
+        area += vertices[i].perp_dot(vertices[(i + 1) % n]);
+    }
+    area * 0.5
+}
+
+/// Ear clipping triangulation of a simple counter-clockwise polygon.
+pub fn triangulate(vertices: &[Vec2]) -> Vec<u32> {
+    let mut remaining: Vec<usize> = (0..vertices.len()).collect();
+    let mut indices: Vec<u32> = Vec::with_capacity((vertices.len().saturating_sub(2)) * 3);
+
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let mut ear = None;
+        for i in 0..m {
+            let prev = remaining[(i + m - 1) % m];
+            let current = remaining[i];
+            let next = remaining[(i + 1) % m];
+            let (a, b, c) = (vertices[prev], vertices[current], vertices[next]);
+            // Reflex or collinear corners can't be ears
+            if (b - a).perp_dot(c - b) <= 0.0 {
+                continue;
+            }
+            let blocked = remaining.iter().any(|&k| {
+                k != prev && k != current && k != next && point_in_triangle(vertices[k], a, b, c)
+            });
+            if !blocked {
+                ear = Some(i);
+                break;
+            }
+        }
+
+        match ear {
+            Some(i) => {
+                let prev = remaining[(i + m - 1) % m];
+                let next = remaining[(i + 1) % m];
+                indices.extend([prev as u32, remaining[i] as u32, next as u32]);
+                remaining.remove(i);
+            }
+            None => {
+                // Only degenerate corners are left, drop a collinear one and keep going
+                let collinear = (0..m).find(|&i| {
+                    let a = vertices[remaining[(i + m - 1) % m]];
+                    let b = vertices[remaining[i]];
+                    let c = vertices[remaining[(i + 1) % m]];
+                    (b - a).perp_dot(c - b).abs() <= f32::EPSILON
+                });
+                match collinear {
+                    Some(i) => {
+                        remaining.remove(i);
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+    if remaining.len() == 3 {
+        indices.extend(remaining.iter().map(|&i| i as u32));
+    }
+    indices
+}
+
+/// Centroid of the area enclosed by a simple polygon, the vertex average for degenerate ones.
+pub fn polygon_centroid(vertices: &[Vec2]) -> Vec2 {
+    let n = vertices.len();
+    let mut centroid = Vec2::ZERO;
+    let mut area = 0.0;
+    for i in 0..n {
+        let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+        let cross = a.perp_dot(b);
+        area += cross;
+        centroid += (a + b) * cross;
+    }
+    if area.abs() <= f32::EPSILON {
+        return vertices.iter().copied().sum::<Vec2>() / n.max(1) as f32;
+    }
+    centroid / (3.0 * area)
+}
+
+/// Sutherland-Hodgman clipping of any simple polygon `subject` by the convex polygon `clip`.
+/// Returns the part of `subject` inside `clip`, empty if they don't overlap.
+pub fn clip_polygon(subject: &[Vec2], clip: &[Vec2]) -> Vec<Vec2> {
+    let mut output = subject.to_vec();
+    let orientation = signed_area(clip).signum();
+    for i in 0..clip.len() {
+        if output.is_empty() {
+            break;
+        }
+        let (edge_start, edge_end) = (clip[i], clip[(i + 1) % clip.len()]);
+        let edge = edge_end - edge_start;
+        let inside = |point: Vec2| edge.perp_dot(point - edge_start) * orientation >= 0.0;
+        let input = std::mem::take(&mut output);
+        for j in 0..input.len() {
+            let current = input[j];
+            let previous = input[(j + input.len() - 1) % input.len()];
+            let intersection = || {
+                let direction = current - previous;
+                let t = edge.perp_dot(edge_start - previous) / edge.perp_dot(direction);
+                previous + direction * t
+            };
+            match (inside(previous), inside(current)) {
+                (true, true) => output.push(current),
+                (true, false) => output.push(intersection()),
+                (false, true) => {
+                    output.push(intersection());
+                    output.push(current);
+                }
+                (false, false) => {}
+            }
+        }
+    }
+    output
+}
+
+/// Hertel-Mehlhorn decomposition: greedily merges triangles across diagonals while the
+/// result stays convex. `triangles` are indices produced by [`triangulate`].
+pub fn convex_decomposition(vertices: &[Vec2], triangles: &[u32]) -> Vec<Vec<Vec2>> {
+    let mut pieces: Vec<Vec<usize>> = triangles
+        .chunks_exact(3)
+        .map(|t| vec![t[0] as usize, t[1] as usize, t[2] as usize])
+        .collect();
+
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'search: for i in 0..pieces.len() {
+            for j in (i + 1)..pieces.len() {
+                if let Some(piece) = merge_pieces(&pieces[i], &pieces[j], vertices) {
+                    pieces[i] = piece;
+                    pieces.remove(j);
+                    merged = true;
+                    break 'search;
+                }
+            }
+        }
+    }
+
+    pieces
+        .iter()
+        .map(|piece| piece.iter().map(|&i| vertices[i]).collect())
+        .collect()
+}
+
+/// Joins two pieces sharing an edge if the union is convex.
+fn merge_pieces(a: &[usize], b: &[usize], vertices: &[Vec2]) -> Option<Vec<usize>> {
+    let n = a.len();
+    let m = b.len();
+    for i in 0..n {
+        let (u, v) = (a[i], a[(i + 1) % n]);
+        // Counter-clockwise neighbours traverse the shared edge in opposite directions
+        let Some(j) = (0..m).find(|&j| b[j] == v && b[(j + 1) % m] == u) else {
+            continue;
+        };
+        let mut piece: Vec<usize> = Vec::with_capacity(n + m - 2);
+        // a from v around to u, then b from u around to v, without repeating the shared pair
+        for k in 0..n {
+            piece.push(a[(i + 1 + k) % n]);
+        }
+        for k in 1..m - 1 {
+            piece.push(b[(j + 1 + k) % m]);
+        }
+        if is_convex(&piece, vertices) {
+            return Some(piece);
+        }
+        return None;
+    }
+    None
+}
+
+fn is_convex(piece: &[usize], vertices: &[Vec2]) -> bool {
+    let n = piece.len();
+    (0..n).all(|i| {
+        let a = vertices[piece[i]];
+        let b = vertices[piece[(i + 1) % n]];
+        let c = vertices[piece[(i + 2) % n]];
+        (b - a).perp_dot(c - b) >= -f32::EPSILON
+    })
+}
+
+fn point_in_triangle(point: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let d1 = (b - a).perp_dot(point - a);
+    let d2 = (c - b).perp_dot(point - b);
+    let d3 = (a - c).perp_dot(point - c);
+    d1 >= 0.0 && d2 >= 0.0 && d3 >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counter-clockwise L shape of area 3.
+    fn l_shape() -> Vec<Vec2> {
+        vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(0.0, 2.0),
+        ]
+    }
+
+    #[test]
+    fn ear_clipping_covers_the_polygon() {
+        let vertices = l_shape();
+        let indices = triangulate(&vertices);
+        assert_eq!(indices.len(), 3 * (vertices.len() - 2));
+        let mut area = 0.0;
+        for triangle in indices.chunks_exact(3) {
+            let triangle_area = signed_area(&[
+                vertices[triangle[0] as usize],
+                vertices[triangle[1] as usize],
+                vertices[triangle[2] as usize],
+            ]);
+            assert!(triangle_area > 0.0);
+            area += triangle_area;
+        }
+        assert!((area - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn convex_decomposition_of_l_shape() {
+        let vertices = l_shape();
+        let pieces = convex_decomposition(&vertices, &triangulate(&vertices));
+        assert_eq!(pieces.len(), 2);
+        let mut area = 0.0;
+        for piece in &pieces {
+            let n = piece.len();
+            for i in 0..n {
+                let turn = (piece[(i + 1) % n] - piece[i]).perp_dot(piece[(i + 2) % n] - piece[(i + 1) % n]);
+                assert!(turn >= -1e-6);
+            }
+            area += signed_area(piece);
+        }
+        assert!((area - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn centroid_of_l_shape() {
+        // Two unit squares and one more, centroids (0.5, 0.5), (1.5, 0.5) and (0.5, 1.5)
+        let centroid = polygon_centroid(&l_shape());
+        assert!(centroid.abs_diff_eq(Vec2::splat(5.0 / 6.0), 1e-5));
+    }
+}
@@ -1,9 +1,9 @@
 use std::f32::consts::PI;
 use glam::{Mat2, Vec2};
-use crate::{ ColorRGBA, Rigidbody};
+use crate::{AnchorSnap, ColorRGBA, GearTarget, Rigidbody};
 use crate::body_builder::BodyBuilder;
-use crate::collision_detection::sat_collision;
-use crate::enums::{BodyType, InputMode};
+use crate::collision_detection::intersects;
+use crate::enums::{InputMode, SelectedJoint};
 use crate::input::UiSystem;
 use crate::physics::PhysicsSystem;
 
@@ -32,7 +32,7 @@ impl PhysicsSystem {
                 break;
             }
             if  self.weld_joints[i].body_a == index || self.weld_joints[i].body_b == index {
-                self.remove_weld_joint(i);
+                self.remove_weld_joint(i, ui_system);
                 continue;
             }
             if self.weld_joints[i].body_a > index {
@@ -49,7 +49,7 @@ impl PhysicsSystem {
                 break;
             }
             if  self.pivot_joints[i].body_a == index || self.pivot_joints[i].body_b == index {
-                self.remove_pivot_joint(i);
+                self.remove_pivot_joint(i, ui_system);
                 continue;
             }
             if self.pivot_joints[i].body_a > index {
@@ -60,6 +60,121 @@ impl PhysicsSystem {
             }
             i += 1;
         }
+        let mut i = 0;
+        loop {
+            if i >= self.prismatic_joints.len() {
+                break;
+            }
+            if  self.prismatic_joints[i].body_a == index || self.prismatic_joints[i].body_b == index {
+                self.remove_prismatic_joint(i);
+                continue;
+            }
+            if self.prismatic_joints[i].body_a > index {
+                self.prismatic_joints[i].body_a -= 1;
+            }
+            if self.prismatic_joints[i].body_b > index {
+                self.prismatic_joints[i].body_b -= 1;
+            }
+            i += 1;
+        }
+        let mut i = 0;
+        loop {
+            if i >= self.distance_joints.len() {
+                break;
+            }
+            if  self.distance_joints[i].body_a == index || self.distance_joints[i].body_b == index {
+                self.remove_distance_joint(i);
+                continue;
+            }
+            if self.distance_joints[i].body_a > index {
+                self.distance_joints[i].body_a -= 1;
+            }
+            if self.distance_joints[i].body_b > index {
+                self.distance_joints[i].body_b -= 1;
+            }
+            i += 1;
+        }
+        let mut i = 0;
+        loop {
+            if i >= self.rope_joints.len() {
+                break;
+            }
+            if  self.rope_joints[i].joint.body_a == index || self.rope_joints[i].joint.body_b == index {
+                self.remove_rope_joint(i);
+                continue;
+            }
+            if self.rope_joints[i].joint.body_a > index {
+                self.rope_joints[i].joint.body_a -= 1;
+            }
+            if self.rope_joints[i].joint.body_b > index {
+                self.rope_joints[i].joint.body_b -= 1;
+            }
+            i += 1;
+        }
+        let mut i = 0;
+        loop {
+            if i >= self.wheel_joints.len() {
+                break;
+            }
+            if  self.wheel_joints[i].body_a == index || self.wheel_joints[i].body_b == index {
+                self.remove_wheel_joint(i);
+                continue;
+            }
+            if self.wheel_joints[i].body_a > index {
+                self.wheel_joints[i].body_a -= 1;
+            }
+            if self.wheel_joints[i].body_b > index {
+                self.wheel_joints[i].body_b -= 1;
+            }
+            i += 1;
+        }
+        let mut i = 0;
+        loop {
+            if i >= self.pulley_joints.len() {
+                break;
+            }
+            if  self.pulley_joints[i].body_a == index || self.pulley_joints[i].body_b == index {
+                self.remove_pulley_joint(i);
+                continue;
+            }
+            if self.pulley_joints[i].body_a > index {
+                self.pulley_joints[i].body_a -= 1;
+            }
+            if self.pulley_joints[i].body_b > index {
+                self.pulley_joints[i].body_b -= 1;
+            }
+            i += 1;
+        }
+        if let Some(mouse_joint) = &mut self.mouse_joint {
+            if mouse_joint.body == index {
+                self.mouse_joint = None;
+            } else if mouse_joint.body > index {
+                mouse_joint.body -= 1;
+            }
+        }
+        self.telemetry.retain(|telemetry| telemetry.body != index);
+        for telemetry in &mut self.telemetry {
+            if telemetry.body > index {
+                telemetry.body -= 1;
+            }
+        }
+        // A punctured outline can no longer hold pressure, the remaining springs stay
+        self.soft_bodies.retain(|soft_body| !soft_body.outline.contains(&index));
+        for soft_body in &mut self.soft_bodies {
+            soft_body.particles.retain(|&particle| particle != index);
+            for particle in soft_body.particles.iter_mut().chain(soft_body.outline.iter_mut()) {
+                if *particle > index {
+                    *particle -= 1;
+                }
+            }
+        }
+        for polygon in &mut self.polygons {
+            for connected in &mut polygon.connected_anchors {
+                if *connected > index {
+                    *connected -= 1;
+                }
+            }
+        }
         if ui_system.mouse_spring.is_some() && ui_system.mouse_spring.unwrap() > index {
             ui_system.mouse_spring = Some(ui_system.mouse_spring.unwrap() - 1);
         }
@@ -83,65 +198,103 @@ impl PhysicsSystem {
 
     pub fn remove_spring(&mut self, index: usize, ui_system: &mut UiSystem) {
         self.springs.remove(index);
-        if ui_system.mouse_spring.is_some() {
-            if ui_system.mouse_spring.unwrap() == index{
-                ui_system.mouse_spring = None;
-            }
-        }
+        Self::move_indices(&mut ui_system.mouse_spring, index);
         if ui_system.selected_spring.is_some() {
             ui_system.selected_spring = None;
         }
     }
-    pub fn remove_weld_joint(&mut self, index: usize) {
-        let a = self.weld_joints[index].body_a;
-        let b = self.weld_joints[index].body_b;
-        let a_i = self.weld_joints[index].a_index;
-        let b_i = self.weld_joints[index].b_index;
-        self.polygons[a].connected_anchors.remove(a_i);
-        self.polygons[b].connected_anchors.remove(b_i);
-        for weld_joint in &mut self.weld_joints{
-            if weld_joint.a_index > index {
-                weld_joint.a_index -= 1;
-            }
-            if weld_joint.b_index > index {
-                weld_joint.b_index -= 1;
+    /// Removes the joints and springs that exceeded their break threshold.
+    pub fn remove_broken(&mut self, ui_system: &mut UiSystem) {
+        for i in (0..self.weld_joints.len()).rev() {
+            if self.weld_joints[i].broken {
+                self.remove_weld_joint(i, ui_system);
             }
         }
-        for pivot_joint in &mut self.pivot_joints{
-            if pivot_joint.a_index > index {
-                pivot_joint.a_index -= 1;
+        for i in (0..self.pivot_joints.len()).rev() {
+            if self.pivot_joints[i].broken {
+                self.remove_pivot_joint(i, ui_system);
             }
-            if pivot_joint.b_index > index {
-                pivot_joint.b_index -= 1;
+        }
+        for i in (0..self.springs.len()).rev() {
+            if self.springs[i].broken {
+                self.remove_spring(i, ui_system);
             }
         }
-        self.weld_joints.remove(index);
     }
 
-    pub fn remove_pivot_joint(&mut self, index: usize) {
-        let a = self.pivot_joints[index].body_a;
-        let b = self.pivot_joints[index].body_b;
-        let a_i = self.pivot_joints[index].a_index;
-        let b_i = self.pivot_joints[index].b_index;
-        self.polygons[a].connected_anchors.remove(a_i);
-        self.polygons[b].connected_anchors.remove(b_i);
-        for weld_joint in &mut self.weld_joints{
-            if weld_joint.a_index > index {
-                weld_joint.a_index -= 1;
-            }
-            if weld_joint.b_index > index {
-                weld_joint.b_index -= 1;
-            }
+    /// Drops one collision filter entry in each direction, the bodies collide again once no joint links them.
+    fn disconnect(&mut self, a: usize, b: usize) {
+        if let Some(i) = self.polygons[a].connected_anchors.iter().position(|&other| other == b) {
+            self.polygons[a].connected_anchors.remove(i);
         }
-        for pivot_joint in &mut self.pivot_joints{
-            if pivot_joint.a_index > index {
-                pivot_joint.a_index -= 1;
-            }
-            if pivot_joint.b_index > index {
-                pivot_joint.b_index -= 1;
-            }
+        if let Some(i) = self.polygons[b].connected_anchors.iter().position(|&other| other == a) {
+            self.polygons[b].connected_anchors.remove(i);
         }
+    }
+
+    pub fn remove_weld_joint(&mut self, index: usize, ui_system: &mut UiSystem) {
+        self.disconnect(self.weld_joints[index].body_a, self.weld_joints[index].body_b);
+        self.weld_joints.remove(index);
+        Self::move_selected_joint(&mut ui_system.selected_joint, SelectedJoint::Weld(index));
+    }
+
+    pub fn remove_pivot_joint(&mut self, index: usize, ui_system: &mut UiSystem) {
+        self.disconnect(self.pivot_joints[index].body_a, self.pivot_joints[index].body_b);
         self.pivot_joints.remove(index);
+        self.remove_gear_target(GearTarget::Pivot(index));
+        Self::move_selected_joint(&mut ui_system.selected_joint, SelectedJoint::Pivot(index));
+    }
+
+    /// Keeps the selected joint pointing at the same joint after `removed` left its list.
+    fn move_selected_joint(selected: &mut Option<SelectedJoint>, removed: SelectedJoint) {
+        *selected = match (*selected, removed) {
+            (Some(SelectedJoint::Weld(i)), SelectedJoint::Weld(index)) if i == index => None,
+            (Some(SelectedJoint::Weld(i)), SelectedJoint::Weld(index)) if i > index => Some(SelectedJoint::Weld(i - 1)),
+            (Some(SelectedJoint::Pivot(i)), SelectedJoint::Pivot(index)) if i == index => None,
+            (Some(SelectedJoint::Pivot(i)), SelectedJoint::Pivot(index)) if i > index => Some(SelectedJoint::Pivot(i - 1)),
+            (selected, _) => selected,
+        };
+    }
+
+    pub fn remove_prismatic_joint(&mut self, index: usize) {
+        self.disconnect(self.prismatic_joints[index].body_a, self.prismatic_joints[index].body_b);
+        self.prismatic_joints.remove(index);
+        self.remove_gear_target(GearTarget::Prismatic(index));
+    }
+
+    /// Drops the gears driven by a removed joint and shifts the indices of those after it.
+    fn remove_gear_target(&mut self, removed: GearTarget) {
+        self.gear_joints.retain(|gear_joint| gear_joint.joint_a != removed && gear_joint.joint_b != removed);
+        for gear_joint in &mut self.gear_joints {
+            for target in [&mut gear_joint.joint_a, &mut gear_joint.joint_b] {
+                match (target, removed) {
+                    (GearTarget::Pivot(index), GearTarget::Pivot(removed_index)) if *index > removed_index => *index -= 1,
+                    (GearTarget::Prismatic(index), GearTarget::Prismatic(removed_index)) if *index > removed_index => *index -= 1,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    pub fn remove_distance_joint(&mut self, index: usize) {
+        self.distance_joints.remove(index);
+    }
+
+    pub fn remove_rope_joint(&mut self, index: usize) {
+        self.rope_joints.remove(index);
+    }
+
+    pub fn remove_wheel_joint(&mut self, index: usize) {
+        self.disconnect(self.wheel_joints[index].body_a, self.wheel_joints[index].body_b);
+        self.wheel_joints.remove(index);
+    }
+
+    pub fn remove_pulley_joint(&mut self, index: usize) {
+        self.pulley_joints.remove(index);
+    }
+
+    pub fn remove_gear_joint(&mut self, index: usize) {
+        self.gear_joints.remove(index);
     }
 
 }
@@ -169,8 +322,8 @@ impl UiSystem {
         let mouse_polygon =
             Rigidbody::rectangle(0.02, 0.02, position, 1.0, 1.0, ColorRGBA::white());
         for i in (0..physics_system.polygons.len()).rev() {
-            let result = sat_collision(&physics_system.polygons[i], &mouse_polygon);
-            if result[1].y != 0.0 && (self.spawn_ghost_polygon == None || i != self.spawn_ghost_polygon.unwrap()) && (self.spring_polygon == None || i != self.spring_polygon.unwrap()) {
+            let result = intersects(&physics_system.polygons[i], &mouse_polygon);
+            if result && (self.spawn_ghost_polygon == None || i != self.spawn_ghost_polygon.unwrap()) && (self.spring_polygon == None || i != self.spring_polygon.unwrap()) {
                 polygon_index = Some(i);
                 break;
             }
@@ -184,8 +337,8 @@ impl UiSystem {
         let mouse_polygon =
             Rigidbody::rectangle(0.02, 0.02, position, 1.0, 1.0, ColorRGBA::white());
         for i in (0..physics_system.polygons.len()).rev() {
-            let result = sat_collision(&physics_system.polygons[i], &mouse_polygon);
-            if result[1].y != 0.0 && (self.spawn_ghost_polygon == None || i != self.spawn_ghost_polygon.unwrap()) && (self.spring_polygon == None || i != self.spring_polygon.unwrap()) {
+            let result = intersects(&physics_system.polygons[i], &mouse_polygon);
+            if result && (self.spawn_ghost_polygon == None || i != self.spawn_ghost_polygon.unwrap()) && (self.spring_polygon == None || i != self.spring_polygon.unwrap()) {
                 polygons.push(i);
             }
         }
@@ -198,8 +351,8 @@ impl UiSystem {
             BodyBuilder::create_rigidbody(&self.spawn_parameters, &None);
         mouse_polygon.translate(position);
         for i in 0..physics_system.polygons.len() {
-            let result = sat_collision(&physics_system.polygons[i], &mouse_polygon);
-            if result[1].y != 0.0 && (self.spawn_ghost_polygon == None || i != self.spawn_ghost_polygon.unwrap()) && (self.spring_polygon == None || i != self.spring_polygon.unwrap()) {
+            let result = intersects(&physics_system.polygons[i], &mouse_polygon);
+            if result && (self.spawn_ghost_polygon == None || i != self.spawn_ghost_polygon.unwrap()) && (self.spring_polygon == None || i != self.spring_polygon.unwrap()) {
                 return false;
             }
         }
@@ -210,11 +363,26 @@ impl UiSystem {
         let position = self.get_mouse_world_position();
         let mouse_polygon =
             Rigidbody::rectangle(0.02, 0.02, position, 1.0, 1.0, ColorRGBA::white());
-        let result = sat_collision(polygon, &mouse_polygon);
-        if result[1].y != 0.0 {
-            return true;
+        intersects(polygon, &mouse_polygon)
+    }
+
+    /// Weld or pivot joint whose anchor marker is under the mouse.
+    pub fn get_joint_under_mouse(&self, physics_system: &mut PhysicsSystem) -> Option<SelectedJoint> {
+        for i in (0..physics_system.weld_joints.len()).rev() {
+            let mut polygon = BodyBuilder::create_joint();
+            polygon.move_to(physics_system.weld_joints[i].get_anchor_world_position(&physics_system.polygons));
+            if self.is_colliding_mouse(&polygon) {
+                return Some(SelectedJoint::Weld(i));
+            }
         }
-        false
+        for i in (0..physics_system.pivot_joints.len()).rev() {
+            let mut polygon = BodyBuilder::create_joint();
+            polygon.move_to(physics_system.pivot_joints[i].get_anchor_world_position(&physics_system.polygons));
+            if self.is_colliding_mouse(&polygon) {
+                return Some(SelectedJoint::Pivot(i));
+            }
+        }
+        None
     }
 
     pub fn get_spring_under_mouse(&self, physics_system: &mut PhysicsSystem) -> Option<usize> {
@@ -223,8 +391,8 @@ impl UiSystem {
         let mouse_spring =
             Rigidbody::rectangle(0.02, 0.02, position, 1.0, 1.0, ColorRGBA::white());
         for i in 0..physics_system.springs.len() {
-            let result = sat_collision(&mouse_spring, &physics_system.springs[i].connector);
-            if result[1].y != 0.0 && (self.spring_polygon == None || i != self.spring_polygon.unwrap()) {
+            let result = intersects(&mouse_spring, &physics_system.springs[i].connector);
+            if result && (self.spring_polygon == None || i != self.spring_polygon.unwrap()) {
                 spring_index = Some(i);
                 break;
             }
@@ -232,12 +400,26 @@ impl UiSystem {
         spring_index
     }
 
+    /// Body space anchor on `body` for a spring or joint placed at the world point `position`,
+    /// snapped according to the spawner settings.
+    pub fn spawn_anchor(&self, physics_system: &PhysicsSystem, body: usize, position: Vec2) -> Vec2 {
+        let polygon = &physics_system.polygons[body];
+        polygon.snap_anchor(polygon.inverse_transform_point(position), self.spawn_parameters.anchor_snap)
+    }
+
+    /// World point a spring or joint placed at `position` attaches to on the body under the mouse.
+    pub fn spawn_anchor_world_position(&self, physics_system: &mut PhysicsSystem, position: Vec2) -> Option<Vec2> {
+        let body = self.get_polygon_under_mouse(physics_system)?;
+        Some(physics_system.polygons[body].transform_point(self.spawn_anchor(physics_system, body, position)))
+    }
+
     pub fn create_mouse_ghost(&mut self, physics_system: &mut PhysicsSystem) {
         if self.spawn_ghost_polygon.is_some() {
-            physics_system.polygons.remove(self.spawn_ghost_polygon.unwrap());
+            // The drag preview body may have been pushed after the ghost, so indices need moving
+            physics_system.remove_rigidbody(self.spawn_ghost_polygon.unwrap(), self);
         }
         self.spawn_ghost_polygon = None;
-        if self.input_mode == InputMode::Spawn && (self.spawn_parameters.body_type == BodyType::Rectangle || self.spawn_parameters.body_type == BodyType::RegularPolygon) {
+        if self.input_mode == InputMode::Spawn && self.spawn_parameters.body_type.is_rigidbody() {
             physics_system.polygons.push(BodyBuilder::create_rigidbody(&self.spawn_parameters, &None));
             let length = physics_system.polygons.len() - 1;
             let position = self.get_mouse_world_position();
@@ -246,13 +428,29 @@ impl UiSystem {
             physics_system.polygons[length].collision = false;
             physics_system.polygons[length].gravity_multiplier = 0.0;
             self.spawn_ghost_polygon = Some(length);
-        } else if self.input_mode == InputMode::Spawn && (self.spawn_parameters.body_type == BodyType::WeldJoint || self.spawn_parameters.body_type == BodyType::PivotJoint) {
+        } else if self.input_mode == InputMode::Spawn && self.spawn_parameters.body_type.is_overlap_joint() {
             physics_system.polygons.push(BodyBuilder::create_joint());
             let length = physics_system.polygons.len() - 1;
-            let position = self.get_mouse_world_position();
+            let mut position = self.get_mouse_world_position();
+            if self.spawn_parameters.anchor_snap != AnchorSnap::Free {
+                position = self.spawn_anchor_world_position(physics_system, position).unwrap_or(position);
+            }
             physics_system.polygons[length].move_to(position);
             physics_system.polygons[length].change_color(ColorRGBA::new(1.0, 1.0, 1.0, 0.3));
             self.spawn_ghost_polygon = Some(length);
+        } else if self.input_mode == InputMode::Spawn
+            && self.spawn_parameters.body_type.is_drag_link()
+            && self.spawn_parameters.anchor_snap != AnchorSnap::Free
+        {
+            // Marks where the link would attach on the body under the mouse
+            let position = self.get_mouse_world_position();
+            if let Some(anchor) = self.spawn_anchor_world_position(physics_system, position) {
+                let mut marker = BodyBuilder::create_joint();
+                marker.move_to(anchor);
+                marker.change_color(ColorRGBA::new(1.0, 1.0, 1.0, 0.5));
+                physics_system.polygons.push(marker);
+                self.spawn_ghost_polygon = Some(physics_system.polygons.len() - 1);
+            }
         }
     }
 }
@@ -263,13 +461,6 @@ pub fn rotate_in_place(to_rotate: &mut Vec2, center: Vec2, angle: f32) -> &mut V
     to_rotate
 }
 
-pub fn rotate(to_rotate: Vec2, center: Vec2, angle: f32) -> Vec2 {
-    let rot = Mat2::from_angle(angle);
-    let mut start_vec = to_rotate;
-    start_vec = rot.mul_vec2(start_vec - center) + center;
-    start_vec
-}
-
 pub fn vec2_to_string(v: Vec2) -> String {
     let angle = v.to_angle() / PI * 180.0;
     let magnitude = v.length();
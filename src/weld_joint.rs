@@ -1,19 +1,24 @@
 use glam::{Vec2, Vec3, Mat3};
 use crate::Rigidbody;
-use crate::utility::{rotate};
 
 #[derive(Clone)]
 pub struct WeldJoint {
     pub(crate) body_a: usize,
     pub(crate) body_b: usize,
-    local_anchor_a: Vec2,
-    local_anchor_b: Vec2,
-    start_angle: f32,
-    reference_angle: f32,
-    pub a_index: usize,
-    pub b_index: usize,
+    pub(crate) local_anchor_a: Vec2,
+    pub(crate) local_anchor_b: Vec2,
+    pub(crate) reference_angle: f32,
 
     pub beta: f32,
+
+    /// Largest constraint force/torque the weld holds before it breaks, unbreakable when `None`.
+    pub break_force: Option<f32>,
+    pub break_torque: Option<f32>,
+    /// Constraint force and torque body b received during the last solve.
+    pub reaction_force: Vec2,
+    pub reaction_torque: f32,
+    /// Set once a break threshold was exceeded, a broken joint no longer acts on the bodies.
+    pub broken: bool,
 }
 
 impl WeldJoint {
@@ -30,9 +35,7 @@ impl WeldJoint {
             b = &mut right[0];
         }
         a.connected_anchors.push(body_b);
-        let a_index = a.connected_anchors.len() - 1;
         b.connected_anchors.push(body_a);
-        let b_index = b.connected_anchors.len() - 1;
         
         let reference_angle = b.angle - a.angle;
         Self {
@@ -40,15 +43,30 @@ impl WeldJoint {
             body_b,
             local_anchor_a,
             local_anchor_b,
-            a_index,
-            b_index,
-            start_angle: a.angle,
             reference_angle,
             beta: 0.05,
+            break_force: None,
+            break_torque: None,
+            reaction_force: Vec2::ZERO,
+            reaction_torque: 0.0,
+            broken: false,
         }
     }
 
-    pub fn solve_velocity_constraints(&self, rigidbodys: &mut Vec<Rigidbody>, dt: f32) {
+    pub fn set_break_force(&mut self, break_force: f32) -> &mut Self {
+        self.break_force = Some(break_force);
+        self
+    }
+
+    pub fn set_break_torque(&mut self, break_torque: f32) -> &mut Self {
+        self.break_torque = Some(break_torque);
+        self
+    }
+
+    pub fn solve_velocity_constraints(&mut self, rigidbodys: &mut Vec<Rigidbody>, dt: f32) {
+        if self.broken {
+            return;
+        }
         let a;
         let b;
         if self.body_a > self.body_b {
@@ -140,9 +158,18 @@ impl WeldJoint {
 
         b.velocity += lin_impulse * inv_mb;
         b.angular_velocity += inv_ib * (rb.perp_dot(lin_impulse) + ang_impulse);
+
+        self.reaction_force = lin_impulse / dt;
+        self.reaction_torque = ang_impulse / dt;
+        if let Some(break_force) = self.break_force && self.reaction_force.length() > break_force {
+            self.broken = true;
+        }
+        if let Some(break_torque) = self.break_torque && self.reaction_torque.abs() > break_torque {
+            self.broken = true;
+        }
     }
 
     pub fn get_anchor_world_position(&self, rigidbodys: &Vec<Rigidbody>) -> Vec2 {
-        rigidbodys[self.body_a].center + rotate(self.local_anchor_a, Vec2::ZERO, rigidbodys[self.body_a].angle - self.start_angle)
+        rigidbodys[self.body_a].transform_point(self.local_anchor_a)
     }
 }
@@ -0,0 +1,193 @@
+use glam::Vec2;
+use crate::distance_joint::{apply_impulse, create_link};
+use crate::{ColorRGBA, Rigidbody};
+
+/// Vehicle wheel: body b slides on a suspension spring along an axis fixed in body a and spins freely.
+#[derive(Clone)]
+pub struct WheelJoint {
+    local_anchor_a: Vec2,
+    local_anchor_b: Vec2,
+    /// Unit suspension direction in body a's local frame.
+    local_axis_a: Vec2,
+    pub(crate) body_a: usize,
+    pub(crate) body_b: usize,
+
+    /// Baumgarte stabilization factor for positional drift (small: 0.01..0.2)
+    pub beta: f32,
+
+    /// Suspension spring along the axis, a stiffness of zero leaves the wheel free to slide.
+    pub stiffness: f32,
+    pub damping: f32,
+
+    /// Drives the wheel's spin relative to body a towards `motor_speed` using at most `max_motor_torque`.
+    pub enable_motor: bool,
+    pub motor_speed: f32,
+    pub max_motor_torque: f32,
+
+    /// Keeps the suspension travel between `lower_translation` and `upper_translation`.
+    pub enable_limit: bool,
+    pub lower_translation: f32,
+    pub upper_translation: f32,
+}
+
+impl WheelJoint {
+    pub fn new(local_anchor_a: Vec2, local_anchor_b: Vec2, local_axis_a: Vec2, rigidbodys: &mut [Rigidbody], body_a: usize, body_b: usize) -> Self {
+        let a;
+        let b;
+        if body_a > body_b {
+            let (left, right) = rigidbodys.split_at_mut(body_a);
+            b = &mut left[body_b];
+            a = &mut right[0];
+        } else {
+            let (left, right) = rigidbodys.split_at_mut(body_b);
+            a = &mut left[body_a];
+            b = &mut right[0];
+        }
+        a.connected_anchors.push(body_b);
+        b.connected_anchors.push(body_a);
+
+        Self {
+            local_anchor_a,
+            local_anchor_b,
+            local_axis_a: local_axis_a.normalize_or(Vec2::Y),
+            body_a,
+            body_b,
+            beta: 0.1,
+            stiffness: 0.0,
+            damping: 0.0,
+            enable_motor: false,
+            motor_speed: 0.0,
+            max_motor_torque: 0.0,
+            enable_limit: false,
+            lower_translation: 0.0,
+            upper_translation: 0.0,
+        }
+    }
+
+    pub fn set_spring(&mut self, stiffness: f32, damping: f32) -> &mut Self {
+        self.stiffness = stiffness.max(0.0);
+        self.damping = damping.max(0.0);
+        self
+    }
+
+    pub fn set_motor(&mut self, motor_speed: f32, max_motor_torque: f32) -> &mut Self {
+        self.enable_motor = true;
+        self.motor_speed = motor_speed;
+        self.max_motor_torque = max_motor_torque;
+        self
+    }
+
+    pub fn set_limits(&mut self, lower_translation: f32, upper_translation: f32) -> &mut Self {
+        self.enable_limit = true;
+        self.lower_translation = lower_translation.min(upper_translation);
+        self.upper_translation = upper_translation.max(lower_translation);
+        self
+    }
+
+    /// Suspension compression, distance of anchor b from anchor a measured along the axis.
+    pub fn joint_translation(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        let a = &rigidbodys[self.body_a];
+        let b = &rigidbodys[self.body_b];
+        let d = b.transform_point(self.local_anchor_b) - a.transform_point(self.local_anchor_a);
+        d.dot(a.rotation_matrix() * self.local_axis_a)
+    }
+
+    /// Elastic energy of the suspension spring.
+    pub fn potential_energy(&self, rigidbodys: &[Rigidbody]) -> f32 {
+        let translation = self.joint_translation(rigidbodys);
+        0.5 * self.stiffness * translation * translation
+    }
+
+    /// World space suspension direction.
+    pub fn get_axis(&self, rigidbodys: &[Rigidbody]) -> Vec2 {
+        rigidbodys[self.body_a].rotation_matrix() * self.local_axis_a
+    }
+
+    pub fn solve_velocity_constraints(&self, rigidbodys: &mut [Rigidbody], dt: f32) {
+        let a;
+        let b;
+        if self.body_a > self.body_b {
+            let (left, right) = rigidbodys.split_at_mut(self.body_a);
+            b = &mut left[self.body_b];
+            a = &mut right[0];
+        } else {
+            let (left, right) = rigidbodys.split_at_mut(self.body_b);
+            a = &mut left[self.body_a];
+            b = &mut right[0];
+        }
+
+        let ra = a.rotation_matrix() * self.local_anchor_a;
+        let rb = b.rotation_matrix() * self.local_anchor_b;
+        let d = (b.center + rb) - (a.center + ra);
+        let axis = a.rotation_matrix() * self.local_axis_a;
+
+        let inv_ma = 1.0 / a.mass;
+        let inv_mb = 1.0 / b.mass;
+        let inv_ia = 1.0 / a.moment_of_inertia;
+        let inv_ib = 1.0 / b.moment_of_inertia;
+
+        if self.enable_motor && inv_ia + inv_ib > 0.0 {
+            let max_impulse = self.max_motor_torque * dt;
+            let relative_speed = b.angular_velocity - a.angular_velocity;
+            let impulse = (-(relative_speed - self.motor_speed) / (inv_ia + inv_ib)).clamp(-max_impulse, max_impulse);
+            a.angular_velocity -= inv_ia * impulse;
+            b.angular_velocity += inv_ib * impulse;
+        }
+
+        // Axial lever arms, body a's arm reaches to anchor b since the axis is fixed in a
+        let a1 = (d + ra).perp_dot(axis);
+        let a2 = rb.perp_dot(axis);
+        let axial_mass = inv_ma + inv_mb + inv_ia * a1 * a1 + inv_ib * a2 * a2;
+        let axial_speed = |a: &Rigidbody, b: &Rigidbody| {
+            axis.dot(b.velocity - a.velocity) + a2 * b.angular_velocity - a1 * a.angular_velocity
+        };
+        let translation = d.dot(axis);
+
+        // Soft constraint, `gamma` softens the effective mass and `bias` pulls the translation back to zero
+        let softness = dt * (self.damping + dt * self.stiffness);
+        if softness > 0.0 && axial_mass > 0.0 {
+            let gamma = 1.0 / softness;
+            let bias = translation * dt * self.stiffness * gamma;
+            let impulse = -(axial_speed(a, b) + bias) / (axial_mass + gamma);
+            apply_impulse(a, b, axis, a1, a2, impulse);
+        }
+
+        if self.enable_limit && axial_mass > 0.0 {
+            // Speculative while inside the range, Baumgarte once the limit has been passed
+            let bias = |separation: f32| {
+                if separation > 0.0 { separation / dt } else { self.beta * separation / dt }
+            };
+
+            let lower_separation = translation - self.lower_translation;
+            let impulse = (-(axial_speed(a, b) + bias(lower_separation)) / axial_mass).max(0.0);
+            apply_impulse(a, b, axis, a1, a2, impulse);
+
+            let upper_separation = self.upper_translation - translation;
+            let impulse = (-(-axial_speed(a, b) + bias(upper_separation)) / axial_mass).max(0.0);
+            apply_impulse(a, b, axis, a1, a2, -impulse);
+        }
+
+        // Keep anchor b on the line through anchor a
+        let perp = axis.perp();
+        let s1 = (d + ra).perp_dot(perp);
+        let s2 = rb.perp_dot(perp);
+        let perp_mass = inv_ma + inv_mb + inv_ia * s1 * s1 + inv_ib * s2 * s2;
+        if perp_mass > 0.0 {
+            let c_dot = perp.dot(b.velocity - a.velocity) + s2 * b.angular_velocity - s1 * a.angular_velocity;
+            let bias = (self.beta / dt) * perp.dot(d);
+            let impulse = -(c_dot + bias) / perp_mass;
+            apply_impulse(a, b, perp, s1, s2, impulse);
+        }
+    }
+
+    pub fn get_anchor_world_position(&self, rigidbodys: &[Rigidbody]) -> Vec2 {
+        rigidbodys[self.body_b].transform_point(self.local_anchor_b)
+    }
+
+    /// Short line along the suspension axis through the wheel's anchor.
+    pub fn create_axis(&self, rigidbodys: &[Rigidbody]) -> Rigidbody {
+        let anchor = self.get_anchor_world_position(rigidbodys);
+        let axis = self.get_axis(rigidbodys);
+        create_link(anchor - axis * 0.25, anchor + axis * 0.25, ColorRGBA::new(1.0, 1.0, 1.0, 0.5))
+    }
+}
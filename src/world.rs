@@ -5,6 +5,7 @@ use crate::input::UiSystem;
 use crate::physics::PhysicsSystem;
 use crate::render::RenderSystem;
 use crate::timing::Timing;
+use crate::{DistanceJoint, FluidRegion, ForceField, PairwiseConstants, ParticleFluid, GearJoint, PivotJoint, PrismaticJoint, PulleyJoint, RopeJoint, SoftBody, WeldJoint, WheelJoint};
 
 #[derive(Clone)]
 pub struct Camera {
@@ -23,7 +24,69 @@ pub struct Parameters {
     pub clear_color: ColorRGBA,
     pub is_running: bool,
     pub initial_camera: Camera,
+    /// Strength of the N-body gravity, bodies attract with the product of their masses and gravity multipliers.
     pub gravitational_constant: f32,
+    /// Strength of the Coulomb force between charged bodies.
+    pub coulomb_constant: f32,
+    /// Strength of the forces and torques between magnetic dipoles.
+    pub magnetic_constant: f32,
+    /// Length added in quadrature to the distances of the pairwise forces, zero for the exact laws.
+    pub softening: f32,
+    /// Draws the electric and magnetic field lines.
+    pub field_lines: bool,
+    /// Seconds of motion drawn ahead of every moving body under the pairwise forces and gravity, zero draws none.
+    /// Recomputed whenever a body moves and skipped in scenes with more than 200 bodies.
+    pub prediction_time: f32,
+}
+
+impl Default for Parameters {
+    /// Real time stepping with gravity on and every pairwise force off, scenes override what they need with
+    /// `..Default::default()`.
+    fn default() -> Self {
+        Self {
+            delta_time: 0.0,
+            updates_per_frame: 1,
+            time_multiplier: 1.0,
+            angular_velocity: true,
+            gravity: true,
+            world_size: 300.0,
+            gravity_force: Vec2::new(0.0, -9.81),
+            clear_color: ColorRGBA::new(0.0, 0.0, 0.0, 1.0),
+            is_running: false,
+            initial_camera: Camera {
+                camera_pos: Vec4::new(0.0, 0.0, 0.0, -10.0),
+                scaling_factor: 10.0,
+            },
+            gravitational_constant: 0.0,
+            coulomb_constant: 0.0,
+            magnetic_constant: 0.0,
+            softening: 0.0,
+            field_lines: false,
+            prediction_time: 0.0,
+        }
+    }
+}
+
+/// Everything besides the bodies and springs a scene can start with, see [`run_scene`](crate::run_scene).
+#[derive(Clone, Default)]
+pub struct Scene {
+    pub weld_joints: Vec<WeldJoint>,
+    pub pivot_joints: Vec<PivotJoint>,
+    pub prismatic_joints: Vec<PrismaticJoint>,
+    pub distance_joints: Vec<DistanceJoint>,
+    pub rope_joints: Vec<RopeJoint>,
+    pub wheel_joints: Vec<WheelJoint>,
+    pub pulley_joints: Vec<PulleyJoint>,
+    /// Gears refer to `pivot_joints` and `prismatic_joints` by index.
+    pub gear_joints: Vec<GearJoint>,
+    /// Pressure models of soft bodies, their springs belong in the spring list.
+    pub soft_bodies: Vec<SoftBody>,
+    /// Wind, attractor and vortex fields acting on the bodies inside their regions.
+    pub force_fields: Vec<ForceField>,
+    /// Water volumes buoying up the bodies inside them.
+    pub fluid_regions: Vec<FluidRegion>,
+    /// Particle liquids splashing around and pushing on the bodies.
+    pub particle_fluids: Vec<ParticleFluid>,
 }
 
 pub struct World {
@@ -63,7 +126,14 @@ impl World {
         if parameters.is_running == true { 
             physics.update_physics(&parameters);
         }
-        physics.energy.update_energy(&physics.polygons, &physics.springs, parameters);
+        let constraint_energy = physics.calculate_constraint_energy();
+        physics.energy.update_energy(&physics.polygons, &physics.springs, constraint_energy, parameters);
+        if parameters.is_running {
+            physics.energy_history.record(&physics.energy, physics.time);
+            for telemetry in &mut physics.telemetry {
+                telemetry.record(&physics.polygons, physics.time, physics.dt);
+            }
+        }
     }
 
     pub(crate) fn update(&mut self) {
@@ -90,7 +160,7 @@ impl World {
 
 
         if self.ui.is_pointer_used == false {
-            self.ui.handle_input(&mut self.physics, &mut self.color_system);
+            self.ui.handle_input(&mut self.physics, &mut self.color_system, &self.parameters);
         }
 
         for i in 0..self.physics.polygons.len() {
@@ -102,7 +172,16 @@ impl World {
                 self.physics.remove_rigidbody(i, &mut self.ui);
             }
         }
-        
+        if self.parameters.world_size > 0.0 {
+            for particle_fluid in &mut self.physics.particle_fluids {
+                particle_fluid.particles.retain(|particle| particle.position.length() <= self.parameters.world_size);
+            }
+        }
+
+        self.physics.remove_broken(&mut self.ui);
+        let gravity = if self.parameters.gravity { self.parameters.gravity_force } else { Vec2::ZERO };
+        let constants = PairwiseConstants::new(&self.parameters);
+        self.physics.trajectory_prediction.update(&self.physics.polygons, &constants, gravity, self.parameters.prediction_time);
         self.ui.create_mouse_ghost(&mut self.physics);
     }
     
@@ -4,26 +4,25 @@ use egui_wgpu::wgpu;
 use egui_wgpu::wgpu::util::DeviceExt;
 use glam::Vec2;
 use winit::window::Window;
-use crate::{ColorRGBA, Parameters, Rigidbody, World};
-use crate::body_builder::{BodyBuilder, RigidbodyParams, SpringParams};
+use crate::{AnchorSnap, ChainParams, ColorRGBA, Explosion, Falloff, ForceField, Parameters, Rigidbody, Scene, SoftBodyParams, World};
+use crate::body_builder::{BodyBuilder, DragParams, FluidSpawnParams, ForceFieldSpawnParams, JointParams, ParticleSpawnParams, RigidbodyParams, SoftBodySpawnParams, SpringParams};
 use crate::color::{ColorRange, ColorSystem, PaletteParams};
 use crate::egui_tools::EguiRenderer;
 use crate::enums::{BodyType, ColorType, DraggingState, InputMode, Menu};
 use crate::input::UiSystem;
+use crate::material::{Material, MATERIAL_PRESETS_PATH};
+use crate::orbit::TrajectoryPrediction;
 use crate::physics::PhysicsSystem;
-use crate::pivot_joint::PivotJoint;
 use crate::render::{RenderSystem, Uniforms, Vertex};
-use crate::spring::Spring;
+use crate::spring::{Spring, SpringLaw};
 use crate::timing::Timing;
-use crate::weld_joint::WeldJoint;
 
 impl World{
     pub(crate) async fn new(
         window: Arc<Window>,
         polygons: Vec<Rigidbody>,
         springs: Vec<Spring>,
-        weld_joints: Vec<WeldJoint>,
-        pivot_joints: Vec<PivotJoint>,
+        scene: Scene,
         parameters: Parameters,
     ) -> Self{
         let size = window.inner_size();
@@ -33,7 +32,31 @@ impl World{
             aspect_ratio,
             padding: [0.0; 7],
         };
-        let (vertices, indices) = World::get_vertices_and_indices(&polygons, &springs, &weld_joints, &pivot_joints);
+        let physics: PhysicsSystem = PhysicsSystem{
+            springs,
+            polygons,
+            weld_joints: scene.weld_joints,
+            pivot_joints: scene.pivot_joints,
+            prismatic_joints: scene.prismatic_joints,
+            distance_joints: scene.distance_joints,
+            rope_joints: scene.rope_joints,
+            wheel_joints: scene.wheel_joints,
+            pulley_joints: scene.pulley_joints,
+            gear_joints: scene.gear_joints,
+            soft_bodies: scene.soft_bodies,
+            force_fields: scene.force_fields,
+            fluid_regions: scene.fluid_regions,
+            particle_fluids: scene.particle_fluids,
+            mouse_joint: None,
+            dt: 0.000001,
+            energy: Default::default(),
+            energy_history: Default::default(),
+            telemetry: vec![],
+            break_events: vec![],
+            time: 0.0,
+            trajectory_prediction: TrajectoryPrediction::default(),
+        };
+        let (vertices, indices) = World::get_vertices_and_indices(&physics, &parameters);
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
             backends: wgpu::Backends::PRIMARY,
             ..Default::default()
@@ -198,9 +221,12 @@ impl World{
                 radius: 0.3533,
                 pos: Vec2::ZERO,
                 mass: 1.0,
+                use_density: false,
+                density: 1.0,
                 width: 0.5,
                 height: 0.5,
                 restitution: 0.8,
+                friction: 0.0,
                 color: None,
                 collides: true,
                 rotation: 0.0,
@@ -208,7 +234,10 @@ impl World{
                 velocity: Vec2::ZERO,
                 color_type: ColorType::Random,
                 gravity_multiplier: 1.0,
+                charge: 0.0,
+                magnetic_moment: 0.0,
                 eternal: false,
+                orbit_eccentricity: None,
             },
 
             spring_params: SpringParams {
@@ -219,7 +248,39 @@ impl World{
                 body_b: 0,
                 anchor_a: Default::default(),
                 anchor_b: Default::default(),
+                break_force: None,
+                law: SpringLaw::Linear,
+            },
+            joint_params: JointParams::default(),
+            soft_body_params: SoftBodySpawnParams {
+                grid: false,
+                resolution: 16,
+                size: 0.6,
+                params: SoftBodyParams { pressure: 20.0, ..Default::default() },
+            },
+            chain_params: ChainParams::default(),
+            force_field_params: ForceFieldSpawnParams {
+                field: ForceField::radial(Vec2::ZERO, 5.0),
+                bounded: false,
+                region_size: Vec2::new(4.0, 4.0),
+            },
+            fluid_params: FluidSpawnParams {
+                size: Vec2::new(6.0, 3.0),
+                density: 1.0,
+                linear_drag: 2.0,
+                angular_drag: 1.0,
+            },
+            particle_params: ParticleSpawnParams {
+                size: Vec2::new(1.0, 1.0),
+                spacing: 0.05,
             },
+            explosion: Explosion::new(Vec2::ZERO, 5.0, 50.0, Falloff::Linear),
+            anchor_snap: AnchorSnap::Free,
+            materials: Material::presets()
+                .into_iter()
+                .chain(Material::load_presets(MATERIAL_PRESETS_PATH))
+                .collect(),
+            material_name: String::from("Custom"),
         };
         let palette_params = PaletteParams {
             start_range: ColorRange { x: Range{start: 0.0, end: 30.0}, y: Range{start: 65.0, end: 75.0}, z: Range{start: 45.0, end: 55.0} },
@@ -255,15 +316,6 @@ impl World{
             runtime: 0.0,
             test: false,
         };
-        let physics: PhysicsSystem = PhysicsSystem{
-            springs,
-            polygons,
-            weld_joints,
-            pivot_joints,
-            dt: 0.000001,
-            energy: Default::default(),
-        };
-
         let color_system: ColorSystem = ColorSystem {
             palette_params,
             color_palette: None,
@@ -278,18 +330,20 @@ impl World{
             is_pointer_used: false,
             selected_polygon: None,
             selected_spring: None,
+            selected_joint: None,
             spring_polygon: None,
             mouse_spring: None,
             spawn_ghost_polygon: None,
             input_mode: InputMode::Spawn,
             dragging: DraggingState::NotDragging,
             menus,
-            drag_params: SpringParams {
-                stiffness: 10.0,
-                dampening: 1.0,
-                ..Default::default()
+            drag_params: DragParams {
+                max_force: 1000.0,
+                frequency: 5.0,
+                damping_ratio: 0.7,
             },
             spawn_parameters,
+            orbit_eccentricity: 0.0,
             camera: parameters.initial_camera.clone(),
             window_dimensions: Vec2::new(render.config.width as f32, render.config.height as f32),
         };